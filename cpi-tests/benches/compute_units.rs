@@ -0,0 +1,251 @@
+//! Compute units of every CPI, of serializing their data, and of the readers on the fixture accounts.
+//! The results are compared with the last table of `benches/compute_units.md`, a new table is added
+//! on top of it when anything changed. Commit it along with the change that moved the numbers.
+//!
+//! The programs must be built first, see the `README.md` at the root of the repository. The CPIs
+//! include what the stub costs to echo what it received, `noop` is what the caller costs before
+//! doing anything
+
+use caller::{
+    BURN, BURN_COLLECTION, CAN_TRANSFER, CREATE_ASSET, CREATE_COLLECTION, NOOP, PATCH_ATTRIBUTES,
+    PRINT_EDITION, READ_ATTRIBUTES, READ_ROYALTIES, RESOLVE_ROYALTIES, SERIALIZE, TRANSFER,
+    TRY_SERIALIZE, UPDATE_ASSET_PLUGIN, UPDATE_COLLECTION_PLUGIN,
+};
+use mollusk_svm::{program, Mollusk};
+use mollusk_svm_bencher::MolluskComputeUnitBencher;
+use solana_account::Account;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+const CALLER_ID: Pubkey = Pubkey::new_from_array([9; 32]);
+const MPL_CORE_ID: Pubkey = Pubkey::new_from_array(mpl_core_pinocchio::MPL_CORE_ID);
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array(mpl_core_pinocchio::SYSTEM_PROGRAM_ID);
+const SPL_NOOP_ID: Pubkey = Pubkey::new_from_array(mpl_core_pinocchio::SPL_NOOP_ID);
+
+const ASSET: Pubkey = Pubkey::new_from_array([10; 32]);
+const COLLECTION: Pubkey = Pubkey::new_from_array([11; 32]);
+const AUTHORITY: Pubkey = Pubkey::new_from_array([12; 32]);
+const PAYER: Pubkey = Pubkey::new_from_array([13; 32]);
+const OWNER: Pubkey = Pubkey::new_from_array([14; 32]);
+const UPDATE_AUTHORITY: Pubkey = Pubkey::new_from_array([15; 32]);
+const NEW_OWNER: Pubkey = Pubkey::new_from_array([16; 32]);
+
+/// An asset in a collection, with Royalties and Attributes
+const ASSET_WITH_ATTRIBUTES: &[u8] =
+    include_bytes!("../../tests/fixtures/asset_royalties_attributes.bin");
+/// An asset in a collection, with Edition, VerifiedCreators, Autograph, UpdateDelegate and FreezeDelegate
+const ASSET_WITH_SIGNATURES: &[u8] =
+    include_bytes!("../../tests/fixtures/asset_edition_signatures.bin");
+/// A collection with MasterEdition, Royalties and PermanentFreezeDelegate
+const COLLECTION_WITH_MASTER_EDITION: &[u8] =
+    include_bytes!("../../tests/fixtures/collection_master_edition.bin");
+
+fn account(owner: Pubkey, data: &[u8]) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data: data.to_vec(),
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+struct Case {
+    name: &'static str,
+    instruction: Instruction,
+    accounts: Vec<(Pubkey, Account)>,
+}
+
+impl Case {
+    /// Same as the CPI tests: every optional account is set, assets and collections are owned by mpl core
+    fn new(
+        name: &'static str,
+        case: u8,
+        accounts: &[AccountMeta],
+        asset_data: &[u8],
+        collection_data: &[u8],
+    ) -> Self {
+        let mut keyed_accounts = vec![
+            program::keyed_account_for_system_program(),
+            (
+                MPL_CORE_ID,
+                program::create_program_account_loader_v3(&MPL_CORE_ID),
+            ),
+        ];
+        for meta in accounts {
+            if keyed_accounts.iter().any(|(key, _)| key == &meta.pubkey) {
+                continue;
+            }
+            let account = match meta.pubkey {
+                ASSET => account(MPL_CORE_ID, asset_data),
+                COLLECTION => account(MPL_CORE_ID, collection_data),
+                _ => account(SYSTEM_PROGRAM_ID, &[]),
+            };
+            keyed_accounts.push((meta.pubkey, account));
+        }
+
+        Self {
+            name,
+            instruction: Instruction::new_with_bytes(CALLER_ID, &[case, 0xff], accounts.to_vec()),
+            accounts: keyed_accounts,
+        }
+    }
+
+    fn reader(name: &'static str, case: u8, asset_data: &[u8], collection_data: &[u8]) -> Self {
+        Self::new(
+            name,
+            case,
+            &[
+                AccountMeta::new_readonly(ASSET, false),
+                AccountMeta::new_readonly(COLLECTION, false),
+                AccountMeta::new_readonly(AUTHORITY, false),
+            ],
+            asset_data,
+            collection_data,
+        )
+    }
+}
+
+fn main() {
+    let create_asset = [
+        AccountMeta::new(ASSET, true),
+        AccountMeta::new(COLLECTION, false),
+        AccountMeta::new_readonly(AUTHORITY, true),
+        AccountMeta::new(PAYER, true),
+        AccountMeta::new_readonly(OWNER, false),
+        AccountMeta::new_readonly(UPDATE_AUTHORITY, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SPL_NOOP_ID, false),
+        AccountMeta::new_readonly(MPL_CORE_ID, false),
+    ];
+    let update_asset = [
+        AccountMeta::new(ASSET, false),
+        AccountMeta::new(COLLECTION, false),
+        AccountMeta::new(PAYER, true),
+        AccountMeta::new_readonly(AUTHORITY, true),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SPL_NOOP_ID, false),
+        AccountMeta::new_readonly(MPL_CORE_ID, false),
+    ];
+
+    let cases = [
+        Case::new("noop", NOOP, &[], &[], &[]),
+        Case::new("serialize_to", SERIALIZE, &[], &[], &[]),
+        Case::new("try_serialize_to", TRY_SERIALIZE, &[], &[], &[]),
+        Case::new("create_asset", CREATE_ASSET, &create_asset, &[], &[]),
+        Case::new(
+            "print_edition",
+            PRINT_EDITION,
+            &create_asset,
+            &[],
+            COLLECTION_WITH_MASTER_EDITION,
+        ),
+        Case::new(
+            "create_collection",
+            CREATE_COLLECTION,
+            &[
+                AccountMeta::new(COLLECTION, true),
+                AccountMeta::new_readonly(UPDATE_AUTHORITY, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        ),
+        Case::new(
+            "transfer",
+            TRANSFER,
+            &[
+                AccountMeta::new(ASSET, false),
+                AccountMeta::new_readonly(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new_readonly(NEW_OWNER, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        ),
+        Case::new("burn", BURN, &update_asset, &[], &[]),
+        Case::new(
+            "burn_collection",
+            BURN_COLLECTION,
+            &[
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(AUTHORITY, true),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        ),
+        Case::new(
+            "update_asset_plugin",
+            UPDATE_ASSET_PLUGIN,
+            &update_asset,
+            &[],
+            &[],
+        ),
+        Case::new(
+            "patch_attributes",
+            PATCH_ATTRIBUTES,
+            &update_asset,
+            ASSET_WITH_ATTRIBUTES,
+            &[],
+        ),
+        Case::new(
+            "update_collection_plugin",
+            UPDATE_COLLECTION_PLUGIN,
+            &[
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        ),
+        Case::reader(
+            "read_royalties_asset",
+            READ_ROYALTIES,
+            ASSET_WITH_ATTRIBUTES,
+            &[],
+        ),
+        Case::reader(
+            "read_attributes",
+            READ_ATTRIBUTES,
+            ASSET_WITH_ATTRIBUTES,
+            &[],
+        ),
+        Case::reader(
+            "resolve_royalties_from_collection",
+            RESOLVE_ROYALTIES,
+            ASSET_WITH_SIGNATURES,
+            COLLECTION_WITH_MASTER_EDITION,
+        ),
+        Case::reader(
+            "can_transfer",
+            CAN_TRANSFER,
+            ASSET_WITH_SIGNATURES,
+            COLLECTION_WITH_MASTER_EDITION,
+        ),
+    ];
+
+    // the programs are looked up in SBF_OUT_DIR, which defaults to target/deploy
+    let mut mollusk = Mollusk::new(&CALLER_ID, "caller");
+    mollusk.add_program(&MPL_CORE_ID, "mpl_core_stub");
+
+    cases
+        .iter()
+        .fold(MolluskComputeUnitBencher::new(mollusk), |bencher, case| {
+            bencher.bench((case.name, &case.instruction, &case.accounts))
+        })
+        .must_pass(true)
+        .execute();
+}
@@ -0,0 +1,363 @@
+//! Invokes one of the CPI structs of mpl_core_pinocchio, picked by the first byte of the instruction data.
+//! The accounts are passed in the order of the struct's fields. The second byte has a bit for each
+//! optional account, in the same order, telling whether it is `Some` or `None`
+//!
+//! The cases after `PRINT_EDITION` don't invoke anything, they are only there to be benchmarked.
+//! The readers take the asset, then the collection (optional, bit 0), then the authority
+
+#![no_std]
+
+use core::hint::black_box;
+
+use mpl_core_pinocchio::{
+    data::{
+        burn_asset::BurnAssetV1InstructionData,
+        burn_collection::BurnCollectionV1InstructionData,
+        create_asset::{CreateAssetV1InstructionData, DataState, PrintEditionV1InstructionData},
+        create_collection::CreateCollectionV1InstructionData,
+        plugins::{
+            read_royalties_asset, AttributeOp, Creator, FreezeDelegate, PermanentFreezeDelegate,
+            Plugin, PluginAuthority, PluginAuthorityPair, Royalties, RoyaltiesInfo, RuleSet,
+        },
+        transfer::TransferV1InstructionData,
+        update_asset_plugin::{
+            UpdateAssetPluginPatchV1InstructionData, UpdateAssetPluginV1InstructionData,
+        },
+        update_collection_plugin::UpdateCollectionPluginV1InstructionData,
+        view::{AssetView, CollectionView},
+        Serialize,
+    },
+    error::MplCoreError,
+    instructions::{
+        burn_asset::BurnV1, burn_collection::BurnCollectionV1, create_asset::CreateAssetV1,
+        create_collection::CreateCollectionV1, transfer::TransferV1,
+        update_asset_plugin::UpdateAssetPluginV1,
+        update_collection_plugin::UpdateCollectionPluginV1,
+    },
+};
+use pinocchio::{
+    account_info::AccountInfo, default_allocator, nostd_panic_handler, program_entrypoint,
+    program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+#[cfg(all(target_arch = "bpf", target_os = "none"))]
+use solana_compiler_builtins as _;
+
+program_entrypoint!(process_instruction);
+default_allocator!();
+nostd_panic_handler!();
+
+pub const CREATE_ASSET: u8 = 0;
+pub const CREATE_COLLECTION: u8 = 1;
+pub const TRANSFER: u8 = 2;
+pub const BURN: u8 = 3;
+pub const BURN_COLLECTION: u8 = 4;
+pub const UPDATE_ASSET_PLUGIN: u8 = 5;
+pub const UPDATE_COLLECTION_PLUGIN: u8 = 6;
+/// UpdateAssetPluginV1 with an Attributes patch, the asset must have an Attributes plugin
+pub const PATCH_ATTRIBUTES: u8 = 7;
+/// CreateAssetV1 printing the next edition, the collection must have a MasterEdition plugin
+pub const PRINT_EDITION: u8 = 8;
+/// Does nothing, what every other case costs on top of the entrypoint
+pub const NOOP: u8 = 9;
+/// Serializes the CreateAssetV1 data of `CREATE_ASSET` with `serialize_to`
+pub const SERIALIZE: u8 = 10;
+/// Same as `SERIALIZE` with `try_serialize_to`, which computes the size first like the CPIs do
+pub const TRY_SERIALIZE: u8 = 11;
+/// `read_royalties_asset`
+pub const READ_ROYALTIES: u8 = 12;
+/// `AssetView::new`, then reads the `level` attribute
+pub const READ_ATTRIBUTES: u8 = 13;
+/// `RoyaltiesInfo::resolve` and `compute_payouts`
+pub const RESOLVE_ROYALTIES: u8 = 14;
+/// `AssetView::can_transfer`
+pub const CAN_TRANSFER: u8 = 15;
+
+pub const CREATOR: Pubkey = [1; 32];
+
+const CREATORS: [Creator; 1] = [Creator {
+    address: CREATOR,
+    percentage: 100,
+}];
+
+fn create_asset_data<'a>(
+    plugins: &'a [PluginAuthorityPair<'a>],
+) -> CreateAssetV1InstructionData<'a> {
+    CreateAssetV1InstructionData {
+        data_state: DataState::AccountState,
+        name: b"Asset",
+        uri: b"https://example.com/asset.json",
+        plugins: Some(plugins),
+    }
+}
+
+fn create_asset_plugins() -> [PluginAuthorityPair<'static>; 2] {
+    [
+        PluginAuthorityPair {
+            plugin: Plugin::Royalties(Royalties {
+                basis_points: 500,
+                creators: &CREATORS,
+                rule_set: RuleSet::None,
+            }),
+            authority: None,
+        },
+        PluginAuthorityPair {
+            plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+            authority: Some(PluginAuthority::Owner),
+        },
+    ]
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [case, optional] = *instruction_data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let some = |bit: u8, account| (optional & (1 << bit) != 0).then_some(account);
+
+    let mut buffer = [0; 512];
+
+    match case {
+        CREATE_ASSET | PRINT_EDITION => {
+            let [asset, collection, authority, payer, owner, update_authority, system_program, log_wrapper, mpl_core] =
+                accounts
+            else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let create = CreateAssetV1 {
+                asset,
+                collection: some(0, collection),
+                authority: some(1, authority),
+                payer,
+                owner: some(2, owner),
+                update_authority: some(3, update_authority),
+                system_program,
+                log_wrapper: some(4, log_wrapper),
+                mpl_core,
+            };
+
+            if case == PRINT_EDITION {
+                // only the edition number is read, the collection must not be borrowed during the CPI
+                let print = {
+                    let data = collection.try_borrow_data()?;
+                    PrintEditionV1InstructionData::next(
+                        &CollectionView::new(&data)?,
+                        b"Print",
+                        b"https://example.com/print.json",
+                    )?
+                };
+                return create.invoke_print_edition(&print, &mut buffer);
+            }
+
+            create.invoke(&create_asset_data(&create_asset_plugins()), &mut buffer)
+        }
+        CREATE_COLLECTION => {
+            let [collection, update_authority, payer, system_program, mpl_core] = accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            CreateCollectionV1 {
+                collection,
+                update_authority: some(0, update_authority),
+                payer,
+                system_program,
+                mpl_core,
+            }
+            .invoke(
+                &CreateCollectionV1InstructionData {
+                    name: b"Collection",
+                    uri: b"https://example.com/collection.json",
+                    plugins: Some(&[PluginAuthorityPair {
+                        plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+                            frozen: false,
+                        }),
+                        authority: Some(PluginAuthority::UpdateAuthority),
+                    }]),
+                },
+                &mut buffer,
+            )
+        }
+        TRANSFER => {
+            let [asset, collection, payer, authority, new_owner, system_program, log_wrapper, mpl_core] =
+                accounts
+            else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            TransferV1 {
+                asset,
+                collection: some(0, collection),
+                payer,
+                authority: some(1, authority),
+                new_owner,
+                system_program,
+                log_wrapper: some(2, log_wrapper),
+                mpl_core,
+            }
+            .invoke(
+                &TransferV1InstructionData {
+                    compression_proof: None,
+                },
+                &mut buffer,
+            )
+        }
+        BURN => {
+            let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] =
+                accounts
+            else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            BurnV1 {
+                asset,
+                collection: some(0, collection),
+                payer,
+                authority: some(1, authority),
+                system_program,
+                log_wrapper: some(2, log_wrapper),
+                mpl_core,
+            }
+            .invoke(
+                &BurnAssetV1InstructionData {
+                    compression_proof: None,
+                },
+                &mut buffer,
+            )
+        }
+        BURN_COLLECTION => {
+            let [collection, payer, authority, log_wrapper, mpl_core] = accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            BurnCollectionV1 {
+                collection,
+                payer,
+                authority: some(0, authority),
+                log_wrapper: some(1, log_wrapper),
+                mpl_core,
+            }
+            .invoke(
+                &BurnCollectionV1InstructionData {
+                    compression_proof: None,
+                },
+                &mut buffer,
+            )
+        }
+        UPDATE_ASSET_PLUGIN | PATCH_ATTRIBUTES => {
+            let [asset, collection, payer, authority, system_program, log_wrapper, mpl_core] =
+                accounts
+            else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let update = UpdateAssetPluginV1 {
+                asset,
+                collection: some(0, collection),
+                payer,
+                authority: some(1, authority),
+                system_program,
+                log_wrapper: some(2, log_wrapper),
+                mpl_core,
+            };
+
+            if case == PATCH_ATTRIBUTES {
+                // the patch borrows the asset, which mpl core needs during the CPI
+                let len = {
+                    let data = asset.try_borrow_data()?;
+                    let attributes = AssetView::new(&data)?
+                        .attributes()?
+                        .ok_or(MplCoreError::MissingPlugin)?;
+                    UpdateAssetPluginPatchV1InstructionData {
+                        patch: attributes.patch(&[
+                            AttributeOp::Set {
+                                key: b"level",
+                                value: b"8",
+                            },
+                            AttributeOp::Remove { key: b"class" },
+                        ]),
+                    }
+                    .try_serialize_to(&mut buffer)?
+                };
+                return update.invoke_serialized(&buffer[..len]);
+            }
+
+            update.invoke(
+                &UpdateAssetPluginV1InstructionData {
+                    plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                },
+                &mut buffer,
+            )
+        }
+        UPDATE_COLLECTION_PLUGIN => {
+            let [collection, payer, authority, system_program, log_wrapper, mpl_core] = accounts
+            else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            UpdateCollectionPluginV1 {
+                collection,
+                payer,
+                authority: some(0, authority),
+                system_program,
+                log_wrapper: some(1, log_wrapper),
+                mpl_core,
+            }
+            .invoke(
+                &UpdateCollectionPluginV1InstructionData {
+                    plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+                        frozen: true,
+                    }),
+                },
+                &mut buffer,
+            )
+        }
+        NOOP => Ok(()),
+        SERIALIZE | TRY_SERIALIZE => {
+            let plugins = create_asset_plugins();
+            let data = create_asset_data(&plugins);
+            let len = if case == SERIALIZE {
+                data.serialize_to(black_box(&mut buffer))
+            } else {
+                data.try_serialize_to(black_box(&mut buffer))?
+            };
+            black_box(&buffer[..len]);
+            Ok(())
+        }
+        READ_ROYALTIES | READ_ATTRIBUTES | RESOLVE_ROYALTIES | CAN_TRANSFER => {
+            let [asset, collection, authority] = accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let asset = asset.try_borrow_data()?;
+            let collection = some(0, collection)
+                .map(|collection| collection.try_borrow_data())
+                .transpose()?;
+
+            match case {
+                READ_ROYALTIES => {
+                    black_box(read_royalties_asset(&asset)?);
+                }
+                READ_ATTRIBUTES => {
+                    let attributes = AssetView::new(&asset)?
+                        .attributes()?
+                        .ok_or(MplCoreError::MissingPlugin)?;
+                    black_box(attributes.get_u64(b"level")?);
+                }
+                _ => {
+                    let asset = AssetView::new(&asset)?;
+                    let collection = collection.as_deref().map(CollectionView::new).transpose()?;
+
+                    if case == RESOLVE_ROYALTIES {
+                        let royalties = RoyaltiesInfo::resolve(&asset, collection.as_ref())?;
+                        for payout in royalties.compute_payouts(1_000_000_000)? {
+                            black_box(payout);
+                        }
+                    } else {
+                        black_box(asset.can_transfer(
+                            collection.as_ref(),
+                            authority.key(),
+                            program_id,
+                        )?);
+                    }
+                }
+            }
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,42 @@
+//! Stands in for mpl core at `MPL_CORE_ID`. It doesn't do anything, it only echoes what it was
+//! invoked with as return data, so the tests can see exactly what mpl core would have received:
+//! the number of accounts, then `pubkey, is_signer, is_writable` for each of them, then the instruction data
+
+#![no_std]
+
+use pinocchio::{
+    account_info::AccountInfo, default_allocator, nostd_panic_handler, program::set_return_data,
+    program_entrypoint, pubkey::Pubkey, ProgramResult,
+};
+#[cfg(all(target_arch = "bpf", target_os = "none"))]
+use solana_compiler_builtins as _;
+
+program_entrypoint!(process_instruction);
+default_allocator!();
+nostd_panic_handler!();
+
+/// The most return data the runtime allows
+const MAX_RETURN_DATA: usize = 1024;
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let mut echo = [0; MAX_RETURN_DATA];
+    echo[0] = accounts.len() as u8;
+    let mut offset = 1;
+
+    for account in accounts {
+        echo[offset..offset + 32].copy_from_slice(account.key());
+        echo[offset + 32] = account.is_signer() as u8;
+        echo[offset + 33] = account.is_writable() as u8;
+        offset += 34;
+    }
+
+    echo[offset..offset + instruction_data.len()].copy_from_slice(instruction_data);
+    offset += instruction_data.len();
+
+    set_return_data(&echo[..offset]);
+    Ok(())
+}
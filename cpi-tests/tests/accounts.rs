@@ -0,0 +1,123 @@
+//! The account layouts, compared with how the official mpl-core SDK encodes the same accounts.
+//! Like the client tests, these don't need the programs
+
+use mpl_core::{
+    accounts::PluginRegistryV1,
+    types::{
+        ExternalCheckResult as SdkCheckResult, ExternalPluginAdapterType, ExternalRegistryRecord,
+        HookableLifecycleEvent as SdkEvent, Key as SdkKey, PluginAuthority as SdkAuthority,
+        PluginType, RegistryRecord,
+    },
+};
+use mpl_core_pinocchio::data::{
+    asset::{
+        ExternalCheckResult, ExternalRegistryRecordSafe, Key, PluginRegistryV1Safe,
+        RegistryRecordSafe,
+    },
+    external_plugins::HookableLifecycleEvent,
+    plugins::PluginAuthority,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+
+const HOOK_AUTHORITY: [u8; 32] = [12; 32];
+
+fn to_bytes(ours: &impl Serialize) -> Vec<u8> {
+    let mut bytes = vec![0; ours.serialized_size()];
+    let len = ours.try_serialize_to(&mut bytes).unwrap();
+    assert_eq!(len, bytes.len());
+    bytes
+}
+
+#[test]
+fn registry_with_lifecycle_checks() {
+    // every event, each with a different set of check flags
+    let checks = [
+        (
+            HookableLifecycleEvent::Create,
+            ExternalCheckResult { flags: 1 },
+        ),
+        (
+            HookableLifecycleEvent::Transfer,
+            ExternalCheckResult { flags: 2 },
+        ),
+        (
+            HookableLifecycleEvent::Burn,
+            ExternalCheckResult { flags: 4 },
+        ),
+        (
+            HookableLifecycleEvent::Update,
+            ExternalCheckResult { flags: 6 },
+        ),
+        (
+            HookableLifecycleEvent::Execute,
+            ExternalCheckResult { flags: 7 },
+        ),
+    ];
+    let sdk_checks = [
+        (SdkEvent::Create, 1),
+        (SdkEvent::Transfer, 2),
+        (SdkEvent::Burn, 4),
+        (SdkEvent::Update, 6),
+        (SdkEvent::Execute, 7),
+    ]
+    .map(|(event, flags)| (event, SdkCheckResult { flags }))
+    .to_vec();
+
+    let ours = PluginRegistryV1Safe {
+        key: Key::PluginRegistryV1,
+        registry: &[RegistryRecordSafe {
+            plugin_type: PluginType::Royalties as u8,
+            authority: PluginAuthority::UpdateAuthority,
+            offset: 119,
+        }],
+        external_registry: &[
+            ExternalRegistryRecordSafe {
+                plugin_type: ExternalPluginAdapterType::LifecycleHook as u8,
+                authority: PluginAuthority::Address(HOOK_AUTHORITY),
+                lifecycle_checks: Some(&checks),
+                offset: 160,
+                data_offset: Some(230),
+                data_len: Some(12),
+            },
+            ExternalRegistryRecordSafe {
+                plugin_type: ExternalPluginAdapterType::AppData as u8,
+                authority: PluginAuthority::Owner,
+                lifecycle_checks: None,
+                offset: 242,
+                data_offset: None,
+                data_len: None,
+            },
+        ],
+    };
+    let sdk = PluginRegistryV1 {
+        key: SdkKey::PluginRegistryV1,
+        registry: vec![RegistryRecord {
+            plugin_type: PluginType::Royalties,
+            authority: SdkAuthority::UpdateAuthority,
+            offset: 119,
+        }],
+        external_registry: vec![
+            ExternalRegistryRecord {
+                plugin_type: ExternalPluginAdapterType::LifecycleHook,
+                authority: SdkAuthority::Address {
+                    address: Pubkey::new_from_array(HOOK_AUTHORITY),
+                },
+                lifecycle_checks: Some(sdk_checks),
+                offset: 160,
+                data_offset: Some(230),
+                data_len: Some(12),
+            },
+            ExternalRegistryRecord {
+                plugin_type: ExternalPluginAdapterType::AppData,
+                authority: SdkAuthority::Owner,
+                lifecycle_checks: None,
+                offset: 242,
+                data_offset: None,
+                data_len: None,
+            },
+        ],
+    };
+
+    assert_eq!(to_bytes(&ours), borsh::to_vec(&sdk).unwrap());
+}
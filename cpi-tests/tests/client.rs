@@ -0,0 +1,333 @@
+//! The owned instructions of the `client` feature, compared with the ones the official mpl-core SDK builds,
+//! for every combination of optional accounts. Unlike the CPI tests these don't need the programs
+
+use mpl_core::{
+    instructions::{
+        BurnCollectionV1Builder, BurnV1Builder, CreateCollectionV1Builder, CreateV1Builder,
+        TransferV1Builder, UpdateCollectionPluginV1Builder, UpdatePluginV1Builder,
+    },
+    types::{
+        Attribute, Attributes, DataState, Edition, FreezeDelegate, PermanentFreezeDelegate, Plugin,
+        PluginAuthority, PluginAuthorityPair,
+    },
+};
+use mpl_core_pinocchio::{
+    client::{
+        burn_asset::BurnV1, burn_collection::BurnCollectionV1, create_asset::CreateAssetV1,
+        create_collection::CreateCollectionV1, transfer::TransferV1,
+        update_asset_plugin::UpdateAssetPluginV1,
+        update_collection_plugin::UpdateCollectionPluginV1, Instruction,
+    },
+    data::{
+        burn_asset::BurnAssetV1InstructionData,
+        burn_collection::BurnCollectionV1InstructionData,
+        create_asset::{self, CreateAssetV1InstructionData, PrintEditionV1InstructionData},
+        create_collection::CreateCollectionV1InstructionData,
+        plugins::{self, AttributeOp, AttributesView},
+        transfer::TransferV1InstructionData,
+        update_asset_plugin::{
+            UpdateAssetPluginPatchV1InstructionData, UpdateAssetPluginV1InstructionData,
+        },
+        update_collection_plugin::UpdateCollectionPluginV1InstructionData,
+        Serialize,
+    },
+    SPL_NOOP_ID, SYSTEM_PROGRAM_ID,
+};
+use solana_program::pubkey::Pubkey;
+
+const ASSET: [u8; 32] = [10; 32];
+const COLLECTION: [u8; 32] = [11; 32];
+const AUTHORITY: [u8; 32] = [12; 32];
+const PAYER: [u8; 32] = [13; 32];
+const OWNER: [u8; 32] = [14; 32];
+const UPDATE_AUTHORITY: [u8; 32] = [15; 32];
+const NEW_OWNER: [u8; 32] = [16; 32];
+
+fn sdk(key: [u8; 32]) -> Pubkey {
+    Pubkey::new_from_array(key)
+}
+
+/// `Some(key)` if `bit` is set in `optional`
+fn some(optional: u8, bit: u8, key: [u8; 32]) -> Option<[u8; 32]> {
+    (optional & (1 << bit) != 0).then_some(key)
+}
+
+fn assert_same(client: Instruction, expected: solana_program::instruction::Instruction) {
+    assert_eq!(client.program_id, expected.program_id.to_bytes());
+    let accounts = client
+        .accounts
+        .iter()
+        .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+        .collect::<Vec<_>>();
+    let expected_accounts = expected
+        .accounts
+        .iter()
+        .map(|meta| (meta.pubkey.to_bytes(), meta.is_signer, meta.is_writable))
+        .collect::<Vec<_>>();
+    assert_eq!(accounts, expected_accounts);
+    assert_eq!(client.data, expected.data);
+}
+
+#[test]
+fn create_asset() {
+    for optional in 0..1 << 5 {
+        let client = CreateAssetV1 {
+            asset: ASSET,
+            collection: some(optional, 0, COLLECTION),
+            authority: some(optional, 1, AUTHORITY),
+            payer: PAYER,
+            owner: some(optional, 2, OWNER),
+            update_authority: some(optional, 3, UPDATE_AUTHORITY),
+            log_wrapper: some(optional, 4, SPL_NOOP_ID),
+        };
+        let mut builder = CreateV1Builder::new();
+        builder
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION).map(sdk))
+            .authority(some(optional, 1, AUTHORITY).map(sdk))
+            .payer(sdk(PAYER))
+            .owner(some(optional, 2, OWNER).map(sdk))
+            .update_authority(some(optional, 3, UPDATE_AUTHORITY).map(sdk))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .log_wrapper(some(optional, 4, SPL_NOOP_ID).map(sdk))
+            .data_state(DataState::AccountState);
+
+        assert_same(
+            client.instruction(&CreateAssetV1InstructionData {
+                data_state: create_asset::DataState::AccountState,
+                name: b"Asset",
+                uri: b"https://example.com/asset.json",
+                plugins: Some(&[plugins::PluginAuthorityPair {
+                    plugin: plugins::Plugin::FreezeDelegate(plugins::FreezeDelegate {
+                        frozen: true,
+                    }),
+                    authority: Some(plugins::PluginAuthority::Owner),
+                }]),
+            }),
+            builder
+                .name("Asset".to_string())
+                .uri("https://example.com/asset.json".to_string())
+                .plugins(vec![PluginAuthorityPair {
+                    plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+                    authority: Some(PluginAuthority::Owner),
+                }])
+                .instruction(),
+        );
+
+        assert_same(
+            client.print_edition_instruction(&PrintEditionV1InstructionData {
+                data_state: create_asset::DataState::AccountState,
+                name: b"Print",
+                uri: b"https://example.com/print.json",
+                edition: plugins::Edition { number: 4 },
+                edition_authority: None,
+            }),
+            builder
+                .name("Print".to_string())
+                .uri("https://example.com/print.json".to_string())
+                .plugins(vec![PluginAuthorityPair {
+                    plugin: Plugin::Edition(Edition { number: 4 }),
+                    authority: None,
+                }])
+                .instruction(),
+        );
+    }
+}
+
+#[test]
+fn create_collection() {
+    for optional in 0..1 << 1 {
+        let client = CreateCollectionV1 {
+            collection: COLLECTION,
+            update_authority: some(optional, 0, UPDATE_AUTHORITY),
+            payer: PAYER,
+        };
+
+        assert_same(
+            client.instruction(&CreateCollectionV1InstructionData {
+                name: b"Collection",
+                uri: b"https://example.com/collection.json",
+                plugins: None,
+            }),
+            CreateCollectionV1Builder::new()
+                .collection(sdk(COLLECTION))
+                .update_authority(some(optional, 0, UPDATE_AUTHORITY).map(sdk))
+                .payer(sdk(PAYER))
+                .system_program(sdk(SYSTEM_PROGRAM_ID))
+                .name("Collection".to_string())
+                .uri("https://example.com/collection.json".to_string())
+                .instruction(),
+        );
+    }
+}
+
+#[test]
+fn transfer() {
+    for optional in 0..1 << 3 {
+        let client = TransferV1 {
+            asset: ASSET,
+            collection: some(optional, 0, COLLECTION),
+            payer: PAYER,
+            authority: some(optional, 1, AUTHORITY),
+            new_owner: NEW_OWNER,
+            log_wrapper: some(optional, 2, SPL_NOOP_ID),
+        };
+
+        assert_same(
+            client.instruction(&TransferV1InstructionData {
+                compression_proof: None,
+            }),
+            TransferV1Builder::new()
+                .asset(sdk(ASSET))
+                .collection(some(optional, 0, COLLECTION).map(sdk))
+                .payer(sdk(PAYER))
+                .authority(some(optional, 1, AUTHORITY).map(sdk))
+                .new_owner(sdk(NEW_OWNER))
+                .system_program(Some(sdk(SYSTEM_PROGRAM_ID)))
+                .log_wrapper(some(optional, 2, SPL_NOOP_ID).map(sdk))
+                .instruction(),
+        );
+    }
+}
+
+#[test]
+fn burn() {
+    for optional in 0..1 << 3 {
+        let client = BurnV1 {
+            asset: ASSET,
+            collection: some(optional, 0, COLLECTION),
+            payer: PAYER,
+            authority: some(optional, 1, AUTHORITY),
+            log_wrapper: some(optional, 2, SPL_NOOP_ID),
+        };
+
+        assert_same(
+            client.instruction(&BurnAssetV1InstructionData {
+                compression_proof: None,
+            }),
+            BurnV1Builder::new()
+                .asset(sdk(ASSET))
+                .collection(some(optional, 0, COLLECTION).map(sdk))
+                .payer(sdk(PAYER))
+                .authority(some(optional, 1, AUTHORITY).map(sdk))
+                .system_program(Some(sdk(SYSTEM_PROGRAM_ID)))
+                .log_wrapper(some(optional, 2, SPL_NOOP_ID).map(sdk))
+                .instruction(),
+        );
+    }
+}
+
+#[test]
+fn burn_collection() {
+    for optional in 0..1 << 2 {
+        let client = BurnCollectionV1 {
+            collection: COLLECTION,
+            payer: PAYER,
+            authority: some(optional, 0, AUTHORITY),
+            log_wrapper: some(optional, 1, SPL_NOOP_ID),
+        };
+
+        assert_same(
+            client.instruction(&BurnCollectionV1InstructionData {
+                compression_proof: None,
+            }),
+            BurnCollectionV1Builder::new()
+                .collection(sdk(COLLECTION))
+                .payer(sdk(PAYER))
+                .authority(some(optional, 0, AUTHORITY).map(sdk))
+                .log_wrapper(some(optional, 1, SPL_NOOP_ID).map(sdk))
+                .instruction(),
+        );
+    }
+}
+
+#[test]
+fn update_asset_plugin() {
+    // Attributes [level=7, class=mage], as they are stored after the plugin discriminant
+    let mut attributes = 2u32.to_le_bytes().to_vec();
+    for field in ["level", "7", "class", "mage"] {
+        attributes.extend((field.len() as u32).to_le_bytes());
+        attributes.extend(field.as_bytes());
+    }
+    let attributes = AttributesView::from_bytes(&attributes).unwrap();
+
+    for optional in 0..1 << 3 {
+        let client = UpdateAssetPluginV1 {
+            asset: ASSET,
+            collection: some(optional, 0, COLLECTION),
+            payer: PAYER,
+            authority: some(optional, 1, AUTHORITY),
+            log_wrapper: some(optional, 2, SPL_NOOP_ID),
+        };
+        let mut builder = UpdatePluginV1Builder::new();
+        builder
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION).map(sdk))
+            .payer(sdk(PAYER))
+            .authority(some(optional, 1, AUTHORITY).map(sdk))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .log_wrapper(some(optional, 2, SPL_NOOP_ID).map(sdk));
+
+        assert_same(
+            client.instruction(&UpdateAssetPluginV1InstructionData {
+                plugin: plugins::Plugin::FreezeDelegate(plugins::FreezeDelegate { frozen: false }),
+            }),
+            builder
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+                .instruction(),
+        );
+
+        let patch = UpdateAssetPluginPatchV1InstructionData {
+            patch: attributes.patch(&[
+                AttributeOp::Set {
+                    key: b"level",
+                    value: b"8",
+                },
+                AttributeOp::Remove { key: b"class" },
+            ]),
+        };
+        let instruction = client.patch_instruction(&patch);
+        assert_eq!(instruction.data.len(), patch.serialized_size());
+        assert_same(
+            instruction,
+            builder
+                .plugin(Plugin::Attributes(Attributes {
+                    attribute_list: vec![Attribute {
+                        key: "level".to_string(),
+                        value: "8".to_string(),
+                    }],
+                }))
+                .instruction(),
+        );
+    }
+}
+
+#[test]
+fn update_collection_plugin() {
+    for optional in 0..1 << 2 {
+        let client = UpdateCollectionPluginV1 {
+            collection: COLLECTION,
+            payer: PAYER,
+            authority: some(optional, 0, AUTHORITY),
+            log_wrapper: some(optional, 1, SPL_NOOP_ID),
+        };
+
+        assert_same(
+            client.instruction(&UpdateCollectionPluginV1InstructionData {
+                plugin: plugins::Plugin::PermanentFreezeDelegate(
+                    plugins::PermanentFreezeDelegate { frozen: true },
+                ),
+            }),
+            UpdateCollectionPluginV1Builder::new()
+                .collection(sdk(COLLECTION))
+                .payer(sdk(PAYER))
+                .authority(some(optional, 0, AUTHORITY).map(sdk))
+                .system_program(sdk(SYSTEM_PROGRAM_ID))
+                .log_wrapper(some(optional, 1, SPL_NOOP_ID).map(sdk))
+                .plugin(Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+                    frozen: true,
+                }))
+                .instruction(),
+        );
+    }
+}
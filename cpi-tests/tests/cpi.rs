@@ -0,0 +1,476 @@
+//! Runs every CPI struct inside the SVM, from the `caller` program, against `mpl_core_stub` registered
+//! at `MPL_CORE_ID`. What the stub received is compared with the instruction the official mpl-core
+//! SDK builds for the same accounts and arguments: account order, signer/writable flags and data.
+//!
+//! The programs must be built first, see the `README.md` at the root of the repository
+
+use caller::{
+    BURN, BURN_COLLECTION, CREATE_ASSET, CREATE_COLLECTION, CREATOR, PATCH_ATTRIBUTES,
+    PRINT_EDITION, TRANSFER, UPDATE_ASSET_PLUGIN, UPDATE_COLLECTION_PLUGIN,
+};
+use mollusk_svm::{program, Mollusk};
+use mpl_core::{
+    instructions::{
+        BurnCollectionV1Builder, BurnV1Builder, CreateCollectionV1Builder, CreateV1Builder,
+        TransferV1Builder, UpdateCollectionPluginV1Builder, UpdatePluginV1Builder,
+    },
+    types::{
+        Attribute, Attributes, Creator, DataState, Edition, FreezeDelegate,
+        PermanentFreezeDelegate, Plugin, PluginAuthority, PluginAuthorityPair, Royalties, RuleSet,
+    },
+};
+use solana_account::Account;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+const CALLER_ID: Pubkey = Pubkey::new_from_array([9; 32]);
+const MPL_CORE_ID: Pubkey = Pubkey::new_from_array(mpl_core_pinocchio::MPL_CORE_ID);
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array(mpl_core_pinocchio::SYSTEM_PROGRAM_ID);
+const SPL_NOOP_ID: Pubkey = Pubkey::new_from_array(mpl_core_pinocchio::SPL_NOOP_ID);
+
+const ASSET: Pubkey = Pubkey::new_from_array([10; 32]);
+const COLLECTION: Pubkey = Pubkey::new_from_array([11; 32]);
+const AUTHORITY: Pubkey = Pubkey::new_from_array([12; 32]);
+const PAYER: Pubkey = Pubkey::new_from_array([13; 32]);
+const OWNER: Pubkey = Pubkey::new_from_array([14; 32]);
+const UPDATE_AUTHORITY: Pubkey = Pubkey::new_from_array([15; 32]);
+const NEW_OWNER: Pubkey = Pubkey::new_from_array([16; 32]);
+
+/// An asset with Royalties and Attributes (level=7, class=mage), in a collection
+const ASSET_WITH_ATTRIBUTES: &[u8] =
+    include_bytes!("../../tests/fixtures/asset_royalties_attributes.bin");
+/// A collection with a MasterEdition plugin that has minted 3 assets
+const COLLECTION_WITH_MASTER_EDITION: &[u8] =
+    include_bytes!("../../tests/fixtures/collection_master_edition.bin");
+
+/// What the stub was invoked with
+#[derive(Debug, PartialEq)]
+struct Received {
+    /// pubkey, is_signer, is_writable
+    accounts: Vec<(Pubkey, bool, bool)>,
+    data: Vec<u8>,
+}
+
+impl Received {
+    fn from_echo(echo: &[u8]) -> Self {
+        let len = usize::from(echo[0]);
+        let accounts = echo[1..1 + len * 34]
+            .chunks(34)
+            .map(|account| {
+                (
+                    Pubkey::new_from_array(account[..32].try_into().unwrap()),
+                    account[32] == 1,
+                    account[33] == 1,
+                )
+            })
+            .collect();
+        Self {
+            accounts,
+            data: echo[1 + len * 34..].to_vec(),
+        }
+    }
+
+    /// What mpl core receives for an instruction built with the official SDK
+    fn from_sdk(instruction: solana_program::instruction::Instruction) -> Self {
+        assert_eq!(instruction.program_id.to_bytes(), MPL_CORE_ID.to_bytes());
+        Self {
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| (pubkey(meta.pubkey), meta.is_signer, meta.is_writable))
+                .collect(),
+            data: instruction.data,
+        }
+    }
+}
+
+fn pubkey(key: solana_program::pubkey::Pubkey) -> Pubkey {
+    Pubkey::new_from_array(key.to_bytes())
+}
+
+fn sdk(key: Pubkey) -> solana_program::pubkey::Pubkey {
+    solana_program::pubkey::Pubkey::new_from_array(key.to_bytes())
+}
+
+/// `Some(key)` if `bit` is set in `optional`, matching what the caller does with its accounts
+fn some(optional: u8, bit: u8, key: Pubkey) -> Option<solana_program::pubkey::Pubkey> {
+    (optional & (1 << bit) != 0).then(|| sdk(key))
+}
+
+fn account(owner: Pubkey, data: &[u8]) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data: data.to_vec(),
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Runs the caller with `accounts`, given in the order of the CPI struct's fields.
+/// Assets and collections are owned by mpl core and hold `asset_data` and `collection_data`
+fn invoke(
+    case: u8,
+    optional: u8,
+    accounts: &[AccountMeta],
+    asset_data: &[u8],
+    collection_data: &[u8],
+) -> Received {
+    // the programs are looked up in SBF_OUT_DIR, which defaults to target/deploy
+    let mut mollusk = Mollusk::new(&CALLER_ID, "caller");
+    mollusk.add_program(&MPL_CORE_ID, "mpl_core_stub");
+
+    let mut keyed_accounts = vec![
+        program::keyed_account_for_system_program(),
+        (
+            MPL_CORE_ID,
+            program::create_program_account_loader_v3(&MPL_CORE_ID),
+        ),
+    ];
+    for meta in accounts {
+        if keyed_accounts.iter().any(|(key, _)| key == &meta.pubkey) {
+            continue;
+        }
+        let account = match meta.pubkey {
+            ASSET => account(MPL_CORE_ID, asset_data),
+            COLLECTION => account(MPL_CORE_ID, collection_data),
+            _ => account(SYSTEM_PROGRAM_ID, &[]),
+        };
+        keyed_accounts.push((meta.pubkey, account));
+    }
+
+    let instruction = Instruction::new_with_bytes(CALLER_ID, &[case, optional], accounts.to_vec());
+    let result = mollusk.process_instruction(&instruction, &keyed_accounts);
+    assert!(
+        result.program_result.is_ok(),
+        "case {case} failed: {:?}",
+        result.program_result
+    );
+
+    Received::from_echo(&result.return_data)
+}
+
+/// Every optional account set, and none of them
+const OPTIONAL: [u8; 2] = [0xff, 0];
+
+#[test]
+fn create_asset() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            CREATE_ASSET,
+            optional,
+            &[
+                AccountMeta::new(ASSET, true),
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(OWNER, false),
+                AccountMeta::new_readonly(UPDATE_AUTHORITY, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        );
+
+        let expected = CreateV1Builder::new()
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION))
+            .authority(some(optional, 1, AUTHORITY))
+            .payer(sdk(PAYER))
+            .owner(some(optional, 2, OWNER))
+            .update_authority(some(optional, 3, UPDATE_AUTHORITY))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .log_wrapper(some(optional, 4, SPL_NOOP_ID))
+            .data_state(DataState::AccountState)
+            .name("Asset".to_string())
+            .uri("https://example.com/asset.json".to_string())
+            .plugins(vec![
+                PluginAuthorityPair {
+                    plugin: Plugin::Royalties(Royalties {
+                        basis_points: 500,
+                        creators: vec![Creator {
+                            address: solana_program::pubkey::Pubkey::new_from_array(CREATOR),
+                            percentage: 100,
+                        }],
+                        rule_set: RuleSet::None,
+                    }),
+                    authority: None,
+                },
+                PluginAuthorityPair {
+                    plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+                    authority: Some(PluginAuthority::Owner),
+                },
+            ])
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn print_edition() {
+    for optional in [0b1, 0b11111] {
+        let received = invoke(
+            PRINT_EDITION,
+            optional,
+            &[
+                AccountMeta::new(ASSET, true),
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(OWNER, false),
+                AccountMeta::new_readonly(UPDATE_AUTHORITY, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            COLLECTION_WITH_MASTER_EDITION,
+        );
+
+        let expected = CreateV1Builder::new()
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION))
+            .authority(some(optional, 1, AUTHORITY))
+            .payer(sdk(PAYER))
+            .owner(some(optional, 2, OWNER))
+            .update_authority(some(optional, 3, UPDATE_AUTHORITY))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .log_wrapper(some(optional, 4, SPL_NOOP_ID))
+            .data_state(DataState::AccountState)
+            .name("Print".to_string())
+            .uri("https://example.com/print.json".to_string())
+            .plugins(vec![PluginAuthorityPair {
+                plugin: Plugin::Edition(Edition { number: 4 }),
+                authority: None,
+            }])
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn create_collection() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            CREATE_COLLECTION,
+            optional,
+            &[
+                AccountMeta::new(COLLECTION, true),
+                AccountMeta::new_readonly(UPDATE_AUTHORITY, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        );
+
+        let expected = CreateCollectionV1Builder::new()
+            .collection(sdk(COLLECTION))
+            .update_authority(some(optional, 0, UPDATE_AUTHORITY))
+            .payer(sdk(PAYER))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .name("Collection".to_string())
+            .uri("https://example.com/collection.json".to_string())
+            .plugins(vec![PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false }),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            }])
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn transfer() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            TRANSFER,
+            optional,
+            &[
+                AccountMeta::new(ASSET, false),
+                AccountMeta::new_readonly(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new_readonly(NEW_OWNER, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        );
+
+        let expected = TransferV1Builder::new()
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION))
+            .payer(sdk(PAYER))
+            .authority(some(optional, 1, AUTHORITY))
+            .new_owner(sdk(NEW_OWNER))
+            .system_program(Some(sdk(SYSTEM_PROGRAM_ID)))
+            .log_wrapper(some(optional, 2, SPL_NOOP_ID))
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn burn() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            BURN,
+            optional,
+            &[
+                AccountMeta::new(ASSET, false),
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        );
+
+        let expected = BurnV1Builder::new()
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION))
+            .payer(sdk(PAYER))
+            .authority(some(optional, 1, AUTHORITY))
+            .system_program(Some(sdk(SYSTEM_PROGRAM_ID)))
+            .log_wrapper(some(optional, 2, SPL_NOOP_ID))
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn burn_collection() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            BURN_COLLECTION,
+            optional,
+            &[
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new(AUTHORITY, true),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        );
+
+        let expected = BurnCollectionV1Builder::new()
+            .collection(sdk(COLLECTION))
+            .payer(sdk(PAYER))
+            .authority(some(optional, 0, AUTHORITY))
+            .log_wrapper(some(optional, 1, SPL_NOOP_ID))
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn update_asset_plugin() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            UPDATE_ASSET_PLUGIN,
+            optional,
+            &[
+                AccountMeta::new(ASSET, false),
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        );
+
+        let expected = UpdatePluginV1Builder::new()
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION))
+            .payer(sdk(PAYER))
+            .authority(some(optional, 1, AUTHORITY))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .log_wrapper(some(optional, 2, SPL_NOOP_ID))
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn patch_attributes() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            PATCH_ATTRIBUTES,
+            optional,
+            &[
+                AccountMeta::new(ASSET, false),
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            ASSET_WITH_ATTRIBUTES,
+            &[],
+        );
+
+        let expected = UpdatePluginV1Builder::new()
+            .asset(sdk(ASSET))
+            .collection(some(optional, 0, COLLECTION))
+            .payer(sdk(PAYER))
+            .authority(some(optional, 1, AUTHORITY))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .log_wrapper(some(optional, 2, SPL_NOOP_ID))
+            .plugin(Plugin::Attributes(Attributes {
+                attribute_list: vec![Attribute {
+                    key: "level".to_string(),
+                    value: "8".to_string(),
+                }],
+            }))
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
+
+#[test]
+fn update_collection_plugin() {
+    for optional in OPTIONAL {
+        let received = invoke(
+            UPDATE_COLLECTION_PLUGIN,
+            optional,
+            &[
+                AccountMeta::new(COLLECTION, false),
+                AccountMeta::new(PAYER, true),
+                AccountMeta::new_readonly(AUTHORITY, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(MPL_CORE_ID, false),
+            ],
+            &[],
+            &[],
+        );
+
+        let expected = UpdateCollectionPluginV1Builder::new()
+            .collection(sdk(COLLECTION))
+            .payer(sdk(PAYER))
+            .authority(some(optional, 0, AUTHORITY))
+            .system_program(sdk(SYSTEM_PROGRAM_ID))
+            .log_wrapper(some(optional, 1, SPL_NOOP_ID))
+            .plugin(Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+                frozen: true,
+            }))
+            .instruction();
+        assert_eq!(received, Received::from_sdk(expected));
+    }
+}
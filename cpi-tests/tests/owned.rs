@@ -0,0 +1,242 @@
+//! The owned mirrors of the `borsh` feature, read from what the official mpl-core SDK encodes and
+//! written back. Like the client tests, these don't need the programs
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_core::{
+    instructions::{
+        BurnCollectionV1Builder, BurnV1Builder, CreateCollectionV1Builder, CreateV1Builder,
+        TransferV1Builder, UpdateCollectionPluginV1Builder, UpdatePluginV1Builder,
+    },
+    types::{
+        AddBlocker, Attribute, Attributes, Autograph, AutographSignature, BubblegumV2,
+        BurnDelegate, CompressionProof, Creator, DataState, Edition, FreezeDelegate, FreezeExecute,
+        Groups, HashablePluginSchema, ImmutableMetadata, MasterEdition, PermanentBurnDelegate,
+        PermanentFreezeDelegate, PermanentFreezeExecute, PermanentTransferDelegate, Plugin,
+        PluginAuthority, PluginAuthorityPair, Royalties, RuleSet, TransferDelegate,
+        UpdateAuthority, UpdateDelegate, VerifiedCreators, VerifiedCreatorsSignature,
+    },
+};
+use mpl_core_pinocchio::data::{
+    create_asset,
+    owned::{
+        OwnedBurnAssetV1InstructionData, OwnedBurnCollectionV1InstructionData,
+        OwnedCompressionProof, OwnedCreateAssetV1InstructionData,
+        OwnedCreateCollectionV1InstructionData, OwnedPlugin, OwnedTransferV1InstructionData,
+        OwnedUpdateAssetPluginV1InstructionData, OwnedUpdateCollectionPluginV1InstructionData,
+    },
+    plugins,
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+const ALICE: Pubkey = Pubkey::new_from_array([1; 32]);
+const BOB: Pubkey = Pubkey::new_from_array([2; 32]);
+
+/// Reads `bytes` into `T` and checks it writes back the same bytes
+fn round_trip<T: BorshDeserialize + BorshSerialize>(bytes: &[u8]) -> T {
+    let ours = borsh::from_slice::<T>(bytes).unwrap();
+    assert_eq!(borsh::to_vec(&ours).unwrap(), bytes);
+    ours
+}
+
+/// Same as `round_trip`, for the data of an instruction the SDK built
+fn round_trip_data<T: BorshDeserialize + BorshSerialize>(instruction: Instruction) -> T {
+    round_trip(&instruction.data)
+}
+
+/// One of each plugin, with every rule set
+fn plugins() -> Vec<Plugin> {
+    let royalties = |rule_set| {
+        Plugin::Royalties(Royalties {
+            basis_points: 500,
+            creators: vec![
+                Creator {
+                    address: ALICE,
+                    percentage: 60,
+                },
+                Creator {
+                    address: BOB,
+                    percentage: 40,
+                },
+            ],
+            rule_set,
+        })
+    };
+    vec![
+        royalties(RuleSet::None),
+        royalties(RuleSet::ProgramAllowList(vec![ALICE])),
+        royalties(RuleSet::ProgramDenyList(vec![ALICE, BOB])),
+        Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+        Plugin::BurnDelegate(BurnDelegate {}),
+        Plugin::TransferDelegate(TransferDelegate {}),
+        Plugin::UpdateDelegate(UpdateDelegate {
+            additional_delegates: vec![BOB],
+        }),
+        Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false }),
+        Plugin::Attributes(Attributes {
+            attribute_list: vec![
+                Attribute {
+                    key: "level".to_string(),
+                    value: "3".to_string(),
+                },
+                Attribute {
+                    key: "class".to_string(),
+                    value: "mage".to_string(),
+                },
+            ],
+        }),
+        Plugin::PermanentTransferDelegate(PermanentTransferDelegate {}),
+        Plugin::PermanentBurnDelegate(PermanentBurnDelegate {}),
+        Plugin::Edition(Edition { number: 4 }),
+        Plugin::MasterEdition(MasterEdition {
+            max_supply: Some(100),
+            name: Some("Master".to_string()),
+            uri: None,
+        }),
+        Plugin::AddBlocker(AddBlocker {}),
+        Plugin::ImmutableMetadata(ImmutableMetadata {}),
+        Plugin::VerifiedCreators(VerifiedCreators {
+            signatures: vec![VerifiedCreatorsSignature {
+                address: ALICE,
+                verified: true,
+            }],
+        }),
+        Plugin::Autograph(Autograph {
+            signatures: vec![AutographSignature {
+                address: BOB,
+                message: "gm".to_string(),
+            }],
+        }),
+        Plugin::BubblegumV2(BubblegumV2 {}),
+        Plugin::FreezeExecute(FreezeExecute { frozen: true }),
+        Plugin::PermanentFreezeExecute(PermanentFreezeExecute { frozen: false }),
+        Plugin::Groups(Groups {
+            groups: vec![ALICE, BOB],
+        }),
+    ]
+}
+
+fn compression_proof() -> CompressionProof {
+    CompressionProof {
+        owner: ALICE,
+        update_authority: UpdateAuthority::Collection(BOB),
+        name: "compressed".to_string(),
+        uri: "uri".to_string(),
+        seq: 9,
+        plugins: plugins()
+            .into_iter()
+            .enumerate()
+            .map(|(index, plugin)| HashablePluginSchema {
+                index: index as u64,
+                authority: PluginAuthority::Owner,
+                plugin,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn plugins_round_trip() {
+    for plugin in plugins() {
+        round_trip::<OwnedPlugin>(&borsh::to_vec(&plugin).unwrap());
+
+        round_trip_data::<OwnedUpdateAssetPluginV1InstructionData>(
+            UpdatePluginV1Builder::new()
+                .asset(ALICE)
+                .payer(BOB)
+                .plugin(plugin.clone())
+                .instruction(),
+        );
+        round_trip_data::<OwnedUpdateCollectionPluginV1InstructionData>(
+            UpdateCollectionPluginV1Builder::new()
+                .collection(ALICE)
+                .payer(BOB)
+                .plugin(plugin)
+                .instruction(),
+        );
+    }
+
+    let edition = borsh::to_vec(&Plugin::Edition(Edition { number: 4 })).unwrap();
+    assert_eq!(
+        round_trip::<OwnedPlugin>(&edition),
+        OwnedPlugin::Edition(plugins::Edition { number: 4 })
+    );
+}
+
+#[test]
+fn create_instructions_round_trip() {
+    // every plugin, with and without an authority
+    let pairs: Vec<_> = plugins()
+        .into_iter()
+        .enumerate()
+        .map(|(index, plugin)| PluginAuthorityPair {
+            plugin,
+            authority: (index % 2 == 0).then_some(PluginAuthority::Address { address: BOB }),
+        })
+        .collect();
+
+    for with_plugins in [false, true] {
+        let mut builder = CreateV1Builder::new();
+        builder
+            .asset(ALICE)
+            .payer(BOB)
+            .data_state(DataState::LedgerState)
+            .name("Asset".to_string())
+            .uri("https://example.com/asset.json".to_string());
+        if with_plugins {
+            builder.plugins(pairs.clone());
+        }
+        let create = round_trip_data::<OwnedCreateAssetV1InstructionData>(builder.instruction());
+        assert_eq!(create.data_state, create_asset::DataState::LedgerState);
+        assert_eq!(create.name, "Asset");
+        assert_eq!(create.uri, "https://example.com/asset.json");
+        assert_eq!(
+            create.plugins.map(|plugins| plugins.len()),
+            with_plugins.then_some(pairs.len())
+        );
+
+        let mut builder = CreateCollectionV1Builder::new();
+        builder
+            .collection(ALICE)
+            .payer(BOB)
+            .name("Collection".to_string())
+            .uri(String::new());
+        if with_plugins {
+            builder.plugins(pairs.clone());
+        }
+        round_trip_data::<OwnedCreateCollectionV1InstructionData>(builder.instruction());
+    }
+}
+
+#[test]
+fn compression_proofs_round_trip() {
+    let proof = round_trip::<OwnedCompressionProof>(&borsh::to_vec(&compression_proof()).unwrap());
+    assert_eq!(proof.seq, 9);
+    assert_eq!(proof.plugins.len(), plugins().len());
+
+    for with_proof in [false, true] {
+        let mut transfer = TransferV1Builder::new();
+        transfer.asset(ALICE).payer(BOB).new_owner(BOB);
+        let mut burn = BurnV1Builder::new();
+        burn.asset(ALICE).payer(BOB);
+        let mut burn_collection = BurnCollectionV1Builder::new();
+        burn_collection.collection(ALICE).payer(BOB);
+        if with_proof {
+            transfer.compression_proof(compression_proof());
+            burn.compression_proof(compression_proof());
+            burn_collection.compression_proof(compression_proof());
+        }
+
+        let transfer = round_trip_data::<OwnedTransferV1InstructionData>(transfer.instruction());
+        assert_eq!(transfer.compression_proof.is_some(), with_proof);
+        round_trip_data::<OwnedBurnAssetV1InstructionData>(burn.instruction());
+        round_trip_data::<OwnedBurnCollectionV1InstructionData>(burn_collection.instruction());
+    }
+
+    // the data of one instruction isn't read as another
+    let transfer = TransferV1Builder::new()
+        .asset(ALICE)
+        .payer(BOB)
+        .new_owner(BOB)
+        .instruction();
+    assert!(borsh::from_slice::<OwnedBurnAssetV1InstructionData>(&transfer.data).is_err());
+}
@@ -0,0 +1,45 @@
+//! Every reader that takes a whole account, fed the same hostile bytes.
+//! They may return `Err`, but never panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mpl_core_pinocchio::data::{
+    asset::{BaseAssetV1, BaseCollectionV1, Key},
+    plugins::{read_royalties, read_royalties_asset, read_royalties_collection, PluginType},
+    view::{AssetView, CollectionView},
+    Skip,
+};
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = Key::deserialize_from(bytes);
+    let _ = BaseAssetV1::get_collection(bytes);
+    let _ = BaseAssetV1::skip_bytes(bytes);
+    let _ = BaseCollectionV1::skip_bytes(bytes);
+    let _ = read_royalties_asset(bytes);
+    let _ = read_royalties_collection(bytes);
+
+    // a PluginHeaderV1 can be anywhere, as far as read_royalties knows
+    if let Some(&offset) = bytes.first() {
+        let _ = read_royalties(bytes, usize::from(offset));
+    }
+
+    if let Ok(asset) = AssetView::new(bytes) {
+        for record in asset.plugins() {
+            let _ = record;
+        }
+        let _ = asset.find_plugin(PluginType::Royalties);
+        let _ = asset.edition();
+        let _ = asset.attributes();
+        let _ = asset.verified_creators();
+        let _ = asset.autograph();
+    }
+
+    if let Ok(collection) = CollectionView::new(bytes) {
+        for record in collection.plugins() {
+            let _ = record;
+        }
+        let _ = collection.find_plugin(PluginType::Royalties);
+        let _ = collection.master_edition();
+    }
+});
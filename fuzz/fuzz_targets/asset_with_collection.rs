@@ -0,0 +1,48 @@
+//! The readers that combine an asset with its collection: royalties resolution, payouts,
+//! authorities, lifecycle checks and printing editions
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mpl_core_pinocchio::{
+    authority::AuthorityResolver,
+    data::{
+        plugins::{Edition, PluginAuthority, Royalties, RoyaltiesInfo},
+        view::{AssetView, CollectionView},
+    },
+};
+
+fuzz_target!(|input: (&[u8], &[u8], [u8; 32], [u8; 32], u64)| {
+    let (asset, collection, authority, program_id, sale_price) = input;
+
+    let collection = CollectionView::new(collection).ok();
+    if let Some(collection) = &collection {
+        let _ = Edition::next(collection);
+    }
+
+    let Ok(asset) = AssetView::new(asset) else {
+        return;
+    };
+    let collection = collection.as_ref();
+
+    if let Ok(royalties) = RoyaltiesInfo::resolve(&asset, collection) {
+        if let Ok(payouts) = royalties.compute_payouts(sale_price) {
+            for payout in payouts {
+                let _ = payout;
+            }
+        }
+    }
+    if let Ok(Some(royalties)) = Royalties::resolve(&asset, collection) {
+        let _ = royalties.rule_set.is_program_allowed(&program_id);
+    }
+
+    let resolver = AuthorityResolver::new(&asset, collection, &authority);
+    let _ = resolver.is_owner();
+    let _ = resolver.is_update_authority();
+    let _ = resolver.is_update_delegate();
+    let _ = resolver.satisfies(&PluginAuthority::UpdateAuthority);
+
+    let _ = asset.can_transfer(collection, &authority, &program_id);
+    let _ = asset.can_burn(collection, &authority);
+    let _ = asset.can_update(collection, &authority);
+});
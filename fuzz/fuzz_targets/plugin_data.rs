@@ -0,0 +1,75 @@
+//! The plugin readers, which start right after the `Plugin` discriminant, and the patches built
+//! from what they read
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mpl_core_pinocchio::data::{
+    asset::PluginHeaderV1,
+    plugins::{
+        AttributeOp, AttributesView, AutographView, Edition, FreezeDelegate, MasterEdition,
+        PluginAuthority, Royalties, RoyaltiesInfo, RuleSet, UpdateAuthority, UpdateDelegate,
+        VerifiedCreatorsView,
+    },
+    DeserializeSized, Serialize, Skip,
+};
+
+const KEY: [u8; 32] = [1; 32];
+
+fn serialize(value: &impl Serialize) {
+    let mut buffer = vec![0; value.serialized_size()];
+    let _ = value.try_serialize_to(&mut buffer);
+}
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = Royalties::from_bytes(bytes);
+    let _ = RoyaltiesInfo::from_bytes(bytes);
+    let _ = RuleSet::from_bytes(bytes);
+    let _ = UpdateDelegate::from_bytes(bytes);
+    let _ = FreezeDelegate::deserialize(bytes);
+    let _ = Edition::deserialize(bytes);
+    let _ = MasterEdition::from_bytes(bytes);
+    let _ = PluginHeaderV1::deserialize(bytes);
+    let _ = PluginAuthority::deserialize(bytes);
+    let _ = PluginAuthority::skip_bytes(bytes);
+    let _ = UpdateAuthority::deserialize(bytes);
+    let _ = UpdateAuthority::skip_bytes(bytes);
+    let _ = Option::<u64>::skip_bytes(bytes);
+    let _ = <&[PluginAuthority]>::skip_bytes(bytes);
+
+    if let Ok(attributes) = AttributesView::from_bytes(bytes) {
+        for attribute in attributes.iter() {
+            let _ = attribute;
+        }
+        let _ = attributes.get_u64(b"level");
+        let _ = attributes.get_i64(b"level");
+        serialize(&attributes.patch(&[
+            AttributeOp::Set {
+                key: b"level",
+                value: b"1",
+            },
+            AttributeOp::Remove { key: b"class" },
+        ]));
+    }
+
+    if let Ok(verified_creators) = VerifiedCreatorsView::from_bytes(bytes) {
+        for signature in verified_creators.iter() {
+            let _ = signature;
+        }
+        if let Ok(update) = verified_creators.add(&KEY, false) {
+            serialize(&update);
+        }
+        if let Ok(update) = verified_creators.verify(&KEY) {
+            serialize(&update);
+        }
+    }
+
+    if let Ok(autograph) = AutographView::from_bytes(bytes) {
+        for signature in autograph.iter() {
+            let _ = signature;
+        }
+        if let Ok(update) = autograph.add(&KEY, b"gm") {
+            serialize(&update);
+        }
+    }
+});
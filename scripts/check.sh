@@ -0,0 +1,18 @@
+#!/usr/bin/env sh
+# Everything a change has to pass: the crate, with and without its features, and the fuzz targets.
+# The fuzz crate is its own workspace, so `cargo build --workspace` at the root doesn't see it
+set -eu
+
+cd "$(dirname "$0")/.."
+
+cargo fmt --all --check
+cargo build --workspace
+cargo clippy --workspace --all-targets -- -D warnings
+cargo clippy --workspace --all-targets --all-features -- -D warnings
+cargo test --workspace --all-features
+
+(
+    cd fuzz
+    cargo fmt --check
+    cargo clippy --bins -- -D warnings
+)
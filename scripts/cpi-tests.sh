@@ -0,0 +1,43 @@
+#!/usr/bin/env sh
+# Builds the cpi-tests programs into cpi-tests/target/deploy, then runs `cargo test` in cpi-tests, or
+# the cargo command given instead, e.g. `scripts/cpi-tests.sh bench`.
+#
+# The programs are built with `cargo build-sbf` when the Solana toolchain is installed. Without it they
+# are built for the upstream `bpfel-unknown-none` target and relinked with `sbpf-linker`
+# (`cargo install sbpf-linker`), which needs a nightly with LLVM 23, set SBF_TOOLCHAIN to use another one
+set -eu
+
+cd "$(dirname "$0")/../cpi-tests"
+
+PROGRAMS="caller mpl-core-stub"
+DEPLOY=target/deploy
+
+if cargo build-sbf --version >/dev/null 2>&1; then
+    for program in $PROGRAMS; do
+        cargo build-sbf --manifest-path "programs/$program/Cargo.toml" --sbf-out-dir "$DEPLOY"
+    done
+else
+    # target_os = "solana" and the static syscalls make pinocchio call the runtime the way
+    # `cargo build-sbf` does, `--arch=v0` links the SBPFv0 programs the runtime loads by default.
+    # solana-compiler-builtins has no u128 division, the one `compute_payouts` needs is kept from
+    # core's compiler_builtins
+    RUSTFLAGS='--cfg target_os="solana" --cfg target_feature="static-syscalls"
+        -A explicit_builtin_cfgs_in_flags -A unexpected_cfgs
+        -C linker=sbpf-linker -C panic=abort -C relocation-model=static -C target-cpu=v2
+        -C target-feature=+allows-misaligned-mem-access
+        -C link-arg=--llvm-args=--bpf-stack-size=4096 -C link-arg=--export=entrypoint
+        -C link-arg=--export=__udivti3
+        -C link-arg=--arch=v0' \
+        cargo "+${SBF_TOOLCHAIN:-nightly-2026-10-15}" build --release \
+        --target bpfel-unknown-none -Z build-std=core,alloc \
+        $(for program in $PROGRAMS; do printf -- '-p %s ' "$(echo "$program" | tr - _)"; done)
+
+    mkdir -p "$DEPLOY"
+    for program in $PROGRAMS; do
+        name=$(echo "$program" | tr - _)
+        cp "target/bpfel-unknown-none/release/lib$name.so" "$DEPLOY/$name.so"
+    done
+fi
+
+[ $# -eq 0 ] && set -- test
+SBF_OUT_DIR="$DEPLOY" cargo "$@"
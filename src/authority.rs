@@ -0,0 +1,80 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::data::{
+    plugins::{PluginAuthority, PluginType, UpdateAuthority, UpdateDelegate},
+    view::{AssetView, CollectionView},
+};
+
+/// Answers "does this key hold authority X on this asset?", the same way mpl core resolves
+/// `PluginAuthority` when checking a signer.
+///
+/// `collection` must be given if the asset belongs to one, since the collection's update authority
+/// is then the asset's update authority. It is not checked to actually be the asset's collection
+pub struct AuthorityResolver<'v, 'a> {
+    asset: &'v AssetView<'a>,
+    collection: Option<&'v CollectionView<'a>>,
+    key: &'v Pubkey,
+}
+
+impl<'v, 'a> AuthorityResolver<'v, 'a> {
+    pub fn new(
+        asset: &'v AssetView<'a>,
+        collection: Option<&'v CollectionView<'a>>,
+        key: &'v Pubkey,
+    ) -> Self {
+        Self {
+            asset,
+            collection,
+            key,
+        }
+    }
+
+    pub fn is_owner(&self) -> bool {
+        self.asset.owner == self.key
+    }
+
+    /// Whether the key is the asset's update authority, directly or through its collection
+    pub fn is_update_authority(&self) -> bool {
+        match self.asset.update_authority {
+            UpdateAuthority::None => false,
+            UpdateAuthority::Address(address) => &address == self.key,
+            UpdateAuthority::Collection(_) => self
+                .collection
+                .is_some_and(|collection| collection.update_authority == self.key),
+        }
+    }
+
+    /// Whether the key satisfies `plugin_authority`.
+    /// Like in mpl core, the additional delegates of an UpdateDelegate do not count as the update authority here,
+    /// only the UpdateDelegate plugin itself looks at them, see `is_update_delegate`
+    pub fn satisfies(&self, plugin_authority: &PluginAuthority) -> bool {
+        match plugin_authority {
+            PluginAuthority::None => false,
+            PluginAuthority::Owner => self.is_owner(),
+            PluginAuthority::UpdateAuthority => self.is_update_authority(),
+            PluginAuthority::Address(address) => address == self.key,
+        }
+    }
+
+    /// Whether the key holds the UpdateDelegate plugin of the asset or of its collection,
+    /// either as the plugin's authority or as one of its additional delegates
+    pub fn is_update_delegate(&self) -> Result<bool, ProgramError> {
+        let asset_plugin = self.asset.find_plugin(PluginType::UpdateDelegate)?;
+        let collection_plugin = match self.collection {
+            Some(collection) => collection.find_plugin(PluginType::UpdateDelegate)?,
+            None => None,
+        };
+
+        for record in asset_plugin.iter().chain(collection_plugin.iter()) {
+            if self.satisfies(&record.authority)
+                || UpdateDelegate::from_bytes(record.data)?
+                    .additional_delegates
+                    .contains(self.key)
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
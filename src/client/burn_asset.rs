@@ -0,0 +1,40 @@
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    client::{AccountMeta, Instruction},
+    data::burn_asset::BurnAssetV1InstructionData,
+    SYSTEM_PROGRAM_ID,
+};
+
+/// Burn an asset, see `instructions::burn_asset::BurnV1`
+pub struct BurnV1 {
+    /// The asset to burn
+    pub asset: Pubkey,
+    /// The collection the asset belongs to
+    pub collection: Option<Pubkey>,
+    /// Payer
+    pub payer: Pubkey,
+    /// The authority
+    pub authority: Option<Pubkey>,
+    /// The SPL Noop Program
+    pub log_wrapper: Option<Pubkey>,
+}
+
+impl BurnV1 {
+    pub fn instruction(&self, data: &BurnAssetV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::writable(self.asset),
+            AccountMeta::optional(self.collection, AccountMeta::writable),
+            AccountMeta::writable_signer(self.payer),
+            AccountMeta::optional(self.authority, AccountMeta::readonly_signer),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+            AccountMeta::optional(self.log_wrapper, AccountMeta::readonly),
+        ]
+    }
+}
@@ -0,0 +1,35 @@
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    client::{AccountMeta, Instruction},
+    data::burn_collection::BurnCollectionV1InstructionData,
+};
+
+/// Burn a collection, see `instructions::burn_collection::BurnCollectionV1`
+pub struct BurnCollectionV1 {
+    /// The collection to burn
+    pub collection: Pubkey,
+    /// Payer
+    pub payer: Pubkey,
+    /// The authority
+    pub authority: Option<Pubkey>,
+    /// The SPL Noop Program
+    pub log_wrapper: Option<Pubkey>,
+}
+
+impl BurnCollectionV1 {
+    pub fn instruction(&self, data: &BurnCollectionV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::writable(self.collection),
+            AccountMeta::writable_signer(self.payer),
+            AccountMeta::optional(self.authority, AccountMeta::writable_signer),
+            AccountMeta::optional(self.log_wrapper, AccountMeta::readonly),
+        ]
+    }
+}
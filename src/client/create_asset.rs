@@ -0,0 +1,51 @@
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    client::{AccountMeta, Instruction},
+    data::create_asset::{CreateAssetV1InstructionData, PrintEditionV1InstructionData},
+    SYSTEM_PROGRAM_ID,
+};
+
+/// Create an asset, see `instructions::create_asset::CreateAssetV1`
+pub struct CreateAssetV1 {
+    /// The address of the new asset
+    pub asset: Pubkey,
+    /// The collection to which the asset belongs
+    pub collection: Option<Pubkey>,
+    /// The authority signing for creation
+    pub authority: Option<Pubkey>,
+    /// The account paying for the storage fees
+    pub payer: Pubkey,
+    /// The owner of the new asset. Defaults to the authority if not present
+    pub owner: Option<Pubkey>,
+    /// The authority on the new asset
+    pub update_authority: Option<Pubkey>,
+    /// The SPL Noop Program
+    pub log_wrapper: Option<Pubkey>,
+}
+
+impl CreateAssetV1 {
+    pub fn instruction(&self, data: &CreateAssetV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    /// Prints the next edition of `collection`, which should be set
+    pub fn print_edition_instruction(&self, data: &PrintEditionV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::writable_signer(self.asset),
+            AccountMeta::optional(self.collection, AccountMeta::writable),
+            AccountMeta::optional(self.authority, AccountMeta::readonly_signer),
+            AccountMeta::writable_signer(self.payer),
+            AccountMeta::optional(self.owner, AccountMeta::readonly),
+            AccountMeta::optional(self.update_authority, AccountMeta::readonly),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+            AccountMeta::optional(self.log_wrapper, AccountMeta::readonly),
+        ]
+    }
+}
@@ -0,0 +1,34 @@
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    client::{AccountMeta, Instruction},
+    data::create_collection::CreateCollectionV1InstructionData,
+    SYSTEM_PROGRAM_ID,
+};
+
+/// Create a collection, see `instructions::create_collection::CreateCollectionV1`
+pub struct CreateCollectionV1 {
+    /// The address of the new collection
+    pub collection: Pubkey,
+    /// The authority of the new collection
+    pub update_authority: Option<Pubkey>,
+    /// The account paying for the storage fees
+    pub payer: Pubkey,
+}
+
+impl CreateCollectionV1 {
+    pub fn instruction(&self, data: &CreateCollectionV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::writable_signer(self.collection),
+            AccountMeta::optional(self.update_authority, AccountMeta::readonly),
+            AccountMeta::writable_signer(self.payer),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+        ]
+    }
+}
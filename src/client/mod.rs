@@ -0,0 +1,81 @@
+//! Owned instructions for off-chain tooling, built from plain pubkeys with the same accounts and data
+//! the CPI structs of `instructions` send to mpl core. The program accounts are implied, so the structs only
+//! take the accounts that change between calls.
+//!
+//! `Instruction` and `AccountMeta` have the same fields as the ones of `solana-instruction`
+
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{data::Serialize, MPL_CORE_ID};
+
+pub mod burn_asset;
+pub mod burn_collection;
+pub mod create_asset;
+pub mod create_collection;
+pub mod transfer;
+pub mod update_asset_plugin;
+pub mod update_collection_plugin;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    pub const fn new(pubkey: Pubkey, is_writable: bool, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable,
+        }
+    }
+
+    pub const fn readonly(pubkey: Pubkey) -> Self {
+        Self::new(pubkey, false, false)
+    }
+
+    pub const fn writable(pubkey: Pubkey) -> Self {
+        Self::new(pubkey, true, false)
+    }
+
+    pub const fn readonly_signer(pubkey: Pubkey) -> Self {
+        Self::new(pubkey, false, true)
+    }
+
+    pub const fn writable_signer(pubkey: Pubkey) -> Self {
+        Self::new(pubkey, true, true)
+    }
+
+    /// `meta(key)` if the account is set, otherwise the mpl core program in its place, like the CPI structs do
+    pub(crate) fn optional(key: Option<Pubkey>, meta: fn(Pubkey) -> Self) -> Self {
+        match key {
+            Some(key) => meta(key),
+            None => Self::readonly(MPL_CORE_ID),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+impl Instruction {
+    /// An instruction to mpl core, with `data` serialized
+    pub(crate) fn mpl_core(accounts: Vec<AccountMeta>, data: &impl Serialize) -> Self {
+        let mut bytes = vec![0; data.serialized_size()];
+        data.serialize_to(&mut bytes);
+
+        Self {
+            program_id: MPL_CORE_ID,
+            accounts,
+            data: bytes,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    client::{AccountMeta, Instruction},
+    data::transfer::TransferV1InstructionData,
+    SYSTEM_PROGRAM_ID,
+};
+
+/// Transfer an asset, see `instructions::transfer::TransferV1`
+pub struct TransferV1 {
+    /// The asset to transfer
+    pub asset: Pubkey,
+    /// The collection the asset belongs to
+    pub collection: Option<Pubkey>,
+    /// Payer
+    pub payer: Pubkey,
+    /// The authority
+    pub authority: Option<Pubkey>,
+    /// New owner of the asset
+    pub new_owner: Pubkey,
+    /// The SPL Noop Program
+    pub log_wrapper: Option<Pubkey>,
+}
+
+impl TransferV1 {
+    pub fn instruction(&self, data: &TransferV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::writable(self.asset),
+            AccountMeta::optional(self.collection, AccountMeta::readonly),
+            AccountMeta::writable_signer(self.payer),
+            AccountMeta::optional(self.authority, AccountMeta::readonly_signer),
+            AccountMeta::readonly(self.new_owner),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+            AccountMeta::optional(self.log_wrapper, AccountMeta::readonly),
+        ]
+    }
+}
@@ -0,0 +1,53 @@
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    client::{AccountMeta, Instruction},
+    data::{
+        plugins::PluginPatch,
+        update_asset_plugin::{
+            UpdateAssetPluginPatchV1InstructionData, UpdateAssetPluginV1InstructionData,
+        },
+    },
+    SYSTEM_PROGRAM_ID,
+};
+
+/// Update a plugin of an asset, see `instructions::update_asset_plugin::UpdateAssetPluginV1`
+pub struct UpdateAssetPluginV1 {
+    /// The asset to update
+    pub asset: Pubkey,
+    /// The collection to which the asset belongs
+    pub collection: Option<Pubkey>,
+    /// The payer
+    pub payer: Pubkey,
+    /// The authority
+    pub authority: Option<Pubkey>,
+    /// The SPL Noop Program
+    pub log_wrapper: Option<Pubkey>,
+}
+
+impl UpdateAssetPluginV1 {
+    pub fn instruction(&self, data: &UpdateAssetPluginV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    /// Replaces a plugin with a patched version of the current one
+    pub fn patch_instruction(
+        &self,
+        data: &UpdateAssetPluginPatchV1InstructionData<impl PluginPatch>,
+    ) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::writable(self.asset),
+            AccountMeta::optional(self.collection, AccountMeta::writable),
+            AccountMeta::writable_signer(self.payer),
+            AccountMeta::optional(self.authority, AccountMeta::readonly_signer),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+            AccountMeta::optional(self.log_wrapper, AccountMeta::readonly),
+        ]
+    }
+}
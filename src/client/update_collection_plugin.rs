@@ -0,0 +1,37 @@
+use alloc::{vec, vec::Vec};
+
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    client::{AccountMeta, Instruction},
+    data::update_collection_plugin::UpdateCollectionPluginV1InstructionData,
+    SYSTEM_PROGRAM_ID,
+};
+
+/// Update a plugin of a collection, see `instructions::update_collection_plugin::UpdateCollectionPluginV1`
+pub struct UpdateCollectionPluginV1 {
+    /// The collection to update
+    pub collection: Pubkey,
+    /// The payer
+    pub payer: Pubkey,
+    /// The authority
+    pub authority: Option<Pubkey>,
+    /// The SPL Noop Program
+    pub log_wrapper: Option<Pubkey>,
+}
+
+impl UpdateCollectionPluginV1 {
+    pub fn instruction(&self, data: &UpdateCollectionPluginV1InstructionData) -> Instruction {
+        Instruction::mpl_core(self.account_metas(), data)
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::writable(self.collection),
+            AccountMeta::writable_signer(self.payer),
+            AccountMeta::optional(self.authority, AccountMeta::readonly_signer),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+            AccountMeta::optional(self.log_wrapper, AccountMeta::readonly),
+        ]
+    }
+}
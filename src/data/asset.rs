@@ -1,17 +1,21 @@
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
-use crate::data::{
-    plugins::{Plugin, PluginAuthority, UpdateAuthority},
-    skip_sized, skip_sized_slice, DeserializeSized, Serialize, Skip,
+use crate::{
+    data::{
+        external_plugins::{ExternalPluginAdapterWithData, HookableLifecycleEvent},
+        plugins::{Plugin, PluginAuthority, UpdateAuthority},
+        read_pubkey, read_tail, read_u8, skip_sized, skip_sized_slice, DeserializeSized, Serialize,
+        Skip,
+    },
+    error::MplCoreError,
 };
-use core::mem::MaybeUninit;
 
 /// This struct contains processed data about an asset.
-/// TODO: for now external plugins are not supported
 /// I made this different from how the data looks so that it is not terrible to use
 pub struct AssetInfo<'a> {
     pub base: BaseAssetV1<'a>,
     pub plugins: &'a [PluginAuthorityPairWithoutOption<'a>],
+    pub external_plugins: &'a [ExternalPluginAdapterWithData<'a>],
 }
 
 /// I don't fucking know. They have one with an Option<PluginAuthority>, where the PluginAuthority itself can be None!!!!!!! literally None(None)!!!!!! who the fuck made this
@@ -20,14 +24,72 @@ pub struct PluginAuthorityPairWithoutOption<'a> {
     pub authority: PluginAuthority,
 }
 
+impl<'a> AssetInfo<'a> {
+    fn plugin_section(&self) -> PluginSection<'a> {
+        PluginSection {
+            plugins: self.plugins,
+            external_plugins: self.external_plugins,
+        }
+    }
+}
+
 impl<'a> Serialize for AssetInfo<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
-        let mut offset = self.base.serialize_to(buffer);
+        let offset = self.base.serialize_to(buffer);
+        self.plugin_section().serialize_after_base(buffer, offset)
+    }
 
+    fn serialized_size(&self) -> usize {
+        self.base.serialized_size() + self.plugin_section().serialized_size()
+    }
+}
+
+/// Same as `AssetInfo`, for a collection account
+pub struct CollectionInfo<'a> {
+    pub base: BaseCollectionV1<'a>,
+    pub plugins: &'a [PluginAuthorityPairWithoutOption<'a>],
+    pub external_plugins: &'a [ExternalPluginAdapterWithData<'a>],
+}
+
+impl<'a> CollectionInfo<'a> {
+    fn plugin_section(&self) -> PluginSection<'a> {
+        PluginSection {
+            plugins: self.plugins,
+            external_plugins: self.external_plugins,
+        }
+    }
+}
+
+impl<'a> Serialize for CollectionInfo<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let offset = self.base.serialize_to(buffer);
+        self.plugin_section().serialize_after_base(buffer, offset)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.base.serialized_size() + self.plugin_section().serialized_size()
+    }
+}
+
+/// Everything after the base asset or collection: the plugin header, the plugins, the external plugins
+/// with their data and the registry. Assets and collections lay it out the same way
+struct PluginSection<'a> {
+    plugins: &'a [PluginAuthorityPairWithoutOption<'a>],
+    external_plugins: &'a [ExternalPluginAdapterWithData<'a>],
+}
+
+impl PluginSection<'_> {
+    fn has_plugins(&self) -> bool {
+        !self.plugins.is_empty() || !self.external_plugins.is_empty()
+    }
+
+    /// Not a `Serialize` impl since the registry offsets are from the start of the account,
+    /// so this needs the whole buffer and where the base ended. Returns where the account ends
+    fn serialize_after_base(&self, buffer: &mut [u8], mut offset: usize) -> usize {
         // after the base asset, there is a PluginHeaderV1 pointing to where the registry is. This seems completely absolutely fucking monkey brained but whatever
         // I'll plop it down right here and assume there is no data between this and the asset
         // HOWEVER, this only applies if there are any plugins at all
-        if self.plugins.is_empty() {
+        if !self.has_plugins() {
             return offset;
         }
 
@@ -35,41 +97,56 @@ impl<'a> Serialize for AssetInfo<'a> {
         let plugin_header_offset = offset;
         offset += 9;
 
-        // now we write all of the plugins
-        // WARN: this is really ugly I don't care. while serializing I need to store how big each plugin was
-        const MAX_PLUGINS_MAGIC_NUMBER: usize = 16;
-        let mut registry_records: [MaybeUninit<RegistryRecordSafe>; MAX_PLUGINS_MAGIC_NUMBER] =
-            unsafe { MaybeUninit::uninit().assume_init() };
-
-        for (registry_record, plugin_auth_pair) in
-            registry_records.iter_mut().zip(self.plugins.iter())
-        {
-            let start_offset = offset;
-            offset += plugin_auth_pair.plugin.serialize_to(&mut buffer[offset..]);
-
-            *registry_record = MaybeUninit::new(RegistryRecordSafe {
-                plugin_type: plugin_auth_pair.plugin.get_plugin_number(),
-                authority: plugin_auth_pair.authority,
-                offset: u64::try_from(start_offset).unwrap(), // didn't feel like changing this to be a Result<>
-            });
+        // now we write all of the plugins, then all of the external plugins followed by their data
+        let plugins_offset = offset;
+        for pair in self.plugins {
+            offset += pair.plugin.serialize_to(&mut buffer[offset..]);
         }
 
-        let records_slice: &[RegistryRecordSafe] = unsafe {
-            core::slice::from_raw_parts(
-                registry_records.as_ptr() as *const RegistryRecordSafe,
-                self.plugins.len(),
-            )
-        };
-
-        let registry = PluginRegistryV1Safe {
-            key: Key::PluginRegistryV1,
-            registry: &records_slice,
-            // TODO: for now external plugins are not supported
-            external_registry: &[],
-        };
+        for external in self.external_plugins {
+            offset += external.adapter.serialize_to(&mut buffer[offset..]);
+            if let Some(data) = external.data {
+                buffer[offset..offset + data.len()].copy_from_slice(data);
+                offset += data.len();
+            }
+        }
 
+        // the registry goes last. instead of remembering where each plugin went, walk their sizes again
         let registry_offset = offset;
-        offset += registry.serialize_to(&mut buffer[offset..]);
+        offset += Key::PluginRegistryV1.serialize_to(&mut buffer[offset..]);
+
+        let len = u32::try_from(self.plugins.len()).unwrap(); // didn't feel like changing this to be a Result<>
+        offset += len.serialize_to(&mut buffer[offset..]);
+
+        let mut plugin_offset = plugins_offset;
+        for pair in self.plugins {
+            offset += RegistryRecordSafe {
+                plugin_type: pair.plugin.get_plugin_number(),
+                authority: pair.authority,
+                offset: u64::try_from(plugin_offset).unwrap(), // didn't feel like changing this to be a Result<>
+            }
+            .serialize_to(&mut buffer[offset..]);
+            plugin_offset += pair.plugin.serialized_size();
+        }
+
+        let len = u32::try_from(self.external_plugins.len()).unwrap(); // didn't feel like changing this to be a Result<>
+        offset += len.serialize_to(&mut buffer[offset..]);
+
+        for external in self.external_plugins {
+            let adapter_size = external.adapter.serialized_size();
+            offset += ExternalRegistryRecordSafe {
+                plugin_type: external.adapter.adapter_type() as u8,
+                authority: external.authority,
+                lifecycle_checks: external.lifecycle_checks,
+                offset: u64::try_from(plugin_offset).unwrap(), // didn't feel like changing this to be a Result<>
+                data_offset: external
+                    .data
+                    .map(|_| u64::try_from(plugin_offset + adapter_size).unwrap()), // didn't feel like changing this to be a Result<>
+                data_len: external.data.map(|data| u64::try_from(data.len()).unwrap()), // didn't feel like changing this to be a Result<>
+            }
+            .serialize_to(&mut buffer[offset..]);
+            plugin_offset += external.account_size();
+        }
 
         // now finally serialize the header.......
         let header = PluginHeaderV1 {
@@ -81,6 +158,48 @@ impl<'a> Serialize for AssetInfo<'a> {
 
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        if !self.has_plugins() {
+            return 0;
+        }
+
+        let plugins_size: usize = self
+            .plugins
+            .iter()
+            .map(|pair| pair.plugin.serialized_size())
+            .sum();
+
+        let external_plugins_size: usize = self
+            .external_plugins
+            .iter()
+            .map(|external| external.account_size())
+            .sum();
+
+        // key + registry len + (plugin_type + authority + offset) per record
+        // + external registry len + external records
+        let registry_size: usize = 1
+            + 4
+            + self
+                .plugins
+                .iter()
+                .map(|pair| 1 + pair.authority.serialized_size() + 8)
+                .sum::<usize>()
+            + 4
+            + self
+                .external_plugins
+                .iter()
+                .map(|external| {
+                    // plugin_type + authority + lifecycle checks + offset + data offset + data len
+                    1 + external.authority.serialized_size()
+                        + external.lifecycle_checks.serialized_size()
+                        + 8
+                        + if external.data.is_some() { 2 * 9 } else { 2 }
+                })
+                .sum::<usize>();
+
+        9 + plugins_size + external_plugins_size + registry_size
+    }
 }
 
 pub struct BaseAssetV1<'a> {
@@ -97,21 +216,20 @@ impl<'a> BaseAssetV1<'a> {
     pub fn get_collection(bytes: &'a [u8]) -> Result<&'a Pubkey, ProgramError> {
         let key = Key::deserialize_from(bytes)?;
         if !matches!(key, Key::AssetV1) {
-            return Err(ProgramError::InvalidAccountData)?;
+            return Err(MplCoreError::InvalidKey.into());
         }
 
         // skip key + owner
         let mut offset = 1 + 32;
 
-        let update_authority_discriminant = bytes[offset];
+        let update_authority_discriminant = read_u8(bytes, offset)?;
         offset += 1;
 
         if update_authority_discriminant != 2 {
-            return Err(ProgramError::InvalidAccountData)?;   
+            return Err(MplCoreError::UpdateAuthorityNotCollection.into());
         }
 
-        let collection_key = bytes[offset..offset + 32].try_into().map_err(|_| ProgramError::InvalidAccountData)?;
-        return Ok(collection_key);
+        read_pubkey(bytes, offset)
     }
 }
 
@@ -125,17 +243,26 @@ impl<'a> Serialize for BaseAssetV1<'a> {
         offset += self.seq.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.key.serialized_size()
+            + self.owner.serialized_size()
+            + self.update_authority.serialized_size()
+            + self.name.serialized_size()
+            + self.uri.serialized_size()
+            + self.seq.serialized_size()
+    }
 }
 
-impl<'a> Skip for BaseAssetV1<'_> {
+impl Skip for BaseAssetV1<'_> {
     // DOES NOT ASSUME KEY WAS SKIPPED
     fn skip_bytes(bytes: &[u8]) -> Result<usize, ProgramError> {
         let mut offset: usize = 1;
         offset += skip_sized::<Pubkey>();
-        offset += UpdateAuthority::skip_bytes(&bytes[offset..])?;
-        offset += skip_sized_slice::<u8>(&bytes[offset..])?;
-        offset += skip_sized_slice::<u8>(&bytes[offset..])?;
-        offset += Option::<u64>::skip_bytes(&bytes[offset..])?;
+        offset += UpdateAuthority::skip_bytes(read_tail(bytes, offset)?)?;
+        offset += skip_sized_slice::<u8>(read_tail(bytes, offset)?)?;
+        offset += skip_sized_slice::<u8>(read_tail(bytes, offset)?)?;
+        offset += Option::<u64>::skip_bytes(read_tail(bytes, offset)?)?;
         Ok(offset)
     }
 }
@@ -149,13 +276,34 @@ pub struct BaseCollectionV1<'a> {
     pub current_size: u32,
 }
 
-impl<'a> Skip for BaseCollectionV1<'_> {
+impl<'a> Serialize for BaseCollectionV1<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let mut offset = self.key.serialize_to(buffer);
+        offset += self.update_authority.serialize_to(&mut buffer[offset..]);
+        offset += self.name.serialize_to(&mut buffer[offset..]);
+        offset += self.uri.serialize_to(&mut buffer[offset..]);
+        offset += self.num_minted.serialize_to(&mut buffer[offset..]);
+        offset += self.current_size.serialize_to(&mut buffer[offset..]);
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.key.serialized_size()
+            + self.update_authority.serialized_size()
+            + self.name.serialized_size()
+            + self.uri.serialized_size()
+            + self.num_minted.serialized_size()
+            + self.current_size.serialized_size()
+    }
+}
+
+impl Skip for BaseCollectionV1<'_> {
     // DOES NOT ASSUME KEY WAS SKIPPED
     fn skip_bytes(bytes: &[u8]) -> Result<usize, ProgramError> {
         let mut offset: usize = 1;
         offset += skip_sized::<Pubkey>();
-        offset += skip_sized_slice::<u8>(&bytes[offset..])?;
-        offset += skip_sized_slice::<u8>(&bytes[offset..])?;
+        offset += skip_sized_slice::<u8>(read_tail(bytes, offset)?)?;
+        offset += skip_sized_slice::<u8>(read_tail(bytes, offset)?)?;
         offset += skip_sized::<u32>();
         offset += skip_sized::<u32>();
         Ok(offset)
@@ -178,18 +326,22 @@ impl Serialize for Key {
         buffer[0] = *self as u8;
         1
     }
+
+    fn serialized_size(&self) -> usize {
+        1
+    }
 }
 
 impl Key {
     pub fn deserialize_from(bytes: &[u8]) -> Result<Self, ProgramError> {
-        match bytes[0] {
+        match read_u8(bytes, 0)? {
             0 => Ok(Key::Uninitialized),
             1 => Ok(Key::AssetV1),
             2 => Ok(Key::HashedAssetV1),
             3 => Ok(Key::PluginHeaderV1),
             4 => Ok(Key::PluginRegistryV1),
             5 => Ok(Key::CollectionV1),
-            _ => Err(ProgramError::InvalidAccountData),
+            _ => Err(MplCoreError::InvalidKey.into()),
         }
     }
 }
@@ -207,6 +359,10 @@ impl Serialize for PluginHeaderV1 {
             .serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.key.serialized_size() + self.plugin_registry_offset.serialized_size()
+    }
 }
 
 impl DeserializeSized for PluginHeaderV1 {
@@ -214,9 +370,9 @@ impl DeserializeSized for PluginHeaderV1 {
         let key = Key::deserialize_from(bytes)?;
 
         if !matches!(key, Key::PluginHeaderV1) {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(MplCoreError::InvalidKey.into());
         }
-        let plugin_registry_offset = u64::deserialize(&bytes[1..])?;
+        let plugin_registry_offset = u64::deserialize(read_tail(bytes, 1)?)?;
 
         Ok(Self {
             key,
@@ -238,6 +394,12 @@ impl Serialize for RegistryRecordSafe {
         offset += self.offset.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.plugin_type.serialized_size()
+            + self.authority.serialized_size()
+            + self.offset.serialized_size()
+    }
 }
 
 pub struct PluginRegistryV1Safe<'a> {
@@ -253,8 +415,15 @@ impl<'a> Serialize for PluginRegistryV1Safe<'a> {
         offset += self.external_registry.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.key.serialized_size()
+            + self.registry.serialized_size()
+            + self.external_registry.serialized_size()
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ExternalCheckResult {
     pub flags: u32,
 }
@@ -263,20 +432,28 @@ impl Serialize for ExternalCheckResult {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.flags.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.flags.serialized_size()
+    }
 }
 
-impl Serialize for (u8, ExternalCheckResult) {
+impl Serialize for (HookableLifecycleEvent, ExternalCheckResult) {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         let mut offset = self.0.serialize_to(buffer);
         offset += self.1.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.0.serialized_size() + self.1.serialized_size()
+    }
 }
 
 pub struct ExternalRegistryRecordSafe<'a> {
     pub plugin_type: u8,
     pub authority: PluginAuthority,
-    pub lifecycle_checks: Option<&'a [(u8, ExternalCheckResult)]>,
+    pub lifecycle_checks: Option<&'a [(HookableLifecycleEvent, ExternalCheckResult)]>,
     pub offset: u64,
     pub data_offset: Option<u64>,
     pub data_len: Option<u64>,
@@ -292,4 +469,13 @@ impl<'a> Serialize for ExternalRegistryRecordSafe<'a> {
         offset += self.data_len.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.plugin_type.serialized_size()
+            + self.authority.serialized_size()
+            + self.lifecycle_checks.serialized_size()
+            + self.offset.serialized_size()
+            + self.data_offset.serialized_size()
+            + self.data_len.serialized_size()
+    }
 }
@@ -0,0 +1,288 @@
+//! Borsh impls, so the same types can be shared with off-chain code that uses borsh.
+//!
+//! Serializing goes through `Serialize`, so the bytes are always the same as the ones sent in the CPIs.
+//! Borsh has no borrowed deserialization, so the types that borrow their data are deserialized into their
+//! owned mirrors in `owned`, which serialize by borrowing back into them
+
+use alloc::{vec, vec::Vec};
+
+use ::borsh::{
+    io::{Error, ErrorKind, Read, Result, Write},
+    BorshDeserialize, BorshSerialize,
+};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    data::{
+        asset::{Key, PluginHeaderV1},
+        burn_asset::BurnAssetV1InstructionData,
+        burn_collection::BurnCollectionV1InstructionData,
+        create_asset::{CreateAssetV1InstructionData, DataState, PrintEditionV1InstructionData},
+        create_collection::CreateCollectionV1InstructionData,
+        owned::{
+            OwnedAttribute, OwnedAttributes, OwnedAutograph, OwnedAutographSignature,
+            OwnedBurnAssetV1InstructionData, OwnedBurnCollectionV1InstructionData,
+            OwnedCompressionProof, OwnedCreateAssetV1InstructionData,
+            OwnedCreateCollectionV1InstructionData, OwnedGroups, OwnedHashablePluginSchema,
+            OwnedMasterEdition, OwnedPlugin, OwnedPluginAuthorityPairWithOption, OwnedRoyalties,
+            OwnedRuleSet, OwnedTransferV1InstructionData, OwnedUpdateAssetPluginV1InstructionData,
+            OwnedUpdateCollectionPluginV1InstructionData, OwnedUpdateDelegate,
+            OwnedVerifiedCreators,
+        },
+        plugins::{
+            CompressionProof, HashablePluginSchema, Plugin, PluginAuthority, PluginAuthorityPair,
+            PluginPatch, UpdateAuthority,
+        },
+        plugins::{
+            Creator, Edition, FreezeDelegate, FreezeExecute, PermanentFreezeDelegate,
+            PermanentFreezeExecute, PluginType, VerifiedCreatorsSignature,
+        },
+        transfer::TransferV1InstructionData,
+        update_asset_plugin::{
+            UpdateAssetPluginPatchV1InstructionData, UpdateAssetPluginV1InstructionData,
+        },
+        update_collection_plugin::UpdateCollectionPluginV1InstructionData,
+        DeserializeSized, Serialize, Skip,
+    },
+    Instructions,
+};
+
+fn serialize(value: &impl Serialize, writer: &mut impl Write) -> Result<()> {
+    let mut bytes = vec![0; value.serialized_size()];
+    value.serialize_to(&mut bytes);
+    writer.write_all(&bytes)
+}
+
+macro_rules! borsh_serialize {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl BorshSerialize for $ty {
+                fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                    serialize(self, writer)
+                }
+            }
+        )*
+    };
+}
+
+borsh_serialize!(
+    Key,
+    PluginHeaderV1,
+    DataState,
+    PluginAuthority,
+    UpdateAuthority,
+    Plugin<'_>,
+    PluginAuthorityPair<'_>,
+    HashablePluginSchema<'_>,
+    CompressionProof<'_>,
+    CreateAssetV1InstructionData<'_>,
+    PrintEditionV1InstructionData<'_>,
+    CreateCollectionV1InstructionData<'_>,
+    TransferV1InstructionData<'_>,
+    BurnAssetV1InstructionData<'_>,
+    BurnCollectionV1InstructionData<'_>,
+    UpdateAssetPluginV1InstructionData<'_>,
+    UpdateCollectionPluginV1InstructionData<'_>,
+);
+
+impl<P: PluginPatch> BorshSerialize for UpdateAssetPluginPatchV1InstructionData<P> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        serialize(self, writer)
+    }
+}
+
+fn invalid_data(_: ProgramError) -> Error {
+    Error::from(ErrorKind::InvalidData)
+}
+
+/// Reads an enum that is at most a discriminant and a pubkey, `Skip` tells how much follows the discriminant
+fn deserialize_enum<T: DeserializeSized + Skip>(reader: &mut impl Read) -> Result<T> {
+    let mut bytes = [0; 1 + size_of::<Pubkey>()];
+    reader.read_exact(&mut bytes[..1])?;
+    let len = T::skip_bytes(&bytes[..1]).map_err(invalid_data)?;
+    reader.read_exact(&mut bytes[1..len])?;
+    T::deserialize(&bytes[..len]).map_err(invalid_data)
+}
+
+impl BorshDeserialize for Key {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut key = [0];
+        reader.read_exact(&mut key)?;
+        Key::deserialize_from(&key).map_err(invalid_data)
+    }
+}
+
+impl BorshDeserialize for PluginHeaderV1 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0; 9];
+        reader.read_exact(&mut bytes)?;
+        <PluginHeaderV1 as DeserializeSized>::deserialize(&bytes).map_err(invalid_data)
+    }
+}
+
+impl BorshDeserialize for PluginAuthority {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        deserialize_enum(reader)
+    }
+}
+
+impl BorshDeserialize for UpdateAuthority {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        deserialize_enum(reader)
+    }
+}
+
+impl BorshSerialize for OwnedPlugin {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.with_plugin(|plugin| serialize(&plugin, writer))
+            .map_err(invalid_data)?
+    }
+}
+
+impl BorshSerialize for OwnedCompressionProof {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.with_compression_proof(|compression_proof| serialize(&compression_proof, writer))
+            .map_err(invalid_data)?
+    }
+}
+
+macro_rules! borsh_serialize_owned {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl BorshSerialize for $ty {
+                fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                    self.with_data(|data| serialize(data, writer))
+                        .map_err(invalid_data)?
+                }
+            }
+        )*
+    };
+}
+
+borsh_serialize_owned!(
+    OwnedCreateAssetV1InstructionData,
+    OwnedCreateCollectionV1InstructionData,
+    OwnedTransferV1InstructionData,
+    OwnedBurnAssetV1InstructionData,
+    OwnedBurnCollectionV1InstructionData,
+    OwnedUpdateAssetPluginV1InstructionData,
+    OwnedUpdateCollectionPluginV1InstructionData,
+);
+
+/// Reads the fields of a struct in the order they are serialized, after the instruction discriminant if one is given
+macro_rules! borsh_deserialize {
+    ($($ty:ty $([$instruction:ident])? { $($field:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl BorshDeserialize for $ty {
+                fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                    $(deserialize_discriminant(reader, Instructions::$instruction)?;)?
+                    Ok(Self {
+                        $($field: BorshDeserialize::deserialize_reader(reader)?,)*
+                    })
+                }
+            }
+        )*
+    };
+}
+
+borsh_deserialize!(
+    Creator { address, percentage },
+    FreezeDelegate { frozen },
+    PermanentFreezeDelegate { frozen },
+    Edition { number },
+    VerifiedCreatorsSignature { address, verified },
+    FreezeExecute { frozen },
+    PermanentFreezeExecute { frozen },
+    OwnedRoyalties { basis_points, creators, rule_set },
+    OwnedUpdateDelegate { additional_delegates },
+    OwnedGroups { groups },
+    OwnedAttribute { key, value },
+    OwnedAttributes { attribute_list },
+    OwnedMasterEdition { max_supply, name, uri },
+    OwnedVerifiedCreators { signatures },
+    OwnedAutographSignature { address, message },
+    OwnedAutograph { signatures },
+    OwnedPluginAuthorityPairWithOption { plugin, authority },
+    OwnedHashablePluginSchema { index, authority, plugin },
+    OwnedCompressionProof { owner, update_authority, name, uri, seq, plugins },
+    OwnedCreateAssetV1InstructionData [CreateAsset] { data_state, name, uri, plugins },
+    OwnedCreateCollectionV1InstructionData [CreateCollection] { name, uri, plugins },
+    OwnedTransferV1InstructionData [TransferAsset] { compression_proof },
+    OwnedBurnAssetV1InstructionData [BurnAsset] { compression_proof },
+    OwnedBurnCollectionV1InstructionData [BurnCollection] { compression_proof },
+    OwnedUpdateAssetPluginV1InstructionData [UpdateAssetPlugin] { plugin },
+    OwnedUpdateCollectionPluginV1InstructionData [UpdateCollectionPlugin] { plugin },
+);
+
+fn deserialize_discriminant(reader: &mut impl Read, instruction: Instructions) -> Result<()> {
+    if u8::deserialize_reader(reader)? == instruction.to_u8() {
+        Ok(())
+    } else {
+        Err(Error::from(ErrorKind::InvalidData))
+    }
+}
+
+impl BorshDeserialize for DataState {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        match u8::deserialize_reader(reader)? {
+            0 => Ok(Self::AccountState),
+            1 => Ok(Self::LedgerState),
+            _ => Err(Error::from(ErrorKind::InvalidData)),
+        }
+    }
+}
+
+impl BorshDeserialize for OwnedRuleSet {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        match u8::deserialize_reader(reader)? {
+            0 => Ok(Self::None),
+            1 => Ok(Self::ProgramAllowList(Vec::deserialize_reader(reader)?)),
+            2 => Ok(Self::ProgramDenyList(Vec::deserialize_reader(reader)?)),
+            _ => Err(Error::from(ErrorKind::InvalidData)),
+        }
+    }
+}
+
+impl BorshDeserialize for OwnedPlugin {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let plugin_type =
+            PluginType::try_from(u8::deserialize_reader(reader)?).map_err(invalid_data)?;
+        Ok(match plugin_type {
+            PluginType::Royalties => Self::Royalties(BorshDeserialize::deserialize_reader(reader)?),
+            PluginType::FreezeDelegate => {
+                Self::FreezeDelegate(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::BurnDelegate => Self::BurnDelegate,
+            PluginType::TransferDelegate => Self::TransferDelegate,
+            PluginType::UpdateDelegate => {
+                Self::UpdateDelegate(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::PermanentFreezeDelegate => {
+                Self::PermanentFreezeDelegate(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::Attributes => {
+                Self::Attributes(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::PermanentTransferDelegate => Self::PermanentTransferDelegate,
+            PluginType::PermanentBurnDelegate => Self::PermanentBurnDelegate,
+            PluginType::Edition => Self::Edition(BorshDeserialize::deserialize_reader(reader)?),
+            PluginType::MasterEdition => {
+                Self::MasterEdition(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::AddBlocker => Self::AddBlocker,
+            PluginType::ImmutableMetadata => Self::ImmutableMetadata,
+            PluginType::VerifiedCreators => {
+                Self::VerifiedCreators(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::Autograph => Self::Autograph(BorshDeserialize::deserialize_reader(reader)?),
+            PluginType::BubblegumV2 => Self::BubblegumV2,
+            PluginType::FreezeExecute => {
+                Self::FreezeExecute(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::PermanentFreezeExecute => {
+                Self::PermanentFreezeExecute(BorshDeserialize::deserialize_reader(reader)?)
+            }
+            PluginType::Groups => Self::Groups(BorshDeserialize::deserialize_reader(reader)?),
+        })
+    }
+}
@@ -1,9 +1,16 @@
+use pinocchio::program_error::ProgramError;
+
 use crate::{
-    data::{plugins::PluginAuthorityPair, Serialize},
+    data::{
+        plugins::{Edition, Plugin, PluginAuthority, PluginAuthorityPair},
+        view::CollectionView,
+        Serialize,
+    },
     Instructions,
 };
 
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DataState {
     AccountState,
     LedgerState,
@@ -15,7 +22,11 @@ impl Serialize for DataState {
             Self::AccountState => 0,
             Self::LedgerState => 1,
         };
-        return 1;
+        1
+    }
+
+    fn serialized_size(&self) -> usize {
+        1
     }
 }
 
@@ -36,6 +47,71 @@ impl<'a> Serialize for CreateAssetV1InstructionData<'a> {
         offset += self.uri.serialize_to(&mut buffer[offset..]);
         offset += self.plugins.serialize_to(&mut buffer[offset..]);
 
-        return offset;
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + self.data_state.serialized_size()
+            + self.name.serialized_size()
+            + self.uri.serialized_size()
+            + self.plugins.serialized_size()
+    }
+}
+
+/// CreateAssetV1 data for printing the next numbered edition of a collection,
+/// with the Edition plugin as its only plugin. The asset must be created into that collection
+pub struct PrintEditionV1InstructionData<'a> {
+    pub data_state: DataState,
+    pub name: &'a [u8],
+    pub uri: &'a [u8],
+    pub edition: Edition,
+    /// None lets mpl core pick the default authority, the update authority
+    pub edition_authority: Option<PluginAuthority>,
+}
+
+impl<'a> PrintEditionV1InstructionData<'a> {
+    /// Numbers the edition after what `collection` has minted so far, see `Edition::next`
+    pub fn next(
+        collection: &CollectionView,
+        name: &'a [u8],
+        uri: &'a [u8],
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            data_state: DataState::AccountState,
+            name,
+            uri,
+            edition: Edition::next(collection)?,
+            edition_authority: None,
+        })
+    }
+
+    fn plugins(&self) -> [PluginAuthorityPair<'static>; 1] {
+        [PluginAuthorityPair {
+            plugin: Plugin::Edition(Edition {
+                number: self.edition.number,
+            }),
+            authority: self.edition_authority,
+        }]
+    }
+}
+
+impl<'a> Serialize for PrintEditionV1InstructionData<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let plugins = self.plugins();
+
+        CreateAssetV1InstructionData {
+            data_state: self.data_state,
+            name: self.name,
+            uri: self.uri,
+            plugins: Some(&plugins),
+        }
+        .serialize_to(buffer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + self.data_state.serialized_size()
+            + self.name.serialized_size()
+            + self.uri.serialized_size()
+            + Some(&self.plugins()[..]).serialized_size()
     }
 }
@@ -18,6 +18,12 @@ impl<'a> Serialize for CreateCollectionV1InstructionData<'a> {
         offset += self.uri.serialize_to(&mut buffer[offset..]);
         offset += self.plugins.serialize_to(&mut buffer[offset..]);
 
-        return offset;
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + self.name.serialized_size()
+            + self.uri.serialized_size()
+            + self.plugins.serialized_size()
     }
 }
@@ -0,0 +1,591 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    data::{
+        asset::ExternalCheckResult, plugins::PluginAuthority, read_bytes, read_pubkey, read_slice,
+        read_tail, read_u8, DeserializeSized, Serialize,
+    },
+    error::MplCoreError,
+};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalPluginAdapterType {
+    LifecycleHook,
+    Oracle,
+    AppData,
+    LinkedLifecycleHook,
+    LinkedAppData,
+    DataSection,
+    AgentIdentity,
+}
+
+impl TryFrom<u8> for ExternalPluginAdapterType {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::LifecycleHook),
+            1 => Ok(Self::Oracle),
+            2 => Ok(Self::AppData),
+            3 => Ok(Self::LinkedLifecycleHook),
+            4 => Ok(Self::LinkedAppData),
+            5 => Ok(Self::DataSection),
+            6 => Ok(Self::AgentIdentity),
+            _ => Err(MplCoreError::UnknownPluginDiscriminant.into()),
+        }
+    }
+}
+
+/// The events an external plugin can hook into, used in the `lifecycle_checks` of its registry record
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookableLifecycleEvent {
+    Create,
+    Transfer,
+    Burn,
+    Update,
+    Execute,
+}
+
+impl TryFrom<u8> for HookableLifecycleEvent {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Create),
+            1 => Ok(Self::Transfer),
+            2 => Ok(Self::Burn),
+            3 => Ok(Self::Update),
+            4 => Ok(Self::Execute),
+            _ => Err(MplCoreError::InvalidExternalPluginAdapter.into()),
+        }
+    }
+}
+
+impl Serialize for HookableLifecycleEvent {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = *self as u8;
+        1
+    }
+
+    fn serialized_size(&self) -> usize {
+        1
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalPluginAdapterSchema {
+    Binary,
+    Json,
+    MsgPack,
+}
+
+impl TryFrom<u8> for ExternalPluginAdapterSchema {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Binary),
+            1 => Ok(Self::Json),
+            2 => Ok(Self::MsgPack),
+            _ => Err(MplCoreError::InvalidExternalPluginAdapter.into()),
+        }
+    }
+}
+
+impl Serialize for ExternalPluginAdapterSchema {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = *self as u8;
+        1
+    }
+
+    fn serialized_size(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Seed<'a> {
+    Collection,
+    Owner,
+    Recipient,
+    Asset,
+    Address(Pubkey),
+    Bytes(&'a [u8]),
+}
+
+impl<'a> Seed<'a> {
+    /// Reads a seed, returning it and how many bytes it took. `Bytes` borrows from `bytes`
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<(Self, usize), ProgramError> {
+        Ok(match read_u8(bytes, 0)? {
+            0 => (Self::Collection, 1),
+            1 => (Self::Owner, 1),
+            2 => (Self::Recipient, 1),
+            3 => (Self::Asset, 1),
+            4 => (
+                Self::Address(*read_pubkey(bytes, 1)?),
+                1 + size_of::<Pubkey>(),
+            ),
+            5 => {
+                let (seed, len) = read_bytes(bytes, 1)?;
+                (Self::Bytes(seed), 1 + len)
+            }
+            _ => return Err(MplCoreError::InvalidExternalPluginAdapter.into()),
+        })
+    }
+}
+
+impl<'a> Serialize for Seed<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        match self {
+            Self::Collection => {
+                buffer[0] = 0;
+                1
+            }
+            Self::Owner => {
+                buffer[0] = 1;
+                1
+            }
+            Self::Recipient => {
+                buffer[0] = 2;
+                1
+            }
+            Self::Asset => {
+                buffer[0] = 3;
+                1
+            }
+            Self::Address(address) => {
+                buffer[0] = 4;
+                1 + address.serialize_to(&mut buffer[1..])
+            }
+            Self::Bytes(bytes) => {
+                buffer[0] = 5;
+                1 + bytes.serialize_to(&mut buffer[1..])
+            }
+        }
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + match self {
+            Self::Address(address) => address.serialized_size(),
+            Self::Bytes(bytes) => bytes.serialized_size(),
+            Self::Collection | Self::Owner | Self::Recipient | Self::Asset => 0,
+        }
+    }
+}
+
+/// An account a lifecycle hook or oracle needs, resolved by mpl core when the event happens
+#[derive(Clone, Copy)]
+pub enum ExtraAccount<'a> {
+    PreconfiguredProgram {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredCollection {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredOwner {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredRecipient {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredAsset {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    CustomPda {
+        seeds: &'a [Seed<'a>],
+        custom_program_id: Option<Pubkey>,
+        is_signer: bool,
+        is_writable: bool,
+    },
+    Address {
+        address: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+    },
+}
+
+impl<'a> ExtraAccount<'a> {
+    fn flags(&self) -> (bool, bool) {
+        match self {
+            Self::PreconfiguredProgram {
+                is_signer,
+                is_writable,
+            }
+            | Self::PreconfiguredCollection {
+                is_signer,
+                is_writable,
+            }
+            | Self::PreconfiguredOwner {
+                is_signer,
+                is_writable,
+            }
+            | Self::PreconfiguredRecipient {
+                is_signer,
+                is_writable,
+            }
+            | Self::PreconfiguredAsset {
+                is_signer,
+                is_writable,
+            }
+            | Self::CustomPda {
+                is_signer,
+                is_writable,
+                ..
+            }
+            | Self::Address {
+                is_signer,
+                is_writable,
+                ..
+            } => (*is_signer, *is_writable),
+        }
+    }
+}
+
+impl<'a> Serialize for ExtraAccount<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let mut offset = 1;
+
+        buffer[0] = match self {
+            Self::PreconfiguredProgram { .. } => 0,
+            Self::PreconfiguredCollection { .. } => 1,
+            Self::PreconfiguredOwner { .. } => 2,
+            Self::PreconfiguredRecipient { .. } => 3,
+            Self::PreconfiguredAsset { .. } => 4,
+            Self::CustomPda {
+                seeds,
+                custom_program_id,
+                ..
+            } => {
+                offset += seeds.serialize_to(&mut buffer[offset..]);
+                offset += custom_program_id.serialize_to(&mut buffer[offset..]);
+                5
+            }
+            Self::Address { address, .. } => {
+                offset += address.serialize_to(&mut buffer[offset..]);
+                6
+            }
+        };
+
+        let (is_signer, is_writable) = self.flags();
+        offset += is_signer.serialize_to(&mut buffer[offset..]);
+        offset += is_writable.serialize_to(&mut buffer[offset..]);
+
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        // discriminant + is_signer + is_writable
+        3 + match self {
+            Self::CustomPda {
+                seeds,
+                custom_program_id,
+                ..
+            } => seeds.serialized_size() + custom_program_id.serialized_size(),
+            Self::Address { address, .. } => address.serialized_size(),
+            _ => 0,
+        }
+    }
+}
+
+/// Where an oracle account keeps its validation results
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationResultsOffset {
+    NoOffset,
+    /// Right after an 8 byte anchor discriminator
+    Anchor,
+    Custom(u64),
+}
+
+impl ValidationResultsOffset {
+    /// Reads the offset, returning it and how many bytes it took
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), ProgramError> {
+        Ok(match read_u8(bytes, 0)? {
+            0 => (Self::NoOffset, 1),
+            1 => (Self::Anchor, 1),
+            2 => (Self::Custom(u64::deserialize(read_slice(bytes, 1, 8)?)?), 9),
+            _ => return Err(MplCoreError::InvalidExternalPluginAdapter.into()),
+        })
+    }
+}
+
+impl Serialize for ValidationResultsOffset {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        match self {
+            Self::NoOffset => {
+                buffer[0] = 0;
+                1
+            }
+            Self::Anchor => {
+                buffer[0] = 1;
+                1
+            }
+            Self::Custom(offset) => {
+                buffer[0] = 2;
+                1 + offset.serialize_to(&mut buffer[1..])
+            }
+        }
+    }
+
+    fn serialized_size(&self) -> usize {
+        match self {
+            Self::NoOffset | Self::Anchor => 1,
+            Self::Custom(offset) => 1 + offset.serialized_size(),
+        }
+    }
+}
+
+/// Which linked plugin of the collection a DataSection holds the data of
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkedDataKey {
+    LinkedLifecycleHook(Pubkey),
+    LinkedAppData(PluginAuthority),
+}
+
+impl LinkedDataKey {
+    /// Reads the key, returning it and how many bytes it took
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), ProgramError> {
+        Ok(match read_u8(bytes, 0)? {
+            0 => (
+                Self::LinkedLifecycleHook(*read_pubkey(bytes, 1)?),
+                1 + size_of::<Pubkey>(),
+            ),
+            1 => {
+                let data_authority = PluginAuthority::deserialize(read_tail(bytes, 1)?)?;
+                (
+                    Self::LinkedAppData(data_authority),
+                    1 + data_authority.serialized_size(),
+                )
+            }
+            _ => return Err(MplCoreError::InvalidExternalPluginAdapter.into()),
+        })
+    }
+}
+
+impl Serialize for LinkedDataKey {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        match self {
+            Self::LinkedLifecycleHook(hooked_program) => {
+                buffer[0] = 0;
+                1 + hooked_program.serialize_to(&mut buffer[1..])
+            }
+            Self::LinkedAppData(data_authority) => {
+                buffer[0] = 1;
+                1 + data_authority.serialize_to(&mut buffer[1..])
+            }
+        }
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + match self {
+            Self::LinkedLifecycleHook(hooked_program) => hooked_program.serialized_size(),
+            Self::LinkedAppData(data_authority) => data_authority.serialized_size(),
+        }
+    }
+}
+
+/// Used by both LifecycleHook and LinkedLifecycleHook, which have the same fields
+pub struct LifecycleHook<'a> {
+    pub hooked_program: Pubkey,
+    pub extra_accounts: Option<&'a [ExtraAccount<'a>]>,
+    pub data_authority: Option<PluginAuthority>,
+    pub schema: ExternalPluginAdapterSchema,
+}
+
+impl<'a> Serialize for LifecycleHook<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let mut offset = self.hooked_program.serialize_to(buffer);
+        offset += self.extra_accounts.serialize_to(&mut buffer[offset..]);
+        offset += self.data_authority.serialize_to(&mut buffer[offset..]);
+        offset += self.schema.serialize_to(&mut buffer[offset..]);
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.hooked_program.serialized_size()
+            + self.extra_accounts.serialized_size()
+            + self.data_authority.serialized_size()
+            + self.schema.serialized_size()
+    }
+}
+
+pub struct Oracle<'a> {
+    pub base_address: Pubkey,
+    pub base_address_config: Option<ExtraAccount<'a>>,
+    pub results_offset: ValidationResultsOffset,
+}
+
+impl<'a> Serialize for Oracle<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let mut offset = self.base_address.serialize_to(buffer);
+        offset += self.base_address_config.serialize_to(&mut buffer[offset..]);
+        offset += self.results_offset.serialize_to(&mut buffer[offset..]);
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.base_address.serialized_size()
+            + self.base_address_config.serialized_size()
+            + self.results_offset.serialized_size()
+    }
+}
+
+/// Used by both AppData and LinkedAppData, which have the same fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AppData {
+    pub data_authority: PluginAuthority,
+    pub schema: ExternalPluginAdapterSchema,
+}
+
+impl AppData {
+    /// Reads the adapter, returning it and how many bytes it took
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), ProgramError> {
+        let data_authority = PluginAuthority::deserialize(bytes)?;
+        let len = data_authority.serialized_size();
+        let schema = ExternalPluginAdapterSchema::try_from(read_u8(bytes, len)?)?;
+        Ok((
+            Self {
+                data_authority,
+                schema,
+            },
+            len + 1,
+        ))
+    }
+}
+
+impl Serialize for AppData {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let mut offset = self.data_authority.serialize_to(buffer);
+        offset += self.schema.serialize_to(&mut buffer[offset..]);
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.data_authority.serialized_size() + self.schema.serialized_size()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataSection {
+    pub parent_key: LinkedDataKey,
+    pub schema: ExternalPluginAdapterSchema,
+}
+
+impl DataSection {
+    /// Reads the adapter, returning it and how many bytes it took
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), ProgramError> {
+        let (parent_key, len) = LinkedDataKey::from_bytes(bytes)?;
+        let schema = ExternalPluginAdapterSchema::try_from(read_u8(bytes, len)?)?;
+        Ok((Self { parent_key, schema }, len + 1))
+    }
+}
+
+impl Serialize for DataSection {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        let mut offset = self.parent_key.serialize_to(buffer);
+        offset += self.schema.serialize_to(&mut buffer[offset..]);
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.parent_key.serialized_size() + self.schema.serialized_size()
+    }
+}
+
+pub struct AgentIdentity<'a> {
+    pub uri: &'a [u8],
+}
+
+impl<'a> AgentIdentity<'a> {
+    /// Borrows the uri from `bytes`, returning the adapter and how many bytes it took
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<(Self, usize), ProgramError> {
+        let (uri, len) = read_bytes(bytes, 0)?;
+        Ok((Self { uri }, len))
+    }
+}
+
+impl<'a> Serialize for AgentIdentity<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        self.uri.serialize_to(buffer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.uri.serialized_size()
+    }
+}
+
+pub enum ExternalPluginAdapter<'a> {
+    LifecycleHook(LifecycleHook<'a>),
+    Oracle(Oracle<'a>),
+    AppData(AppData),
+    LinkedLifecycleHook(LifecycleHook<'a>),
+    LinkedAppData(AppData),
+    DataSection(DataSection),
+    AgentIdentity(AgentIdentity<'a>),
+}
+
+impl<'a> ExternalPluginAdapter<'a> {
+    pub fn adapter_type(&self) -> ExternalPluginAdapterType {
+        match self {
+            Self::LifecycleHook(_) => ExternalPluginAdapterType::LifecycleHook,
+            Self::Oracle(_) => ExternalPluginAdapterType::Oracle,
+            Self::AppData(_) => ExternalPluginAdapterType::AppData,
+            Self::LinkedLifecycleHook(_) => ExternalPluginAdapterType::LinkedLifecycleHook,
+            Self::LinkedAppData(_) => ExternalPluginAdapterType::LinkedAppData,
+            Self::DataSection(_) => ExternalPluginAdapterType::DataSection,
+            Self::AgentIdentity(_) => ExternalPluginAdapterType::AgentIdentity,
+        }
+    }
+}
+
+impl<'a> Serialize for ExternalPluginAdapter<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.adapter_type() as u8;
+        1 + match self {
+            Self::LifecycleHook(hook) | Self::LinkedLifecycleHook(hook) => {
+                hook.serialize_to(&mut buffer[1..])
+            }
+            Self::Oracle(oracle) => oracle.serialize_to(&mut buffer[1..]),
+            Self::AppData(app_data) | Self::LinkedAppData(app_data) => {
+                app_data.serialize_to(&mut buffer[1..])
+            }
+            Self::DataSection(data_section) => data_section.serialize_to(&mut buffer[1..]),
+            Self::AgentIdentity(agent_identity) => agent_identity.serialize_to(&mut buffer[1..]),
+        }
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + match self {
+            Self::LifecycleHook(hook) | Self::LinkedLifecycleHook(hook) => hook.serialized_size(),
+            Self::Oracle(oracle) => oracle.serialized_size(),
+            Self::AppData(app_data) | Self::LinkedAppData(app_data) => app_data.serialized_size(),
+            Self::DataSection(data_section) => data_section.serialized_size(),
+            Self::AgentIdentity(agent_identity) => agent_identity.serialized_size(),
+        }
+    }
+}
+
+/// An external plugin adapter as stored in an account: the adapter, followed by its data if it has any,
+/// plus what goes in its external registry record
+pub struct ExternalPluginAdapterWithData<'a> {
+    pub adapter: ExternalPluginAdapter<'a>,
+    pub authority: PluginAuthority,
+    /// Which events the plugin hooks into. mpl core sets this for LifecycleHook, LinkedLifecycleHook and Oracle
+    pub lifecycle_checks: Option<&'a [(HookableLifecycleEvent, ExternalCheckResult)]>,
+    /// The data written by the data authority, for the adapters that hold data
+    pub data: Option<&'a [u8]>,
+}
+
+impl<'a> ExternalPluginAdapterWithData<'a> {
+    /// How many bytes the adapter and its data take in the account
+    pub fn account_size(&self) -> usize {
+        self.adapter.serialized_size() + self.data.map_or(0, <[u8]>::len)
+    }
+}
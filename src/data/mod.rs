@@ -1,18 +1,39 @@
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
+use crate::error::MplCoreError;
+
 pub mod asset;
+#[cfg(feature = "borsh")]
+mod borsh;
 pub mod burn_asset;
 pub mod burn_collection;
 pub mod create_asset;
 pub mod create_collection;
+pub mod external_plugins;
+#[cfg(feature = "alloc")]
+pub mod owned;
+pub mod plugin_builder;
 pub mod plugins;
 pub mod transfer;
 pub mod update_asset_plugin;
 pub mod update_collection_plugin;
+pub mod view;
 
 pub trait Serialize {
     /// Serialize into a slice, starting at 0, returning how many bytes were written
     fn serialize_to(&self, buffer: &mut [u8]) -> usize;
+
+    /// How many bytes `serialize_to` will write. Required since 0.2, there is no default since the
+    /// only other way to know is serializing into a buffer that is large enough
+    fn serialized_size(&self) -> usize;
+
+    /// Same as `serialize_to`, but returns an error instead of panicking if the buffer is too small
+    fn try_serialize_to(&self, buffer: &mut [u8]) -> Result<usize, ProgramError> {
+        if buffer.len() < self.serialized_size() {
+            return Err(MplCoreError::BufferTooSmall.into());
+        }
+        Ok(self.serialize_to(buffer))
+    }
 }
 
 pub trait DeserializeSized {
@@ -33,10 +54,11 @@ pub fn skip_sized<T: Sized>() -> usize {
 // faster but items must be sized
 pub fn skip_sized_slice<T: Sized>(bytes: &[u8]) -> Result<usize, ProgramError> {
     let len = u32::deserialize(bytes)?;
-    Ok(
-        4 + (size_of::<T>()
-            * usize::try_from(len).map_err(|_| ProgramError::ArithmeticOverflow)?),
-    )
+    usize::try_from(len)
+        .ok()
+        .and_then(|len| len.checked_mul(size_of::<T>()))
+        .and_then(|size| size.checked_add(4))
+        .ok_or(MplCoreError::NumericalOverflow.into())
 }
 
 impl Serialize for &str {
@@ -52,6 +74,10 @@ impl Serialize for &str {
 
         total_len
     }
+
+    fn serialized_size(&self) -> usize {
+        4 + self.len()
+    }
 }
 
 impl<T: Serialize> Serialize for Option<T> {
@@ -67,15 +93,21 @@ impl<T: Serialize> Serialize for Option<T> {
             }
         }
     }
+
+    fn serialized_size(&self) -> usize {
+        match self {
+            None => 1,
+            Some(data) => 1 + data.serialized_size(),
+        }
+    }
 }
 
 impl<T: Skip> Skip for Option<T> {
     fn skip_bytes(buffer: &[u8]) -> Result<usize, ProgramError> {
-        let disc = buffer[0];
-        match disc {
+        match read_u8(buffer, 0)? {
             0 => Ok(1),
-            1 => Ok(1 + T::skip_bytes(&buffer[1..])?),
-            _ => Err(ProgramError::InvalidAccountData),
+            1 => Ok(1 + T::skip_bytes(read_tail(buffer, 1)?)?),
+            _ => Err(MplCoreError::InvalidOption.into()),
         }
     }
 }
@@ -93,6 +125,10 @@ impl<T: Serialize> Serialize for [T] {
 
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        4 + self.iter().map(T::serialized_size).sum::<usize>()
+    }
 }
 
 impl<T: Serialize> Serialize for &[T] {
@@ -108,19 +144,21 @@ impl<T: Serialize> Serialize for &[T] {
 
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        4 + self.iter().map(T::serialized_size).sum::<usize>()
+    }
 }
 
 impl<T: Skip> Skip for &[T] {
     fn skip_bytes(bytes: &[u8]) -> Result<usize, ProgramError> {
-        let len = u32::from_le_bytes(
-            bytes[0..4]
-                .try_into()
-                .map_err(|_| ProgramError::InvalidAccountData)?,
-        );
+        let len = u32::deserialize(bytes)?;
 
-        let mut offset = 4;
+        let mut offset: usize = 4;
         for _ in 0..len {
-            offset += T::skip_bytes(&bytes[offset..])?;
+            offset = offset
+                .checked_add(T::skip_bytes(read_tail(bytes, offset)?)?)
+                .ok_or(MplCoreError::NumericalOverflow)?;
         }
 
         Ok(offset)
@@ -132,6 +170,10 @@ impl Serialize for Pubkey {
         buffer[..32].copy_from_slice(self);
         32
     }
+
+    fn serialized_size(&self) -> usize {
+        32
+    }
 }
 
 impl Serialize for u8 {
@@ -139,6 +181,10 @@ impl Serialize for u8 {
         buffer[0] = *self;
         1
     }
+
+    fn serialized_size(&self) -> usize {
+        1
+    }
 }
 
 impl Serialize for u16 {
@@ -146,14 +192,18 @@ impl Serialize for u16 {
         buffer[..2].copy_from_slice(&self.to_le_bytes());
         2
     }
+
+    fn serialized_size(&self) -> usize {
+        2
+    }
 }
 
 impl DeserializeSized for u16 {
     fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
         Ok(u16::from_le_bytes(
-            bytes[0..2]
+            read_slice(bytes, 0, 2)?
                 .try_into()
-                .map_err(|_| ProgramError::InvalidAccountData)?,
+                .map_err(|_| MplCoreError::UnexpectedEndOfData)?,
         ))
     }
 }
@@ -163,14 +213,18 @@ impl Serialize for u32 {
         buffer[..4].copy_from_slice(&self.to_le_bytes());
         4
     }
+
+    fn serialized_size(&self) -> usize {
+        4
+    }
 }
 
 impl DeserializeSized for u32 {
     fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
         Ok(u32::from_le_bytes(
-            bytes[0..4]
+            read_slice(bytes, 0, 4)?
                 .try_into()
-                .map_err(|_| ProgramError::InvalidAccountData)?,
+                .map_err(|_| MplCoreError::UnexpectedEndOfData)?,
         ))
     }
 }
@@ -180,6 +234,10 @@ impl Serialize for u64 {
         buffer[..8].copy_from_slice(&self.to_le_bytes());
         8
     }
+
+    fn serialized_size(&self) -> usize {
+        8
+    }
 }
 
 impl Skip for u64 {
@@ -191,9 +249,9 @@ impl Skip for u64 {
 impl DeserializeSized for u64 {
     fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
         Ok(u64::from_le_bytes(
-            bytes[0..8]
+            read_slice(bytes, 0, 8)?
                 .try_into()
-                .map_err(|_| ProgramError::InvalidAccountData)?,
+                .map_err(|_| MplCoreError::UnexpectedEndOfData)?,
         ))
     }
 }
@@ -203,4 +261,69 @@ impl Serialize for bool {
         buffer[0] = if *self { 1 } else { 0 };
         1
     }
+
+    fn serialized_size(&self) -> usize {
+        1
+    }
+}
+
+impl DeserializeSized for bool {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
+        match bytes.first() {
+            Some(0) => Ok(false),
+            Some(1) => Ok(true),
+            Some(_) => Err(MplCoreError::InvalidBool.into()),
+            None => Err(MplCoreError::UnexpectedEndOfData.into()),
+        }
+    }
+}
+
+impl DeserializeSized for Pubkey {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
+        Ok(*read_pubkey(bytes, 0)?)
+    }
+}
+
+/// Bounds checked `&bytes[offset..offset + len]`
+pub(crate) fn read_slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], ProgramError> {
+    let end = offset
+        .checked_add(len)
+        .ok_or(MplCoreError::NumericalOverflow)?;
+    bytes
+        .get(offset..end)
+        .ok_or(MplCoreError::UnexpectedEndOfData.into())
+}
+
+/// Bounds checked `&bytes[offset..]`
+pub(crate) fn read_tail(bytes: &[u8], offset: usize) -> Result<&[u8], ProgramError> {
+    bytes
+        .get(offset..)
+        .ok_or(MplCoreError::UnexpectedEndOfData.into())
+}
+
+/// Bounds checked `bytes[offset]`
+pub(crate) fn read_u8(bytes: &[u8], offset: usize) -> Result<u8, ProgramError> {
+    bytes
+        .get(offset)
+        .copied()
+        .ok_or(MplCoreError::UnexpectedEndOfData.into())
+}
+
+/// Borrows the pubkey at `offset` without copying it
+pub(crate) fn read_pubkey(bytes: &[u8], offset: usize) -> Result<&Pubkey, ProgramError> {
+    read_slice(bytes, offset, size_of::<Pubkey>())?
+        .try_into()
+        .map_err(|_| MplCoreError::UnexpectedEndOfData.into())
+}
+
+/// Reads a length prefixed byte string (borsh `String` or `Vec<u8>`) at `offset`,
+/// returning it and how many bytes it took, prefix included
+pub(crate) fn read_bytes(bytes: &[u8], offset: usize) -> Result<(&[u8], usize), ProgramError> {
+    let len = u32::from_le_bytes(
+        read_slice(bytes, offset, 4)?
+            .try_into()
+            .map_err(|_| MplCoreError::UnexpectedEndOfData)?,
+    );
+    let len = usize::try_from(len).map_err(|_| MplCoreError::NumericalOverflow)?;
+    Ok((read_slice(bytes, offset + 4, len)?, 4 + len))
 }
@@ -0,0 +1,1168 @@
+use alloc::{string::String, vec, vec::Vec};
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    data::{
+        asset::{
+            AssetInfo, BaseAssetV1, BaseCollectionV1, CollectionInfo, ExternalCheckResult, Key,
+            PluginAuthorityPairWithoutOption,
+        },
+        burn_asset::BurnAssetV1InstructionData,
+        burn_collection::BurnCollectionV1InstructionData,
+        create_asset::{CreateAssetV1InstructionData, DataState},
+        create_collection::CreateCollectionV1InstructionData,
+        external_plugins::{
+            AgentIdentity, AppData, DataSection, ExternalPluginAdapter,
+            ExternalPluginAdapterSchema, ExternalPluginAdapterType, ExternalPluginAdapterWithData,
+            ExtraAccount, HookableLifecycleEvent, LifecycleHook, Oracle, Seed,
+            ValidationResultsOffset,
+        },
+        plugins::{
+            Attribute, Attributes, AttributesView, Autograph, AutographSignature, AutographView,
+            CompressionProof, Creator, Edition, FreezeDelegate, FreezeExecute, Groups,
+            HashablePluginSchema, MasterEdition, PermanentFreezeDelegate, PermanentFreezeExecute,
+            Plugin, PluginAuthority, PluginAuthorityPair, PluginType, Royalties, RuleSet,
+            UpdateAuthority, UpdateDelegate, VerifiedCreators, VerifiedCreatorsSignature,
+            VerifiedCreatorsView,
+        },
+        read_pubkey, read_tail, read_u8,
+        transfer::TransferV1InstructionData,
+        update_asset_plugin::UpdateAssetPluginV1InstructionData,
+        update_collection_plugin::UpdateCollectionPluginV1InstructionData,
+        view::{
+            AssetView, CollectionView, ExternalPluginRecord, ExternalRegistryIter, PluginRecord,
+            RegistryIter,
+        },
+        DeserializeSized, Serialize,
+    },
+    error::MplCoreError,
+};
+
+/// An asset decoded into owned data, for off-chain code and tests where borrowing from the account is a pain.
+/// Plugins and external plugin adapters newer than this crate are skipped, like the views do
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedAsset {
+    pub owner: Pubkey,
+    pub update_authority: UpdateAuthority,
+    pub name: String,
+    pub uri: String,
+    pub seq: Option<u64>,
+    pub plugins: Vec<OwnedPluginAuthorityPair>,
+    pub external_plugins: Vec<OwnedExternalPluginAdapterWithData>,
+}
+
+impl OwnedAsset {
+    /// Borrows everything into an `AssetInfo` for `f`, the borrowed plugins can't outlive the call
+    pub fn with_asset_info<R>(&self, f: impl FnOnce(&AssetInfo) -> R) -> Result<R, ProgramError> {
+        with_plugins(&self.plugins, |plugins| {
+            with_external_plugins(&self.external_plugins, |external_plugins| {
+                f(&AssetInfo {
+                    base: BaseAssetV1 {
+                        key: Key::AssetV1,
+                        owner: self.owner,
+                        update_authority: self.update_authority,
+                        name: self.name.as_bytes(),
+                        uri: self.uri.as_bytes(),
+                        seq: self.seq,
+                    },
+                    plugins,
+                    external_plugins,
+                })
+            })
+        })
+    }
+
+    /// The account data of the asset, plugin header and registries included
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ProgramError> {
+        self.with_asset_info(|info| to_bytes(info))
+    }
+}
+
+impl TryFrom<&AssetView<'_>> for OwnedAsset {
+    type Error = ProgramError;
+
+    fn try_from(asset: &AssetView<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            owner: *asset.owner,
+            update_authority: asset.update_authority,
+            name: to_string(asset.name)?,
+            uri: to_string(asset.uri)?,
+            seq: asset.seq,
+            plugins: owned_plugins(asset.plugins())?,
+            external_plugins: owned_external_plugins(asset.external_plugins()?)?,
+        })
+    }
+}
+
+/// A collection decoded into owned data, see `OwnedAsset`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedCollection {
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub uri: String,
+    pub num_minted: u32,
+    pub current_size: u32,
+    pub plugins: Vec<OwnedPluginAuthorityPair>,
+    pub external_plugins: Vec<OwnedExternalPluginAdapterWithData>,
+}
+
+impl OwnedCollection {
+    /// Borrows everything into a `CollectionInfo` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_collection_info<R>(
+        &self,
+        f: impl FnOnce(&CollectionInfo) -> R,
+    ) -> Result<R, ProgramError> {
+        with_plugins(&self.plugins, |plugins| {
+            with_external_plugins(&self.external_plugins, |external_plugins| {
+                f(&CollectionInfo {
+                    base: BaseCollectionV1 {
+                        key: Key::CollectionV1,
+                        update_authority: self.update_authority,
+                        name: self.name.as_bytes(),
+                        uri: self.uri.as_bytes(),
+                        num_minted: self.num_minted,
+                        current_size: self.current_size,
+                    },
+                    plugins,
+                    external_plugins,
+                })
+            })
+        })
+    }
+
+    /// The account data of the collection, plugin header and registries included
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ProgramError> {
+        self.with_collection_info(|info| to_bytes(info))
+    }
+}
+
+impl TryFrom<&CollectionView<'_>> for OwnedCollection {
+    type Error = ProgramError;
+
+    fn try_from(collection: &CollectionView<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            update_authority: *collection.update_authority,
+            name: to_string(collection.name)?,
+            uri: to_string(collection.uri)?,
+            num_minted: collection.num_minted,
+            current_size: collection.current_size,
+            plugins: owned_plugins(collection.plugins())?,
+            external_plugins: owned_external_plugins(collection.external_plugins()?)?,
+        })
+    }
+}
+
+/// Same as `PluginAuthorityPairWithoutOption`, the authority is always known once the plugin is in the account
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedPluginAuthorityPair {
+    pub plugin: OwnedPlugin,
+    pub authority: PluginAuthority,
+}
+
+/// Owned mirror of `PluginAuthorityPair`, for the plugins of the create instructions where
+/// a None authority lets mpl core pick the default one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedPluginAuthorityPairWithOption {
+    pub plugin: OwnedPlugin,
+    pub authority: Option<PluginAuthority>,
+}
+
+/// Owned mirror of `Plugin`, the plugins without borrowed data are reused as they are
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OwnedPlugin {
+    Royalties(OwnedRoyalties),
+    FreezeDelegate(FreezeDelegate),
+    BurnDelegate,
+    TransferDelegate,
+    UpdateDelegate(OwnedUpdateDelegate),
+    PermanentFreezeDelegate(PermanentFreezeDelegate),
+    Attributes(OwnedAttributes),
+    PermanentTransferDelegate,
+    PermanentBurnDelegate,
+    Edition(Edition),
+    MasterEdition(OwnedMasterEdition),
+    AddBlocker,
+    ImmutableMetadata,
+    VerifiedCreators(OwnedVerifiedCreators),
+    Autograph(OwnedAutograph),
+    BubblegumV2,
+    FreezeExecute(FreezeExecute),
+    PermanentFreezeExecute(PermanentFreezeExecute),
+    Groups(OwnedGroups),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedRoyalties {
+    pub basis_points: u16,
+    pub creators: Vec<Creator>,
+    pub rule_set: OwnedRuleSet,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OwnedRuleSet {
+    None,
+    ProgramAllowList(Vec<Pubkey>),
+    ProgramDenyList(Vec<Pubkey>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedUpdateDelegate {
+    pub additional_delegates: Vec<Pubkey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedGroups {
+    pub groups: Vec<Pubkey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedAttributes {
+    pub attribute_list: Vec<OwnedAttribute>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedMasterEdition {
+    pub max_supply: Option<u32>,
+    pub name: Option<String>,
+    pub uri: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedVerifiedCreators {
+    pub signatures: Vec<VerifiedCreatorsSignature>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedAutographSignature {
+    pub address: Pubkey,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedAutograph {
+    pub signatures: Vec<OwnedAutographSignature>,
+}
+
+/// Owned mirror of `ExternalPluginAdapterWithData`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedExternalPluginAdapterWithData {
+    pub adapter: OwnedExternalPluginAdapter,
+    pub authority: PluginAuthority,
+    pub lifecycle_checks: Option<Vec<(HookableLifecycleEvent, ExternalCheckResult)>>,
+    pub data: Option<Vec<u8>>,
+}
+
+/// Owned mirror of `ExternalPluginAdapter`, the adapters without borrowed data are reused as they are
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OwnedExternalPluginAdapter {
+    LifecycleHook(OwnedLifecycleHook),
+    Oracle(OwnedOracle),
+    AppData(AppData),
+    LinkedLifecycleHook(OwnedLifecycleHook),
+    LinkedAppData(AppData),
+    DataSection(DataSection),
+    AgentIdentity(OwnedAgentIdentity),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedLifecycleHook {
+    pub hooked_program: Pubkey,
+    pub extra_accounts: Option<Vec<OwnedExtraAccount>>,
+    pub data_authority: Option<PluginAuthority>,
+    pub schema: ExternalPluginAdapterSchema,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedOracle {
+    pub base_address: Pubkey,
+    pub base_address_config: Option<OwnedExtraAccount>,
+    pub results_offset: ValidationResultsOffset,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedAgentIdentity {
+    pub uri: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OwnedExtraAccount {
+    PreconfiguredProgram {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredCollection {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredOwner {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredRecipient {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredAsset {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    CustomPda {
+        seeds: Vec<OwnedSeed>,
+        custom_program_id: Option<Pubkey>,
+        is_signer: bool,
+        is_writable: bool,
+    },
+    Address {
+        address: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OwnedSeed {
+    Collection,
+    Owner,
+    Recipient,
+    Asset,
+    Address(Pubkey),
+    Bytes(Vec<u8>),
+}
+
+impl OwnedExternalPluginAdapterWithData {
+    /// Decodes the adapter an external registry record points to, and copies its data.
+    /// Same as `OwnedPlugin::from_record` for the adapters newer than this crate
+    pub fn from_record(record: &ExternalPluginRecord) -> Result<Self, ProgramError> {
+        let bytes = record.adapter;
+        let adapter = match ExternalPluginAdapterType::try_from(record.plugin_type)? {
+            ExternalPluginAdapterType::LifecycleHook => {
+                OwnedExternalPluginAdapter::LifecycleHook(OwnedLifecycleHook::from_bytes(bytes)?)
+            }
+            ExternalPluginAdapterType::Oracle => {
+                OwnedExternalPluginAdapter::Oracle(OwnedOracle::from_bytes(bytes)?)
+            }
+            ExternalPluginAdapterType::AppData => {
+                OwnedExternalPluginAdapter::AppData(AppData::from_bytes(bytes)?.0)
+            }
+            ExternalPluginAdapterType::LinkedLifecycleHook => {
+                OwnedExternalPluginAdapter::LinkedLifecycleHook(OwnedLifecycleHook::from_bytes(
+                    bytes,
+                )?)
+            }
+            ExternalPluginAdapterType::LinkedAppData => {
+                OwnedExternalPluginAdapter::LinkedAppData(AppData::from_bytes(bytes)?.0)
+            }
+            ExternalPluginAdapterType::DataSection => {
+                OwnedExternalPluginAdapter::DataSection(DataSection::from_bytes(bytes)?.0)
+            }
+            ExternalPluginAdapterType::AgentIdentity => {
+                OwnedExternalPluginAdapter::AgentIdentity(OwnedAgentIdentity {
+                    uri: to_string(AgentIdentity::from_bytes(bytes)?.0.uri)?,
+                })
+            }
+        };
+
+        Ok(Self {
+            adapter,
+            authority: record.authority,
+            lifecycle_checks: record
+                .lifecycle_checks
+                .map(|checks| checks.iter().collect::<Result<_, ProgramError>>())
+                .transpose()?,
+            data: record.data.map(<[u8]>::to_vec),
+        })
+    }
+}
+
+impl OwnedExternalPluginAdapter {
+    /// The extra accounts to borrow the seeds of, the ones of a lifecycle hook or the oracle's base address config
+    fn extra_accounts(&self) -> &[OwnedExtraAccount] {
+        match self {
+            Self::LifecycleHook(hook) | Self::LinkedLifecycleHook(hook) => {
+                hook.extra_accounts.as_deref().unwrap_or_default()
+            }
+            Self::Oracle(oracle) => oracle.base_address_config.as_slice(),
+            Self::AppData(_)
+            | Self::LinkedAppData(_)
+            | Self::DataSection(_)
+            | Self::AgentIdentity(_) => &[],
+        }
+    }
+
+    /// Borrows the adapter, `extra_accounts` are its `extra_accounts()` borrowed
+    fn as_adapter<'a>(
+        &'a self,
+        extra_accounts: &'a [ExtraAccount<'a>],
+    ) -> ExternalPluginAdapter<'a> {
+        let hook = |hook: &'a OwnedLifecycleHook| LifecycleHook {
+            hooked_program: hook.hooked_program,
+            extra_accounts: hook.extra_accounts.as_ref().map(|_| extra_accounts),
+            data_authority: hook.data_authority,
+            schema: hook.schema,
+        };
+        match self {
+            Self::LifecycleHook(plugin) => ExternalPluginAdapter::LifecycleHook(hook(plugin)),
+            Self::Oracle(oracle) => ExternalPluginAdapter::Oracle(Oracle {
+                base_address: oracle.base_address,
+                // the base address config is the oracle's only extra account
+                base_address_config: extra_accounts.first().copied(),
+                results_offset: oracle.results_offset,
+            }),
+            Self::AppData(plugin) => ExternalPluginAdapter::AppData(*plugin),
+            Self::LinkedLifecycleHook(plugin) => {
+                ExternalPluginAdapter::LinkedLifecycleHook(hook(plugin))
+            }
+            Self::LinkedAppData(plugin) => ExternalPluginAdapter::LinkedAppData(*plugin),
+            Self::DataSection(plugin) => ExternalPluginAdapter::DataSection(*plugin),
+            Self::AgentIdentity(plugin) => ExternalPluginAdapter::AgentIdentity(AgentIdentity {
+                uri: plugin.uri.as_bytes(),
+            }),
+        }
+    }
+}
+
+impl OwnedLifecycleHook {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramError> {
+        let hooked_program = *read_pubkey(bytes, 0)?;
+        let mut offset = size_of::<Pubkey>();
+
+        let extra_accounts = match read_u8(bytes, offset)? {
+            0 => None,
+            1 => {
+                let len = u32::deserialize(read_tail(bytes, offset + 1)?)?;
+                offset += 4;
+                let mut extra_accounts = Vec::new();
+                for _ in 0..len {
+                    let (extra_account, len) =
+                        OwnedExtraAccount::from_bytes(read_tail(bytes, offset + 1)?)?;
+                    offset += len;
+                    extra_accounts.push(extra_account);
+                }
+                Some(extra_accounts)
+            }
+            _ => return Err(MplCoreError::InvalidOption.into()),
+        };
+        offset += 1;
+
+        let data_authority = match read_u8(bytes, offset)? {
+            0 => None,
+            1 => Some(PluginAuthority::deserialize(read_tail(bytes, offset + 1)?)?),
+            _ => return Err(MplCoreError::InvalidOption.into()),
+        };
+        offset += data_authority.serialized_size();
+
+        Ok(Self {
+            hooked_program,
+            extra_accounts,
+            data_authority,
+            schema: ExternalPluginAdapterSchema::try_from(read_u8(bytes, offset)?)?,
+        })
+    }
+}
+
+impl OwnedOracle {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramError> {
+        let base_address = *read_pubkey(bytes, 0)?;
+        let mut offset = size_of::<Pubkey>();
+
+        let base_address_config = match read_u8(bytes, offset)? {
+            0 => None,
+            1 => {
+                let (extra_account, len) =
+                    OwnedExtraAccount::from_bytes(read_tail(bytes, offset + 1)?)?;
+                offset += len;
+                Some(extra_account)
+            }
+            _ => return Err(MplCoreError::InvalidOption.into()),
+        };
+        offset += 1;
+
+        Ok(Self {
+            base_address,
+            base_address_config,
+            results_offset: ValidationResultsOffset::from_bytes(read_tail(bytes, offset)?)?.0,
+        })
+    }
+}
+
+impl OwnedExtraAccount {
+    /// Reads an extra account, returning it and how many bytes it took
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), ProgramError> {
+        let mut offset = 1;
+        let discriminant = read_u8(bytes, 0)?;
+        let mut seeds = Vec::new();
+        let mut custom_program_id = None;
+        let mut address = [0; 32];
+        match discriminant {
+            0..=4 => {}
+            5 => {
+                let len = u32::deserialize(read_tail(bytes, offset)?)?;
+                offset += 4;
+                for _ in 0..len {
+                    let (seed, len) = Seed::from_bytes(read_tail(bytes, offset)?)?;
+                    offset += len;
+                    seeds.push(OwnedSeed::from(seed));
+                }
+                custom_program_id = match read_u8(bytes, offset)? {
+                    0 => None,
+                    1 => Some(*read_pubkey(bytes, offset + 1)?),
+                    _ => return Err(MplCoreError::InvalidOption.into()),
+                };
+                offset += custom_program_id.serialized_size();
+            }
+            6 => {
+                address = *read_pubkey(bytes, offset)?;
+                offset += size_of::<Pubkey>();
+            }
+            _ => return Err(MplCoreError::InvalidExternalPluginAdapter.into()),
+        }
+
+        let is_signer = bool::deserialize(read_tail(bytes, offset)?)?;
+        let is_writable = bool::deserialize(read_tail(bytes, offset + 1)?)?;
+        let extra_account = match discriminant {
+            0 => Self::PreconfiguredProgram {
+                is_signer,
+                is_writable,
+            },
+            1 => Self::PreconfiguredCollection {
+                is_signer,
+                is_writable,
+            },
+            2 => Self::PreconfiguredOwner {
+                is_signer,
+                is_writable,
+            },
+            3 => Self::PreconfiguredRecipient {
+                is_signer,
+                is_writable,
+            },
+            4 => Self::PreconfiguredAsset {
+                is_signer,
+                is_writable,
+            },
+            5 => Self::CustomPda {
+                seeds,
+                custom_program_id,
+                is_signer,
+                is_writable,
+            },
+            _ => Self::Address {
+                address,
+                is_signer,
+                is_writable,
+            },
+        };
+        Ok((extra_account, offset + 2))
+    }
+
+    /// Borrows the seeds of a custom PDA, every other extra account has none
+    fn seeds(&self) -> Vec<Seed<'_>> {
+        match self {
+            Self::CustomPda { seeds, .. } => seeds.iter().map(OwnedSeed::as_seed).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Borrows the extra account, `seeds` are its `seeds()`
+    fn as_extra_account<'a>(&'a self, seeds: &'a [Seed<'a>]) -> ExtraAccount<'a> {
+        match *self {
+            Self::PreconfiguredProgram {
+                is_signer,
+                is_writable,
+            } => ExtraAccount::PreconfiguredProgram {
+                is_signer,
+                is_writable,
+            },
+            Self::PreconfiguredCollection {
+                is_signer,
+                is_writable,
+            } => ExtraAccount::PreconfiguredCollection {
+                is_signer,
+                is_writable,
+            },
+            Self::PreconfiguredOwner {
+                is_signer,
+                is_writable,
+            } => ExtraAccount::PreconfiguredOwner {
+                is_signer,
+                is_writable,
+            },
+            Self::PreconfiguredRecipient {
+                is_signer,
+                is_writable,
+            } => ExtraAccount::PreconfiguredRecipient {
+                is_signer,
+                is_writable,
+            },
+            Self::PreconfiguredAsset {
+                is_signer,
+                is_writable,
+            } => ExtraAccount::PreconfiguredAsset {
+                is_signer,
+                is_writable,
+            },
+            Self::CustomPda {
+                custom_program_id,
+                is_signer,
+                is_writable,
+                ..
+            } => ExtraAccount::CustomPda {
+                seeds,
+                custom_program_id,
+                is_signer,
+                is_writable,
+            },
+            Self::Address {
+                address,
+                is_signer,
+                is_writable,
+            } => ExtraAccount::Address {
+                address,
+                is_signer,
+                is_writable,
+            },
+        }
+    }
+}
+
+impl From<Seed<'_>> for OwnedSeed {
+    fn from(seed: Seed) -> Self {
+        match seed {
+            Seed::Collection => Self::Collection,
+            Seed::Owner => Self::Owner,
+            Seed::Recipient => Self::Recipient,
+            Seed::Asset => Self::Asset,
+            Seed::Address(address) => Self::Address(address),
+            Seed::Bytes(bytes) => Self::Bytes(bytes.to_vec()),
+        }
+    }
+}
+
+impl OwnedSeed {
+    fn as_seed(&self) -> Seed<'_> {
+        match self {
+            Self::Collection => Seed::Collection,
+            Self::Owner => Seed::Owner,
+            Self::Recipient => Seed::Recipient,
+            Self::Asset => Seed::Asset,
+            Self::Address(address) => Seed::Address(*address),
+            Self::Bytes(bytes) => Seed::Bytes(bytes),
+        }
+    }
+}
+
+/// Owned mirror of `HashablePluginSchema`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedHashablePluginSchema {
+    pub index: u64,
+    pub authority: PluginAuthority,
+    pub plugin: OwnedPlugin,
+}
+
+/// Owned mirror of `CompressionProof`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedCompressionProof {
+    pub owner: Pubkey,
+    pub update_authority: UpdateAuthority,
+    pub name: String,
+    pub uri: String,
+    pub seq: u64,
+    pub plugins: Vec<OwnedHashablePluginSchema>,
+}
+
+impl OwnedCompressionProof {
+    /// Borrows everything into a `CompressionProof` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_compression_proof<R>(
+        &self,
+        f: impl FnOnce(CompressionProof) -> R,
+    ) -> Result<R, ProgramError> {
+        let storage = plugin_storage(self.plugins.iter().map(|schema| &schema.plugin));
+        let plugins = self
+            .plugins
+            .iter()
+            .zip(&storage)
+            .map(|(schema, storage)| {
+                Ok(HashablePluginSchema {
+                    index: schema.index,
+                    authority: schema.authority,
+                    plugin: schema.plugin.as_plugin(storage)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ProgramError>>()?;
+        Ok(f(CompressionProof {
+            owner: self.owner,
+            update_authority: self.update_authority,
+            name: self.name.as_bytes(),
+            uri: self.uri.as_bytes(),
+            seq: self.seq,
+            plugins: &plugins,
+        }))
+    }
+}
+
+/// Owned mirror of `CreateAssetV1InstructionData`. `PrintEditionV1InstructionData` is the same
+/// instruction, it reads back as one of these with its Edition as the only plugin
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedCreateAssetV1InstructionData {
+    pub data_state: DataState,
+    pub name: String,
+    pub uri: String,
+    pub plugins: Option<Vec<OwnedPluginAuthorityPairWithOption>>,
+}
+
+impl OwnedCreateAssetV1InstructionData {
+    /// Borrows everything into a `CreateAssetV1InstructionData` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_data<R>(
+        &self,
+        f: impl FnOnce(&CreateAssetV1InstructionData) -> R,
+    ) -> Result<R, ProgramError> {
+        with_plugin_pairs(self.plugins.as_deref(), |plugins| {
+            f(&CreateAssetV1InstructionData {
+                data_state: self.data_state,
+                name: self.name.as_bytes(),
+                uri: self.uri.as_bytes(),
+                plugins,
+            })
+        })
+    }
+}
+
+/// Owned mirror of `CreateCollectionV1InstructionData`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedCreateCollectionV1InstructionData {
+    pub name: String,
+    pub uri: String,
+    pub plugins: Option<Vec<OwnedPluginAuthorityPairWithOption>>,
+}
+
+impl OwnedCreateCollectionV1InstructionData {
+    /// Borrows everything into a `CreateCollectionV1InstructionData` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_data<R>(
+        &self,
+        f: impl FnOnce(&CreateCollectionV1InstructionData) -> R,
+    ) -> Result<R, ProgramError> {
+        with_plugin_pairs(self.plugins.as_deref(), |plugins| {
+            f(&CreateCollectionV1InstructionData {
+                name: self.name.as_bytes(),
+                uri: self.uri.as_bytes(),
+                plugins,
+            })
+        })
+    }
+}
+
+/// Owned mirror of `TransferV1InstructionData`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedTransferV1InstructionData {
+    pub compression_proof: Option<OwnedCompressionProof>,
+}
+
+impl OwnedTransferV1InstructionData {
+    /// Borrows everything into a `TransferV1InstructionData` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_data<R>(
+        &self,
+        f: impl FnOnce(&TransferV1InstructionData) -> R,
+    ) -> Result<R, ProgramError> {
+        with_optional_proof(self.compression_proof.as_ref(), |compression_proof| {
+            f(&TransferV1InstructionData { compression_proof })
+        })
+    }
+}
+
+/// Owned mirror of `BurnAssetV1InstructionData`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedBurnAssetV1InstructionData {
+    pub compression_proof: Option<OwnedCompressionProof>,
+}
+
+impl OwnedBurnAssetV1InstructionData {
+    /// Borrows everything into a `BurnAssetV1InstructionData` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_data<R>(
+        &self,
+        f: impl FnOnce(&BurnAssetV1InstructionData) -> R,
+    ) -> Result<R, ProgramError> {
+        with_optional_proof(self.compression_proof.as_ref(), |compression_proof| {
+            f(&BurnAssetV1InstructionData { compression_proof })
+        })
+    }
+}
+
+/// Owned mirror of `BurnCollectionV1InstructionData`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedBurnCollectionV1InstructionData {
+    pub compression_proof: Option<OwnedCompressionProof>,
+}
+
+impl OwnedBurnCollectionV1InstructionData {
+    /// Borrows everything into a `BurnCollectionV1InstructionData` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_data<R>(
+        &self,
+        f: impl FnOnce(&BurnCollectionV1InstructionData) -> R,
+    ) -> Result<R, ProgramError> {
+        with_optional_proof(self.compression_proof.as_ref(), |compression_proof| {
+            f(&BurnCollectionV1InstructionData { compression_proof })
+        })
+    }
+}
+
+/// Owned mirror of `UpdateAssetPluginV1InstructionData`. `UpdateAssetPluginPatchV1InstructionData` is
+/// the same instruction, it reads back as one of these with the patched plugin
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedUpdateAssetPluginV1InstructionData {
+    pub plugin: OwnedPlugin,
+}
+
+impl OwnedUpdateAssetPluginV1InstructionData {
+    /// Borrows everything into an `UpdateAssetPluginV1InstructionData` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_data<R>(
+        &self,
+        f: impl FnOnce(&UpdateAssetPluginV1InstructionData) -> R,
+    ) -> Result<R, ProgramError> {
+        self.plugin
+            .with_plugin(|plugin| f(&UpdateAssetPluginV1InstructionData { plugin }))
+    }
+}
+
+/// Owned mirror of `UpdateCollectionPluginV1InstructionData`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedUpdateCollectionPluginV1InstructionData {
+    pub plugin: OwnedPlugin,
+}
+
+impl OwnedUpdateCollectionPluginV1InstructionData {
+    /// Borrows everything into an `UpdateCollectionPluginV1InstructionData` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_data<R>(
+        &self,
+        f: impl FnOnce(&UpdateCollectionPluginV1InstructionData) -> R,
+    ) -> Result<R, ProgramError> {
+        self.plugin
+            .with_plugin(|plugin| f(&UpdateCollectionPluginV1InstructionData { plugin }))
+    }
+}
+
+impl OwnedPlugin {
+    /// Borrows the plugin into a `Plugin` for `f`, see `OwnedAsset::with_asset_info`
+    pub fn with_plugin<R>(&self, f: impl FnOnce(Plugin) -> R) -> Result<R, ProgramError> {
+        let storage = PluginStorage::new(self);
+        Ok(f(self.as_plugin(&storage)?))
+    }
+
+    /// Decodes the plugin a registry record points to. Plugins newer than this crate are an
+    /// `UnknownPluginDiscriminant` error here, `OwnedAsset`/`OwnedCollection` skip them instead
+    pub fn from_record(record: &PluginRecord) -> Result<Self, ProgramError> {
+        let data = record.data;
+        Ok(match PluginType::try_from(record.plugin_type)? {
+            PluginType::Royalties => {
+                let royalties = Royalties::from_bytes(data)?;
+                Self::Royalties(OwnedRoyalties {
+                    basis_points: royalties.basis_points,
+                    creators: royalties.creators.to_vec(),
+                    rule_set: match royalties.rule_set {
+                        RuleSet::None => OwnedRuleSet::None,
+                        RuleSet::ProgramAllowList(keys) => {
+                            OwnedRuleSet::ProgramAllowList(keys.to_vec())
+                        }
+                        RuleSet::ProgramDenyList(keys) => {
+                            OwnedRuleSet::ProgramDenyList(keys.to_vec())
+                        }
+                    },
+                })
+            }
+            PluginType::FreezeDelegate => Self::FreezeDelegate(FreezeDelegate::deserialize(data)?),
+            PluginType::BurnDelegate => Self::BurnDelegate,
+            PluginType::TransferDelegate => Self::TransferDelegate,
+            PluginType::UpdateDelegate => Self::UpdateDelegate(OwnedUpdateDelegate {
+                additional_delegates: UpdateDelegate::from_bytes(data)?
+                    .additional_delegates
+                    .to_vec(),
+            }),
+            PluginType::PermanentFreezeDelegate => {
+                Self::PermanentFreezeDelegate(PermanentFreezeDelegate::deserialize(data)?)
+            }
+            PluginType::Attributes => Self::Attributes(OwnedAttributes {
+                attribute_list: AttributesView::from_bytes(data)?
+                    .iter()
+                    .map(|attribute| {
+                        Ok(OwnedAttribute {
+                            key: to_string(attribute.key)?,
+                            value: to_string(attribute.value)?,
+                        })
+                    })
+                    .collect::<Result<_, ProgramError>>()?,
+            }),
+            PluginType::PermanentTransferDelegate => Self::PermanentTransferDelegate,
+            PluginType::PermanentBurnDelegate => Self::PermanentBurnDelegate,
+            PluginType::Edition => Self::Edition(Edition::deserialize(data)?),
+            PluginType::MasterEdition => {
+                let master_edition = MasterEdition::from_bytes(data)?;
+                Self::MasterEdition(OwnedMasterEdition {
+                    max_supply: master_edition.max_supply,
+                    name: master_edition.name.map(to_string).transpose()?,
+                    uri: master_edition.uri.map(to_string).transpose()?,
+                })
+            }
+            PluginType::AddBlocker => Self::AddBlocker,
+            PluginType::ImmutableMetadata => Self::ImmutableMetadata,
+            PluginType::VerifiedCreators => Self::VerifiedCreators(OwnedVerifiedCreators {
+                signatures: VerifiedCreatorsView::from_bytes(data)?.iter().collect(),
+            }),
+            PluginType::Autograph => Self::Autograph(OwnedAutograph {
+                signatures: AutographView::from_bytes(data)?
+                    .iter()
+                    .map(|signature| {
+                        Ok(OwnedAutographSignature {
+                            address: signature.address,
+                            message: to_string(signature.message)?,
+                        })
+                    })
+                    .collect::<Result<_, ProgramError>>()?,
+            }),
+            PluginType::BubblegumV2 => Self::BubblegumV2,
+            PluginType::FreezeExecute => Self::FreezeExecute(FreezeExecute {
+                frozen: bool::deserialize(data)?,
+            }),
+            PluginType::PermanentFreezeExecute => {
+                Self::PermanentFreezeExecute(PermanentFreezeExecute {
+                    frozen: bool::deserialize(data)?,
+                })
+            }
+            PluginType::Groups => Self::Groups(OwnedGroups {
+                groups: Groups::from_bytes(data)?.groups.to_vec(),
+            }),
+        })
+    }
+
+    /// Borrows the plugin, the lists of structs are collected into `storage` first
+    fn as_plugin<'a>(&'a self, storage: &'a PluginStorage<'a>) -> Result<Plugin<'a>, ProgramError> {
+        Ok(match (self, storage) {
+            (Self::Royalties(royalties), _) => Plugin::Royalties(Royalties {
+                basis_points: royalties.basis_points,
+                creators: &royalties.creators,
+                rule_set: match &royalties.rule_set {
+                    OwnedRuleSet::None => RuleSet::None,
+                    OwnedRuleSet::ProgramAllowList(keys) => RuleSet::ProgramAllowList(keys),
+                    OwnedRuleSet::ProgramDenyList(keys) => RuleSet::ProgramDenyList(keys),
+                },
+            }),
+            (Self::FreezeDelegate(plugin), _) => Plugin::FreezeDelegate(*plugin),
+            (Self::BurnDelegate, _) => Plugin::BurnDelegate,
+            (Self::TransferDelegate, _) => Plugin::TransferDelegate,
+            (Self::UpdateDelegate(plugin), _) => Plugin::UpdateDelegate(UpdateDelegate {
+                additional_delegates: &plugin.additional_delegates,
+            }),
+            (Self::PermanentFreezeDelegate(plugin), _) => Plugin::PermanentFreezeDelegate(*plugin),
+            (Self::Attributes(_), PluginStorage::Attributes(attribute_list)) => {
+                Plugin::Attributes(Attributes { attribute_list })
+            }
+            (Self::PermanentTransferDelegate, _) => Plugin::PermanentTransferDelegate,
+            (Self::PermanentBurnDelegate, _) => Plugin::PermanentBurnDelegate,
+            (Self::Edition(plugin), _) => Plugin::Edition(*plugin),
+            (Self::MasterEdition(plugin), _) => Plugin::MasterEdition(MasterEdition {
+                max_supply: plugin.max_supply,
+                name: plugin.name.as_ref().map(|name| name.as_bytes()),
+                uri: plugin.uri.as_ref().map(|uri| uri.as_bytes()),
+            }),
+            (Self::AddBlocker, _) => Plugin::AddBlocker,
+            (Self::ImmutableMetadata, _) => Plugin::ImmutableMetadata,
+            (Self::VerifiedCreators(plugin), _) => Plugin::VerifiedCreators(VerifiedCreators {
+                signatures: &plugin.signatures,
+            }),
+            (Self::Autograph(_), PluginStorage::Autograph(signatures)) => {
+                Plugin::Autograph(Autograph { signatures })
+            }
+            (Self::BubblegumV2, _) => Plugin::BubblegumV2,
+            (Self::FreezeExecute(plugin), _) => Plugin::FreezeExecute(*plugin),
+            (Self::PermanentFreezeExecute(plugin), _) => Plugin::PermanentFreezeExecute(*plugin),
+            (Self::Groups(plugin), _) => Plugin::Groups(Groups {
+                groups: &plugin.groups,
+            }),
+            // storage from PluginStorage::new always matches the plugin
+            (Self::Attributes(_) | Self::Autograph(_), _) => {
+                return Err(MplCoreError::PluginTypeMismatch.into())
+            }
+        })
+    }
+}
+
+/// `Plugin` borrows slices of borrowing structs, which have to live somewhere while it is used
+enum PluginStorage<'a> {
+    None,
+    Attributes(Vec<Attribute<'a>>),
+    Autograph(Vec<AutographSignature<'a>>),
+}
+
+impl<'a> PluginStorage<'a> {
+    fn new(plugin: &'a OwnedPlugin) -> Self {
+        match plugin {
+            OwnedPlugin::Attributes(attributes) => Self::Attributes(
+                attributes
+                    .attribute_list
+                    .iter()
+                    .map(|attribute| Attribute {
+                        key: attribute.key.as_bytes(),
+                        value: attribute.value.as_bytes(),
+                    })
+                    .collect(),
+            ),
+            OwnedPlugin::Autograph(autograph) => Self::Autograph(
+                autograph
+                    .signatures
+                    .iter()
+                    .map(|signature| AutographSignature {
+                        address: signature.address,
+                        message: signature.message.as_bytes(),
+                    })
+                    .collect(),
+            ),
+            _ => Self::None,
+        }
+    }
+}
+
+/// The storage each of `plugins` needs to be borrowed, in the same order
+fn plugin_storage<'a>(plugins: impl Iterator<Item = &'a OwnedPlugin>) -> Vec<PluginStorage<'a>> {
+    plugins.map(PluginStorage::new).collect()
+}
+
+/// Borrows the plugins for `f`, the lists of borrowing structs some of them need are collected first
+fn with_plugins<R>(
+    plugins: &[OwnedPluginAuthorityPair],
+    f: impl FnOnce(&[PluginAuthorityPairWithoutOption]) -> R,
+) -> Result<R, ProgramError> {
+    let storage = plugin_storage(plugins.iter().map(|pair| &pair.plugin));
+    let plugins = plugins
+        .iter()
+        .zip(&storage)
+        .map(|(pair, storage)| {
+            Ok(PluginAuthorityPairWithoutOption {
+                plugin: pair.plugin.as_plugin(storage)?,
+                authority: pair.authority,
+            })
+        })
+        .collect::<Result<Vec<_>, ProgramError>>()?;
+    Ok(f(&plugins))
+}
+
+/// Same as `with_plugins`, for the plugins of the create instructions
+fn with_plugin_pairs<R>(
+    plugins: Option<&[OwnedPluginAuthorityPairWithOption]>,
+    f: impl FnOnce(Option<&[PluginAuthorityPair]>) -> R,
+) -> Result<R, ProgramError> {
+    let Some(plugins) = plugins else {
+        return Ok(f(None));
+    };
+    let storage = plugin_storage(plugins.iter().map(|pair| &pair.plugin));
+    let plugins = plugins
+        .iter()
+        .zip(&storage)
+        .map(|(pair, storage)| {
+            Ok(PluginAuthorityPair {
+                plugin: pair.plugin.as_plugin(storage)?,
+                authority: pair.authority,
+            })
+        })
+        .collect::<Result<Vec<_>, ProgramError>>()?;
+    Ok(f(Some(&plugins)))
+}
+
+fn with_optional_proof<R>(
+    compression_proof: Option<&OwnedCompressionProof>,
+    f: impl FnOnce(Option<CompressionProof>) -> R,
+) -> Result<R, ProgramError> {
+    match compression_proof {
+        Some(compression_proof) => compression_proof.with_compression_proof(|proof| f(Some(proof))),
+        None => Ok(f(None)),
+    }
+}
+
+/// Borrows the external plugin adapters for `f`, the seeds and extra accounts some of them need are collected first
+fn with_external_plugins<R>(
+    external_plugins: &[OwnedExternalPluginAdapterWithData],
+    f: impl FnOnce(&[ExternalPluginAdapterWithData]) -> R,
+) -> R {
+    let seeds: Vec<Vec<_>> = external_plugins
+        .iter()
+        .map(|external| {
+            external
+                .adapter
+                .extra_accounts()
+                .iter()
+                .map(OwnedExtraAccount::seeds)
+                .collect()
+        })
+        .collect();
+    let extra_accounts: Vec<Vec<_>> = external_plugins
+        .iter()
+        .zip(&seeds)
+        .map(|(external, seeds)| {
+            external
+                .adapter
+                .extra_accounts()
+                .iter()
+                .zip(seeds)
+                .map(|(extra_account, seeds)| extra_account.as_extra_account(seeds))
+                .collect()
+        })
+        .collect();
+    let external_plugins: Vec<_> = external_plugins
+        .iter()
+        .zip(&extra_accounts)
+        .map(|(external, extra_accounts)| ExternalPluginAdapterWithData {
+            adapter: external.adapter.as_adapter(extra_accounts),
+            authority: external.authority,
+            lifecycle_checks: external.lifecycle_checks.as_deref(),
+            data: external.data.as_deref(),
+        })
+        .collect();
+    f(&external_plugins)
+}
+
+fn owned_plugins(plugins: RegistryIter) -> Result<Vec<OwnedPluginAuthorityPair>, ProgramError> {
+    let mut owned = Vec::new();
+    for record in plugins {
+        let record = record?;
+        // plugins newer than this crate are skipped, the views never match them either
+        if PluginType::try_from(record.plugin_type).is_err() {
+            continue;
+        }
+        owned.push(OwnedPluginAuthorityPair {
+            plugin: OwnedPlugin::from_record(&record)?,
+            authority: record.authority,
+        });
+    }
+    Ok(owned)
+}
+
+fn owned_external_plugins(
+    external_plugins: ExternalRegistryIter,
+) -> Result<Vec<OwnedExternalPluginAdapterWithData>, ProgramError> {
+    let mut owned = Vec::new();
+    for record in external_plugins {
+        let record = record?;
+        // same as owned_plugins for the adapters newer than this crate
+        if ExternalPluginAdapterType::try_from(record.plugin_type).is_err() {
+            continue;
+        }
+        owned.push(OwnedExternalPluginAdapterWithData::from_record(&record)?);
+    }
+    Ok(owned)
+}
+
+fn to_string(bytes: &[u8]) -> Result<String, ProgramError> {
+    core::str::from_utf8(bytes)
+        .map(String::from)
+        .map_err(|_| MplCoreError::InvalidUtf8.into())
+}
+
+fn to_bytes(data: &impl Serialize) -> Vec<u8> {
+    let mut bytes = vec![0; data.serialized_size()];
+    data.serialize_to(&mut bytes);
+    bytes
+}
@@ -0,0 +1,232 @@
+use core::mem::MaybeUninit;
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    data::plugins::{
+        Attribute, Attributes, Autograph, AutographSignature, Creator, Edition, FreezeDelegate,
+        FreezeExecute, MasterEdition, PermanentFreezeDelegate, PermanentFreezeExecute, Plugin,
+        PluginAuthority, PluginAuthorityPair, PluginType, Royalties, RuleSet, UpdateDelegate,
+        VerifiedCreators, VerifiedCreatorsSignature,
+    },
+    error::MplCoreError,
+};
+
+/// Builds the plugin list of CreateAssetV1 or CreateCollectionV1 into a buffer provided by the caller,
+/// so nothing is allocated:
+///
+/// ```
+/// # use core::mem::MaybeUninit;
+/// # use mpl_core_pinocchio::data::{
+/// #     plugin_builder::Plugins,
+/// #     plugins::{Creator, PluginAuthority, RuleSet},
+/// # };
+/// # let delegate = [1; 32];
+/// # let creators = [Creator { address: [2; 32], percentage: 100 }];
+/// let mut buffer = [const { MaybeUninit::uninit() }; 4];
+/// let plugins = Plugins::new(&mut buffer)
+///     .royalties(500, &creators, RuleSet::None)
+///     .freeze_delegate(false)
+///     .authority(PluginAuthority::Address(delegate))
+///     .immutable_metadata()
+///     .build()?;
+/// assert_eq!(plugins.len(), 3);
+/// # Ok::<(), pinocchio::program_error::ProgramError>(())
+/// ```
+///
+/// Every plugin gets its default authority (`PluginType::default_authority`) unless `authority` is called
+/// right after it. Errors are kept until `build`, which fails if the buffer is too small, if a plugin
+/// was added twice, if `authority` was called before any plugin, or if the authority can't manage
+/// the plugin (`None`, or the update authority on `FreezeDelegate`, `TransferDelegate`, `BurnDelegate`
+/// and the other plugins managed by the owner)
+pub struct Plugins<'b, 'a> {
+    buffer: &'b mut [MaybeUninit<PluginAuthorityPair<'a>>],
+    len: usize,
+    error: Option<MplCoreError>,
+}
+
+impl<'b, 'a> Plugins<'b, 'a> {
+    pub fn new(buffer: &'b mut [MaybeUninit<PluginAuthorityPair<'a>>]) -> Self {
+        Self {
+            buffer,
+            len: 0,
+            error: None,
+        }
+    }
+
+    /// The plugins added so far
+    fn added(&self) -> &[PluginAuthorityPair<'a>] {
+        // SAFETY: the first `len` elements have all been written by `plugin`
+        unsafe {
+            core::slice::from_raw_parts(
+                self.buffer.as_ptr() as *const PluginAuthorityPair<'a>,
+                self.len,
+            )
+        }
+    }
+
+    /// Adds any plugin with its default authority
+    pub fn plugin(mut self, plugin: Plugin<'a>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let plugin_number = plugin.get_plugin_number();
+        if self
+            .added()
+            .iter()
+            .any(|pair| pair.plugin.get_plugin_number() == plugin_number)
+        {
+            self.error = Some(MplCoreError::DuplicatePlugin);
+            return self;
+        }
+
+        let Some(slot) = self.buffer.get_mut(self.len) else {
+            self.error = Some(MplCoreError::BufferTooSmall);
+            return self;
+        };
+
+        let authority = PluginType::try_from(plugin_number)
+            .ok()
+            .map(|plugin_type| plugin_type.default_authority());
+        *slot = MaybeUninit::new(PluginAuthorityPair { plugin, authority });
+        self.len += 1;
+
+        self
+    }
+
+    /// Overrides the authority of the plugin added just before
+    pub fn authority(mut self, authority: PluginAuthority) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let Some(last) = self.len.checked_sub(1) else {
+            self.error = Some(MplCoreError::MissingPlugin);
+            return self;
+        };
+        // SAFETY: the element before `len` has been written by `plugin`
+        let pair = unsafe { self.buffer[last].assume_init_mut() };
+
+        // owner managed plugins are the ones the owner manages by default, they can be
+        // delegated to an address but never to the update authority
+        let owner_managed = PluginType::try_from(pair.plugin.get_plugin_number())
+            .is_ok_and(|plugin_type| plugin_type.default_authority() == PluginAuthority::Owner);
+        match authority {
+            PluginAuthority::None => self.error = Some(MplCoreError::InvalidAuthorityForPlugin),
+            PluginAuthority::UpdateAuthority if owner_managed => {
+                self.error = Some(MplCoreError::InvalidAuthorityForPlugin)
+            }
+            _ => pair.authority = Some(authority),
+        }
+
+        self
+    }
+
+    pub fn royalties(
+        self,
+        basis_points: u16,
+        creators: &'a [Creator],
+        rule_set: RuleSet<'a>,
+    ) -> Self {
+        self.plugin(Plugin::Royalties(Royalties {
+            basis_points,
+            creators,
+            rule_set,
+        }))
+    }
+
+    pub fn freeze_delegate(self, frozen: bool) -> Self {
+        self.plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen }))
+    }
+
+    pub fn burn_delegate(self) -> Self {
+        self.plugin(Plugin::BurnDelegate)
+    }
+
+    pub fn transfer_delegate(self) -> Self {
+        self.plugin(Plugin::TransferDelegate)
+    }
+
+    pub fn update_delegate(self, additional_delegates: &'a [Pubkey]) -> Self {
+        self.plugin(Plugin::UpdateDelegate(UpdateDelegate {
+            additional_delegates,
+        }))
+    }
+
+    pub fn permanent_freeze_delegate(self, frozen: bool) -> Self {
+        self.plugin(Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+            frozen,
+        }))
+    }
+
+    pub fn attributes(self, attribute_list: &'a [Attribute<'a>]) -> Self {
+        self.plugin(Plugin::Attributes(Attributes { attribute_list }))
+    }
+
+    pub fn permanent_transfer_delegate(self) -> Self {
+        self.plugin(Plugin::PermanentTransferDelegate)
+    }
+
+    pub fn permanent_burn_delegate(self) -> Self {
+        self.plugin(Plugin::PermanentBurnDelegate)
+    }
+
+    pub fn edition(self, number: u32) -> Self {
+        self.plugin(Plugin::Edition(Edition { number }))
+    }
+
+    pub fn master_edition(
+        self,
+        max_supply: Option<u32>,
+        name: Option<&'a [u8]>,
+        uri: Option<&'a [u8]>,
+    ) -> Self {
+        self.plugin(Plugin::MasterEdition(MasterEdition {
+            max_supply,
+            name,
+            uri,
+        }))
+    }
+
+    pub fn add_blocker(self) -> Self {
+        self.plugin(Plugin::AddBlocker)
+    }
+
+    pub fn immutable_metadata(self) -> Self {
+        self.plugin(Plugin::ImmutableMetadata)
+    }
+
+    pub fn verified_creators(self, signatures: &'a [VerifiedCreatorsSignature]) -> Self {
+        self.plugin(Plugin::VerifiedCreators(VerifiedCreators { signatures }))
+    }
+
+    pub fn autograph(self, signatures: &'a [AutographSignature<'a>]) -> Self {
+        self.plugin(Plugin::Autograph(Autograph { signatures }))
+    }
+
+    pub fn freeze_execute(self, frozen: bool) -> Self {
+        self.plugin(Plugin::FreezeExecute(FreezeExecute { frozen }))
+    }
+
+    pub fn permanent_freeze_execute(self, frozen: bool) -> Self {
+        self.plugin(Plugin::PermanentFreezeExecute(PermanentFreezeExecute {
+            frozen,
+        }))
+    }
+
+    /// The plugins, ready for the `plugins` of the create instruction data
+    pub fn build(self) -> Result<&'b [PluginAuthorityPair<'a>], ProgramError> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+
+        // SAFETY: the first `len` elements have all been written by `plugin`
+        Ok(unsafe {
+            core::slice::from_raw_parts(
+                self.buffer.as_ptr() as *const PluginAuthorityPair<'a>,
+                self.len,
+            )
+        })
+    }
+}
@@ -1,12 +1,18 @@
 use bytemuck::{try_cast_slice, Pod, Zeroable};
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
-use crate::data::{
-    asset::{BaseAssetV1, BaseCollectionV1, Key, PluginHeaderV1},
-    DeserializeSized, Serialize, Skip,
+use crate::{
+    data::{
+        asset::{BaseAssetV1, BaseCollectionV1, Key, PluginHeaderV1},
+        read_bytes, read_pubkey, read_slice, read_tail, read_u8,
+        view::{AssetView, CollectionView, PluginRecord},
+        DeserializeSized, Serialize, Skip,
+    },
+    error::MplCoreError,
+    BUBBLEGUM_ID,
 };
 
-#[derive(Pod, Zeroable, Clone, Copy)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub struct Creator {
     pub address: Pubkey,
@@ -19,6 +25,10 @@ impl Serialize for Creator {
         offset += self.percentage.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.address.serialized_size() + self.percentage.serialized_size()
+    }
 }
 
 pub enum RuleSet<'a> {
@@ -27,6 +37,39 @@ pub enum RuleSet<'a> {
     ProgramDenyList(&'a [Pubkey]),
 }
 
+impl<'a> RuleSet<'a> {
+    /// Borrows the rule set straight from the account data, pubkeys have no alignment requirements.
+    /// Returns it and how many bytes it took
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<(Self, usize), ProgramError> {
+        // the discriminant is checked first, an unknown rule set has no length to read
+        let list: fn(&'a [Pubkey]) -> Self = match read_u8(bytes, 0)? {
+            0 => return Ok((Self::None, 1)),
+            1 => Self::ProgramAllowList,
+            2 => Self::ProgramDenyList,
+            _ => return Err(MplCoreError::InvalidRuleSet.into()),
+        };
+
+        let len = u32::deserialize(read_slice(bytes, 1, 4)?)?;
+        let size = usize::try_from(len)
+            .map_err(|_| MplCoreError::NumericalOverflow)?
+            .checked_mul(size_of::<Pubkey>())
+            .ok_or(MplCoreError::NumericalOverflow)?;
+        let keys: &[Pubkey] = try_cast_slice(read_slice(bytes, 5, size)?)
+            .map_err(|_| MplCoreError::UnexpectedEndOfData)?;
+
+        Ok((list(keys), 5 + size))
+    }
+
+    /// Whether the creators allow `program_id` to handle the asset
+    pub fn is_program_allowed(&self, program_id: &Pubkey) -> bool {
+        match self {
+            Self::None => true,
+            Self::ProgramAllowList(keys) => keys.contains(program_id),
+            Self::ProgramDenyList(keys) => !keys.contains(program_id),
+        }
+    }
+}
+
 impl<'a> Serialize for RuleSet<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         match self {
@@ -44,6 +87,15 @@ impl<'a> Serialize for RuleSet<'a> {
             }
         }
     }
+
+    fn serialized_size(&self) -> usize {
+        match self {
+            Self::None => 1,
+            Self::ProgramAllowList(keys) | Self::ProgramDenyList(keys) => {
+                1 + keys.serialized_size()
+            }
+        }
+    }
 }
 
 pub struct Royalties<'a> {
@@ -52,6 +104,34 @@ pub struct Royalties<'a> {
     pub rule_set: RuleSet<'a>,
 }
 
+impl<'a> Royalties<'a> {
+    /// Reads a whole Royalties plugin, starting right after the `Plugin` discriminant
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let info = RoyaltiesInfo::from_bytes(bytes)?;
+        // basis points + creators len + creators
+        let rule_set_offset = 2 + 4 + size_of_val(info.creators);
+        let (rule_set, _) = RuleSet::from_bytes(read_tail(bytes, rule_set_offset)?)?;
+
+        Ok(Self {
+            basis_points: info.basis_points,
+            creators: info.creators,
+            rule_set,
+        })
+    }
+
+    /// Royalties of an asset, falling back to the ones of its collection if the asset has no Royalties plugin,
+    /// which is how mpl core resolves them. Unlike `RoyaltiesInfo::resolve` the rule set is read too,
+    /// use this to enforce it
+    pub fn resolve(
+        asset: &AssetView<'a>,
+        collection: Option<&CollectionView<'a>>,
+    ) -> Result<Option<Self>, ProgramError> {
+        find_royalties(asset, collection)?
+            .map(|record| Self::from_bytes(record.data))
+            .transpose()
+    }
+}
+
 impl<'a> Serialize for Royalties<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         let mut offset = self.basis_points.serialize_to(buffer);
@@ -59,36 +139,112 @@ impl<'a> Serialize for Royalties<'a> {
         offset += self.rule_set.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.basis_points.serialized_size()
+            + self.creators.serialized_size()
+            + self.rule_set.serialized_size()
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct FreezeDelegate {
     pub frozen: bool,
 }
 
+impl DeserializeSized for FreezeDelegate {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            frozen: bool::deserialize(bytes)?,
+        })
+    }
+}
+
 impl Serialize for FreezeDelegate {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.frozen.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.frozen.serialized_size()
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct PermanentFreezeDelegate {
     pub frozen: bool,
 }
 
+impl DeserializeSized for PermanentFreezeDelegate {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            frozen: bool::deserialize(bytes)?,
+        })
+    }
+}
+
 impl Serialize for PermanentFreezeDelegate {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.frozen.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.frozen.serialized_size()
+    }
 }
 
 pub struct UpdateDelegate<'a> {
     pub additional_delegates: &'a [Pubkey],
 }
 
+impl<'a> UpdateDelegate<'a> {
+    /// Borrows the additional delegates straight from the account data, pubkeys have no alignment requirements
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let len = u32::deserialize(read_slice(bytes, 0, 4)?)?;
+        let len = usize::try_from(len).map_err(|_| MplCoreError::NumericalOverflow)?;
+        let size = len
+            .checked_mul(size_of::<Pubkey>())
+            .ok_or(MplCoreError::NumericalOverflow)?;
+
+        Ok(Self {
+            additional_delegates: try_cast_slice(read_slice(bytes, 4, size)?)
+                .map_err(|_| MplCoreError::UnexpectedEndOfData)?,
+        })
+    }
+}
+
 impl<'a> Serialize for UpdateDelegate<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.additional_delegates.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.additional_delegates.serialized_size()
+    }
+}
+
+/// The groups the asset or collection belongs to, only the group program adds and removes them
+pub struct Groups<'a> {
+    pub groups: &'a [Pubkey],
+}
+
+impl<'a> Groups<'a> {
+    /// Borrows the group addresses straight from the account data, same layout as `UpdateDelegate`
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            groups: UpdateDelegate::from_bytes(bytes)?.additional_delegates,
+        })
+    }
+}
+
+impl<'a> Serialize for Groups<'a> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        self.groups.serialize_to(buffer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.groups.serialized_size()
+    }
 }
 
 pub struct Attribute<'a> {
@@ -102,6 +258,10 @@ impl<'a> Serialize for Attribute<'a> {
         offset += self.value.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.key.serialized_size() + self.value.serialized_size()
+    }
 }
 
 pub struct Attributes<'a> {
@@ -112,8 +272,240 @@ impl<'a> Serialize for Attributes<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.attribute_list.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.attribute_list.serialized_size()
+    }
+}
+
+/// Borrowed view over the attribute list of an Attributes plugin, read straight from the account data.
+/// The whole list is bounds checked once when created, so lookups can't fail afterwards
+#[derive(Clone, Copy)]
+pub struct AttributesView<'a> {
+    /// The attributes, without the list length
+    bytes: &'a [u8],
+    len: u32,
+}
+
+impl<'a> AttributesView<'a> {
+    /// Starts right after the `Plugin` discriminant
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let len = u32::deserialize(read_slice(bytes, 0, 4)?)?;
+
+        let mut offset = 4;
+        for _ in 0..len {
+            let (_, key_size) = read_bytes(bytes, offset)?;
+            offset += key_size;
+            let (_, value_size) = read_bytes(bytes, offset)?;
+            offset += value_size;
+        }
+
+        Ok(Self {
+            bytes: &bytes[4..offset],
+            len,
+        })
+    }
+
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many bytes the attribute list takes in the account, length included
+    pub fn serialized_size(&self) -> usize {
+        4 + self.bytes.len()
+    }
+
+    pub fn iter(&self) -> AttributesIter<'a> {
+        AttributesIter {
+            bytes: self.bytes,
+            remaining: self.len,
+        }
+    }
+
+    /// The value of the first attribute with this key
+    pub fn get(&self, key: &[u8]) -> Option<&'a [u8]> {
+        self.iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value)
+    }
+
+    /// Applies `ops` on top of these attributes, see `AttributesPatch`
+    pub fn patch<'p>(&self, ops: &'p [AttributeOp<'p>]) -> AttributesPatch<'a, 'p> {
+        AttributesPatch {
+            current: *self,
+            ops,
+        }
+    }
+
+    /// The value of the attribute parsed as a decimal u64, like "42"
+    pub fn get_u64(&self, key: &[u8]) -> Result<Option<u64>, ProgramError> {
+        self.get(key).map(parse_attribute).transpose()
+    }
+
+    /// The value of the attribute parsed as a decimal i64, like "-42"
+    pub fn get_i64(&self, key: &[u8]) -> Result<Option<i64>, ProgramError> {
+        self.get(key).map(parse_attribute).transpose()
+    }
+}
+
+fn parse_attribute<T: core::str::FromStr>(value: &[u8]) -> Result<T, ProgramError> {
+    core::str::from_utf8(value)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .ok_or(MplCoreError::InvalidAttributeValue.into())
+}
+
+pub struct AttributesIter<'a> {
+    bytes: &'a [u8],
+    remaining: u32,
 }
 
+impl<'a> Iterator for AttributesIter<'a> {
+    type Item = Attribute<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // already bounds checked by AttributesView::from_bytes
+        let (key, key_size) = read_bytes(self.bytes, 0).ok()?;
+        let (value, value_size) = read_bytes(self.bytes, key_size).ok()?;
+        self.bytes = &self.bytes[key_size + value_size..];
+
+        Some(Attribute { key, value })
+    }
+}
+
+/// A plugin computed from the current one in the account data, serialized as a whole `Plugin`.
+/// Used with `UpdateAssetPluginPatchV1InstructionData`
+pub trait PluginPatch: Serialize {}
+
+impl PluginPatch for AttributesPatch<'_, '_> {}
+
+/// A change to apply to an attribute list, see `AttributesView::patch`
+#[derive(Clone, Copy)]
+pub enum AttributeOp<'p> {
+    /// Replaces the value of the attribute, or appends it if the key is not there yet
+    Set { key: &'p [u8], value: &'p [u8] },
+    /// Removes the attribute, if it is there
+    Remove { key: &'p [u8] },
+}
+
+impl<'p> AttributeOp<'p> {
+    fn key(&self) -> &'p [u8] {
+        match self {
+            Self::Set { key, .. } | Self::Remove { key } => key,
+        }
+    }
+}
+
+/// The attributes of an asset with a list of set/remove operations applied on top.
+/// Serializes as a full `Plugin::Attributes`, reading the current attributes from the account data
+/// while writing, so the new list never has to be built in memory.
+///
+/// If several operations target the same key, the last one wins.
+///
+/// Nothing is sorted or buffered, so every current attribute looks its key up in the ops and every
+/// Set looks its key up in the ops and in the current list: a pass over the patch costs
+/// O(m · (n + m)) key comparisons for n current attributes and m ops, meant for a handful of ops
+#[derive(Clone, Copy)]
+pub struct AttributesPatch<'a, 'p> {
+    current: AttributesView<'a>,
+    ops: &'p [AttributeOp<'p>],
+}
+
+impl<'a, 'p> AttributesPatch<'a, 'p> {
+    /// The operation that decides the fate of this key, if any
+    fn op_for(&self, key: &[u8]) -> Option<(usize, &AttributeOp<'p>)> {
+        self.ops
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, op)| op.key() == key)
+    }
+
+    /// The attributes that are kept from the current list, with their new value
+    fn kept(&self) -> impl Iterator<Item = Attribute<'_>> {
+        self.current
+            .iter()
+            .filter_map(|attribute| match self.op_for(attribute.key) {
+                None => Some(attribute),
+                Some((_, AttributeOp::Set { value, .. })) => Some(Attribute {
+                    key: attribute.key,
+                    value,
+                }),
+                Some((_, AttributeOp::Remove { .. })) => None,
+            })
+    }
+
+    /// The Set operations for keys that are not in the current list
+    fn appended(&self) -> impl Iterator<Item = Attribute<'_>> {
+        self.ops
+            .iter()
+            .enumerate()
+            .filter_map(|(index, op)| match op {
+                AttributeOp::Set { key, value }
+                    if self.op_for(key).map(|(last, _)| last) == Some(index)
+                        && self.current.get(key).is_none() =>
+                {
+                    Some(Attribute { key, value })
+                }
+                _ => None,
+            })
+    }
+
+    /// How many attributes the patched list has
+    pub fn len(&self) -> Result<u32, ProgramError> {
+        u32::try_from(self.kept().count() + self.appended().count())
+            .map_err(|_| MplCoreError::NumericalOverflow.into())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kept().next().is_none() && self.appended().next().is_none()
+    }
+}
+
+impl Serialize for AttributesPatch<'_, '_> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = PluginType::Attributes as u8;
+        let mut len = 0u32;
+        let mut offset = 5;
+
+        for attribute in self.kept().chain(self.appended()) {
+            offset += attribute.serialize_to(&mut buffer[offset..]);
+            len = len.wrapping_add(1);
+        }
+
+        // counted while writing, try_serialize_to rejects more than u32::MAX attributes
+        buffer[1..5].copy_from_slice(&len.to_le_bytes());
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + 4
+            + self
+                .kept()
+                .chain(self.appended())
+                .map(|attribute| attribute.serialized_size())
+                .sum::<usize>()
+    }
+
+    fn try_serialize_to(&self, buffer: &mut [u8]) -> Result<usize, ProgramError> {
+        self.len()?;
+        if buffer.len() < self.serialized_size() {
+            return Err(MplCoreError::BufferTooSmall.into());
+        }
+        Ok(self.serialize_to(buffer))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Edition {
     pub number: u32,
 }
@@ -122,6 +514,45 @@ impl Serialize for Edition {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.number.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.number.serialized_size()
+    }
+}
+
+impl DeserializeSized for Edition {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            number: u32::deserialize(read_slice(bytes, 0, 4)?)?,
+        })
+    }
+}
+
+impl Edition {
+    /// The edition that comes after everything minted into `collection` so far, numbered from 1.
+    /// Fails if the collection has no MasterEdition, or if it would go over its max supply.
+    ///
+    /// mpl core counts every asset added to the collection in `num_minted`, editions or not,
+    /// and does not enforce `max_supply` itself
+    pub fn next(collection: &CollectionView) -> Result<Self, ProgramError> {
+        let master_edition = collection
+            .master_edition()?
+            .ok_or(MplCoreError::MissingMasterEdition)?;
+
+        let number = collection
+            .num_minted
+            .checked_add(1)
+            .ok_or(MplCoreError::NumericalOverflow)?;
+
+        if master_edition
+            .max_supply
+            .is_some_and(|max_supply| number > max_supply)
+        {
+            return Err(MplCoreError::MaxSupplyReached.into());
+        }
+
+        Ok(Self { number })
+    }
 }
 
 pub struct MasterEdition<'a> {
@@ -130,6 +561,43 @@ pub struct MasterEdition<'a> {
     pub uri: Option<&'a [u8]>,
 }
 
+impl<'a> MasterEdition<'a> {
+    /// Borrows the name and uri straight from the account data, starting right after the `Plugin` discriminant
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let mut offset = 0;
+
+        let max_supply = match read_u8(bytes, offset)? {
+            0 => None,
+            1 => Some(u32::deserialize(read_slice(bytes, offset + 1, 4)?)?),
+            _ => return Err(MplCoreError::InvalidOption.into()),
+        };
+        offset += max_supply.serialized_size();
+
+        let (name, len) = read_option_bytes(bytes, offset)?;
+        offset += len;
+
+        let (uri, _) = read_option_bytes(bytes, offset)?;
+
+        Ok(Self {
+            max_supply,
+            name,
+            uri,
+        })
+    }
+}
+
+/// Reads an `Option<String>`, returning it and how many bytes it took
+fn read_option_bytes(bytes: &[u8], offset: usize) -> Result<(Option<&[u8]>, usize), ProgramError> {
+    match read_u8(bytes, offset)? {
+        0 => Ok((None, 1)),
+        1 => {
+            let (value, len) = read_bytes(bytes, offset + 1)?;
+            Ok((Some(value), 1 + len))
+        }
+        _ => Err(MplCoreError::InvalidOption.into()),
+    }
+}
+
 impl<'a> Serialize for MasterEdition<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         let mut offset = self.max_supply.serialize_to(buffer);
@@ -137,8 +605,13 @@ impl<'a> Serialize for MasterEdition<'a> {
         offset += self.uri.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.max_supply.serialized_size() + self.name.serialized_size() + self.uri.serialized_size()
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct VerifiedCreatorsSignature {
     pub address: Pubkey,
     pub verified: bool,
@@ -150,6 +623,10 @@ impl Serialize for VerifiedCreatorsSignature {
         offset += self.verified.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.address.serialized_size() + self.verified.serialized_size()
+    }
 }
 
 pub struct VerifiedCreators<'a> {
@@ -160,8 +637,162 @@ impl<'a> Serialize for VerifiedCreators<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.signatures.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.signatures.serialized_size()
+    }
+}
+
+/// Borrowed view over the signatures of a VerifiedCreators plugin, read straight from the account data.
+/// The whole list is checked once when created, so iterating can't fail afterwards
+#[derive(Clone, Copy)]
+pub struct VerifiedCreatorsView<'a> {
+    /// The signatures, without the list length
+    bytes: &'a [u8],
+    len: u32,
+}
+
+/// address + verified
+const VERIFIED_CREATORS_SIGNATURE_LEN: usize = size_of::<Pubkey>() + 1;
+
+impl<'a> VerifiedCreatorsView<'a> {
+    /// Starts right after the `Plugin` discriminant
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let len = u32::deserialize(read_slice(bytes, 0, 4)?)?;
+        let size = usize::try_from(len)
+            .map_err(|_| MplCoreError::NumericalOverflow)?
+            .checked_mul(VERIFIED_CREATORS_SIGNATURE_LEN)
+            .ok_or(MplCoreError::NumericalOverflow)?;
+        let signatures = read_slice(bytes, 4, size)?;
+
+        for signature in signatures.chunks_exact(VERIFIED_CREATORS_SIGNATURE_LEN) {
+            bool::deserialize(&signature[size_of::<Pubkey>()..])?;
+        }
+
+        Ok(Self {
+            bytes: signatures,
+            len,
+        })
+    }
+
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = VerifiedCreatorsSignature> + 'a {
+        self.bytes
+            .chunks_exact(VERIFIED_CREATORS_SIGNATURE_LEN)
+            .map(|signature| {
+                let (address, verified) = signature.split_at(size_of::<Pubkey>());
+                VerifiedCreatorsSignature {
+                    // both already checked by from_bytes
+                    address: address.try_into().unwrap_or_default(),
+                    verified: verified[0] == 1,
+                }
+            })
+    }
+
+    pub fn get(&self, address: &Pubkey) -> Option<VerifiedCreatorsSignature> {
+        self.iter().find(|signature| &signature.address == address)
+    }
+
+    /// Adds a creator, verified or not. Fails if the creator is already in the list
+    pub fn add(
+        &self,
+        address: &Pubkey,
+        verified: bool,
+    ) -> Result<VerifiedCreatorsUpdate<'a>, ProgramError> {
+        if self.get(address).is_some() {
+            return Err(MplCoreError::DuplicateSignature.into());
+        }
+
+        Ok(VerifiedCreatorsUpdate {
+            current: *self,
+            signature: VerifiedCreatorsSignature {
+                address: *address,
+                verified,
+            },
+            append: true,
+        })
+    }
+
+    /// The creator signing to verify themselves: marks them verified, adding them if they are not listed yet.
+    /// Fails if they are already verified
+    pub fn verify(&self, address: &Pubkey) -> Result<VerifiedCreatorsUpdate<'a>, ProgramError> {
+        let append = match self.get(address) {
+            Some(signature) if signature.verified => {
+                return Err(MplCoreError::AlreadyVerified.into())
+            }
+            Some(_) => false,
+            None => true,
+        };
+
+        Ok(VerifiedCreatorsUpdate {
+            current: *self,
+            signature: VerifiedCreatorsSignature {
+                address: *address,
+                verified: true,
+            },
+            append,
+        })
+    }
+}
+
+impl VerifiedCreatorsSignature {
+    fn with_verified(self, verified: bool) -> Self {
+        Self { verified, ..self }
+    }
+}
+
+/// The VerifiedCreators plugin with one signature added or verified, see `VerifiedCreatorsView::add`
+/// and `VerifiedCreatorsView::verify`. The other signatures are copied from the account data while serializing
+pub struct VerifiedCreatorsUpdate<'a> {
+    current: VerifiedCreatorsView<'a>,
+    signature: VerifiedCreatorsSignature,
+    /// Whether the signature is new, or replaces the one with the same address
+    append: bool,
+}
+
+impl Serialize for VerifiedCreatorsUpdate<'_> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = PluginType::VerifiedCreators as u8;
+        let len = self.current.len + u32::from(self.append);
+        buffer[1..5].copy_from_slice(&len.to_le_bytes());
+        let mut offset = 5;
+
+        for signature in self.current.iter() {
+            let signature = if signature.address == self.signature.address {
+                signature.with_verified(self.signature.verified)
+            } else {
+                signature
+            };
+            offset += signature.serialize_to(&mut buffer[offset..]);
+        }
+
+        if self.append {
+            offset += self.signature.serialize_to(&mut buffer[offset..]);
+        }
+
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + 4
+            + self.current.bytes.len()
+            + if self.append {
+                VERIFIED_CREATORS_SIGNATURE_LEN
+            } else {
+                0
+            }
+    }
 }
 
+impl PluginPatch for VerifiedCreatorsUpdate<'_> {}
+
 pub struct AutographSignature<'a> {
     pub address: Pubkey,
     pub message: &'a [u8],
@@ -173,8 +804,15 @@ impl<'a> Serialize for AutographSignature<'a> {
         offset += self.message.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.address.serialized_size() + self.message.serialized_size()
+    }
 }
 
+/// The longest autograph message mpl core accepts
+pub const MAX_AUTOGRAPH_MESSAGE_LEN: usize = 200;
+
 pub struct Autograph<'a> {
     pub signatures: &'a [AutographSignature<'a>],
 }
@@ -183,8 +821,134 @@ impl<'a> Serialize for Autograph<'a> {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.signatures.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.signatures.serialized_size()
+    }
+}
+
+/// Borrowed view over the signatures of an Autograph plugin, read straight from the account data.
+/// The whole list is bounds checked once when created, so iterating can't fail afterwards
+#[derive(Clone, Copy)]
+pub struct AutographView<'a> {
+    /// The signatures, without the list length
+    bytes: &'a [u8],
+    len: u32,
+}
+
+impl<'a> AutographView<'a> {
+    /// Starts right after the `Plugin` discriminant
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let len = u32::deserialize(read_slice(bytes, 0, 4)?)?;
+
+        let mut offset = 4;
+        for _ in 0..len {
+            offset += size_of::<Pubkey>();
+            let (_, message_size) = read_bytes(bytes, offset)?;
+            offset += message_size;
+        }
+
+        Ok(Self {
+            bytes: &bytes[4..offset],
+            len,
+        })
+    }
+
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> AutographIter<'a> {
+        AutographIter {
+            bytes: self.bytes,
+            remaining: self.len,
+        }
+    }
+
+    pub fn get(&self, address: &Pubkey) -> Option<AutographSignature<'a>> {
+        self.iter().find(|signature| &signature.address == address)
+    }
+
+    /// A fan signing the asset. Fails if they already signed it, or if the message is longer than
+    /// `MAX_AUTOGRAPH_MESSAGE_LEN`
+    pub fn add(
+        &self,
+        address: &Pubkey,
+        message: &'a [u8],
+    ) -> Result<AutographUpdate<'a>, ProgramError> {
+        if message.len() > MAX_AUTOGRAPH_MESSAGE_LEN {
+            return Err(MplCoreError::AutographMessageTooLong.into());
+        }
+        if self.get(address).is_some() {
+            return Err(MplCoreError::DuplicateSignature.into());
+        }
+
+        Ok(AutographUpdate {
+            current: *self,
+            signature: AutographSignature {
+                address: *address,
+                message,
+            },
+        })
+    }
+}
+
+pub struct AutographIter<'a> {
+    bytes: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> Iterator for AutographIter<'a> {
+    type Item = AutographSignature<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // already bounds checked by AutographView::from_bytes
+        let address = *read_pubkey(self.bytes, 0).ok()?;
+        let (message, message_size) = read_bytes(self.bytes, size_of::<Pubkey>()).ok()?;
+        self.bytes = &self.bytes[size_of::<Pubkey>() + message_size..];
+
+        Some(AutographSignature { address, message })
+    }
+}
+
+/// The Autograph plugin with one signature appended, see `AutographView::add`.
+/// The other signatures are copied from the account data while serializing
+pub struct AutographUpdate<'a> {
+    current: AutographView<'a>,
+    signature: AutographSignature<'a>,
+}
+
+impl Serialize for AutographUpdate<'_> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = PluginType::Autograph as u8;
+        buffer[1..5].copy_from_slice(&(self.current.len + 1).to_le_bytes());
+        let mut offset = 5;
+
+        buffer[offset..offset + self.current.bytes.len()].copy_from_slice(self.current.bytes);
+        offset += self.current.bytes.len();
+
+        offset += self.signature.serialize_to(&mut buffer[offset..]);
+
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + 4 + self.current.bytes.len() + self.signature.serialized_size()
+    }
 }
 
+impl PluginPatch for AutographUpdate<'_> {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct FreezeExecute {
     pub frozen: bool,
 }
@@ -193,8 +957,13 @@ impl Serialize for FreezeExecute {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.frozen.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.frozen.serialized_size()
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct PermanentFreezeExecute {
     pub frozen: bool,
 }
@@ -203,6 +972,10 @@ impl Serialize for PermanentFreezeExecute {
     fn serialize_to(&self, buffer: &mut [u8]) -> usize {
         self.frozen.serialize_to(buffer)
     }
+
+    fn serialized_size(&self) -> usize {
+        self.frozen.serialized_size()
+    }
 }
 
 // FIX: some plugins just had an empty struct inside so I left them empty here
@@ -226,6 +999,78 @@ pub enum Plugin<'a> {
     BubblegumV2,
     FreezeExecute(FreezeExecute),
     PermanentFreezeExecute(PermanentFreezeExecute),
+    Groups(Groups<'a>),
+}
+
+/// The type of a plugin, as stored in the plugin registry.
+/// Follows mpl core 0.12.1, where Groups is the last one
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluginType {
+    Royalties,
+    FreezeDelegate,
+    BurnDelegate,
+    TransferDelegate,
+    UpdateDelegate,
+    PermanentFreezeDelegate,
+    Attributes,
+    PermanentTransferDelegate,
+    PermanentBurnDelegate,
+    Edition,
+    MasterEdition,
+    AddBlocker,
+    ImmutableMetadata,
+    VerifiedCreators,
+    Autograph,
+    BubblegumV2,
+    FreezeExecute,
+    PermanentFreezeExecute,
+    Groups,
+}
+
+impl PluginType {
+    /// The authority mpl core gives the plugin when it is added without one.
+    /// Owner managed plugins default to the owner, everything else to the update authority
+    pub fn default_authority(&self) -> PluginAuthority {
+        match self {
+            Self::FreezeDelegate
+            | Self::BurnDelegate
+            | Self::TransferDelegate
+            | Self::Autograph
+            | Self::FreezeExecute => PluginAuthority::Owner,
+            Self::BubblegumV2 => PluginAuthority::Address(BUBBLEGUM_ID),
+            _ => PluginAuthority::UpdateAuthority,
+        }
+    }
+}
+
+impl TryFrom<u8> for PluginType {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Royalties),
+            1 => Ok(Self::FreezeDelegate),
+            2 => Ok(Self::BurnDelegate),
+            3 => Ok(Self::TransferDelegate),
+            4 => Ok(Self::UpdateDelegate),
+            5 => Ok(Self::PermanentFreezeDelegate),
+            6 => Ok(Self::Attributes),
+            7 => Ok(Self::PermanentTransferDelegate),
+            8 => Ok(Self::PermanentBurnDelegate),
+            9 => Ok(Self::Edition),
+            10 => Ok(Self::MasterEdition),
+            11 => Ok(Self::AddBlocker),
+            12 => Ok(Self::ImmutableMetadata),
+            13 => Ok(Self::VerifiedCreators),
+            14 => Ok(Self::Autograph),
+            15 => Ok(Self::BubblegumV2),
+            16 => Ok(Self::FreezeExecute),
+            17 => Ok(Self::PermanentFreezeExecute),
+            18 => Ok(Self::Groups),
+            _ => Err(MplCoreError::UnknownPluginDiscriminant.into()),
+        }
+    }
 }
 
 impl Plugin<'_> {
@@ -249,6 +1094,7 @@ impl Plugin<'_> {
             Self::BubblegumV2 => 15,
             Self::FreezeExecute(_) => 16,
             Self::PermanentFreezeExecute(_) => 17,
+            Self::Groups(_) => 18,
         }
     }
 }
@@ -289,51 +1135,83 @@ impl<'a> Serialize for Plugin<'a> {
                 1
             }
             Self::PermanentBurnDelegate => {
-                buffer[0] = 7;
+                buffer[0] = 8;
                 1
             }
             Self::Edition(edition) => {
-                buffer[0] = 8;
+                buffer[0] = 9;
                 1 + edition.serialize_to(&mut buffer[1..])
             }
             Self::MasterEdition(master_edition) => {
-                buffer[0] = 9;
+                buffer[0] = 10;
                 1 + master_edition.serialize_to(&mut buffer[1..])
             }
             Self::AddBlocker => {
-                buffer[0] = 10;
+                buffer[0] = 11;
                 1
             }
             Self::ImmutableMetadata => {
-                buffer[0] = 11;
+                buffer[0] = 12;
                 1
             }
             Self::VerifiedCreators(verified_creators) => {
-                buffer[0] = 12;
-                1 + verified_creators.serialize_to(buffer)
+                buffer[0] = 13;
+                1 + verified_creators.serialize_to(&mut buffer[1..])
             }
             Self::Autograph(autograph) => {
-                buffer[0] = 13;
-                1 + autograph.serialize_to(buffer)
+                buffer[0] = 14;
+                1 + autograph.serialize_to(&mut buffer[1..])
             }
             Self::BubblegumV2 => {
-                buffer[0] = 14;
+                buffer[0] = 15;
                 1
             }
             Self::FreezeExecute(freeze_execute) => {
-                buffer[0] = 15;
-                1 + freeze_execute.serialize_to(buffer)
+                buffer[0] = 16;
+                1 + freeze_execute.serialize_to(&mut buffer[1..])
             }
             Self::PermanentFreezeExecute(permanent_freeze_execute) => {
-                buffer[0] = 16;
-                1 + permanent_freeze_execute.serialize_to(buffer)
+                buffer[0] = 17;
+                1 + permanent_freeze_execute.serialize_to(&mut buffer[1..])
+            }
+            Self::Groups(groups) => {
+                buffer[0] = 18;
+                1 + groups.serialize_to(&mut buffer[1..])
+            }
+        }
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + match self {
+            Self::Royalties(royalties) => royalties.serialized_size(),
+            Self::FreezeDelegate(freeze_delegate) => freeze_delegate.serialized_size(),
+            Self::UpdateDelegate(update_delegate) => update_delegate.serialized_size(),
+            Self::PermanentFreezeDelegate(permanent_freeze_delegate) => {
+                permanent_freeze_delegate.serialized_size()
+            }
+            Self::Attributes(attributes) => attributes.serialized_size(),
+            Self::Edition(edition) => edition.serialized_size(),
+            Self::MasterEdition(master_edition) => master_edition.serialized_size(),
+            Self::VerifiedCreators(verified_creators) => verified_creators.serialized_size(),
+            Self::Autograph(autograph) => autograph.serialized_size(),
+            Self::FreezeExecute(freeze_execute) => freeze_execute.serialized_size(),
+            Self::PermanentFreezeExecute(permanent_freeze_execute) => {
+                permanent_freeze_execute.serialized_size()
             }
+            Self::Groups(groups) => groups.serialized_size(),
+            Self::BurnDelegate
+            | Self::TransferDelegate
+            | Self::PermanentTransferDelegate
+            | Self::PermanentBurnDelegate
+            | Self::AddBlocker
+            | Self::ImmutableMetadata
+            | Self::BubblegumV2 => 0,
         }
     }
 }
 
 #[repr(u8)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PluginAuthority {
     None,
     Owner,
@@ -343,13 +1221,24 @@ pub enum PluginAuthority {
 
 impl Skip for PluginAuthority {
     fn skip_bytes(bytes: &[u8]) -> Result<usize, ProgramError> {
-        let disc = bytes[0];
-        match disc {
+        match read_u8(bytes, 0)? {
             0 => Ok(1),
             1 => Ok(1),
             2 => Ok(1),
             3 => Ok(1 + size_of::<Pubkey>()),
-            _ => Err(ProgramError::InvalidAccountData),
+            _ => Err(MplCoreError::InvalidPluginAuthority.into()),
+        }
+    }
+}
+
+impl DeserializeSized for PluginAuthority {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
+        match read_u8(bytes, 0)? {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Owner),
+            2 => Ok(Self::UpdateAuthority),
+            3 => Ok(Self::Address(*read_pubkey(bytes, 1)?)),
+            _ => Err(MplCoreError::InvalidPluginAuthority.into()),
         }
     }
 }
@@ -375,6 +1264,13 @@ impl Serialize for PluginAuthority {
             }
         }
     }
+
+    fn serialized_size(&self) -> usize {
+        match self {
+            Self::Address(key) => 1 + key.serialized_size(),
+            _ => 1,
+        }
+    }
 }
 
 pub struct PluginAuthorityPair<'a> {
@@ -388,8 +1284,13 @@ impl<'a> Serialize for PluginAuthorityPair<'a> {
         offset += self.authority.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.plugin.serialized_size() + self.authority.serialized_size()
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum UpdateAuthority {
     None,
     Address(Pubkey),
@@ -404,25 +1305,43 @@ impl Serialize for UpdateAuthority {
                 1
             }
             Self::Address(address) => {
-                buffer[1] = 0;
+                buffer[0] = 1;
                 1 + address.serialize_to(&mut buffer[1..])
             }
             Self::Collection(collection) => {
-                buffer[2] = 0;
+                buffer[0] = 2;
                 1 + collection.serialize_to(&mut buffer[1..])
             }
         }
     }
+
+    fn serialized_size(&self) -> usize {
+        match self {
+            Self::None => 1,
+            Self::Address(address) => 1 + address.serialized_size(),
+            Self::Collection(collection) => 1 + collection.serialized_size(),
+        }
+    }
+}
+
+impl DeserializeSized for UpdateAuthority {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ProgramError> {
+        match read_u8(bytes, 0)? {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Address(*read_pubkey(bytes, 1)?)),
+            2 => Ok(Self::Collection(*read_pubkey(bytes, 1)?)),
+            _ => Err(MplCoreError::InvalidUpdateAuthority.into()),
+        }
+    }
 }
 
 impl Skip for UpdateAuthority {
     fn skip_bytes(bytes: &[u8]) -> Result<usize, ProgramError> {
-        let disc = bytes[0];
-        match disc {
+        match read_u8(bytes, 0)? {
             0 => Ok(1),
             1 => Ok(1 + size_of::<Pubkey>()),
             2 => Ok(1 + size_of::<Pubkey>()),
-            _ => Err(ProgramError::InvalidAccountData),
+            _ => Err(MplCoreError::InvalidUpdateAuthority.into()),
         }
     }
 }
@@ -440,6 +1359,12 @@ impl<'a> Serialize for HashablePluginSchema<'a> {
         offset += self.plugin.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.index.serialized_size()
+            + self.authority.serialized_size()
+            + self.plugin.serialized_size()
+    }
 }
 
 pub struct CompressionProof<'a> {
@@ -461,6 +1386,15 @@ impl<'a> Serialize for CompressionProof<'a> {
         offset += self.plugins.serialize_to(&mut buffer[offset..]);
         offset
     }
+
+    fn serialized_size(&self) -> usize {
+        self.owner.serialized_size()
+            + self.update_authority.serialized_size()
+            + self.name.serialized_size()
+            + self.uri.serialized_size()
+            + self.seq.serialized_size()
+            + self.plugins.serialized_size()
+    }
 }
 
 pub struct RoyaltiesInfo<'a> {
@@ -468,11 +1402,133 @@ pub struct RoyaltiesInfo<'a> {
     pub creators: &'a [Creator],
 }
 
-impl<'a> RoyaltiesInfo<'_> {
+impl<'a> RoyaltiesInfo<'a> {
     pub fn is_empty(&self) -> bool {
         // TODO: check the creators len instead??
         // either way if basis points are 0 then royalties do nothing
-        return self.basis_points == 0;
+        self.basis_points == 0
+    }
+
+    /// Reads the basis points and creators of a Royalties plugin, starting right after the `Plugin` discriminant.
+    /// The rule set is not read
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let basis_points = u16::deserialize(read_slice(bytes, 0, 2)?)?;
+
+        let num_creators = u32::deserialize(read_slice(bytes, 2, 4)?)?;
+        let creators_size = usize::try_from(num_creators)
+            .map_err(|_| MplCoreError::NumericalOverflow)?
+            .checked_mul(size_of::<Creator>())
+            .ok_or(MplCoreError::NumericalOverflow)?;
+
+        // creators are a pubkey followed by a u8, so they can be borrowed as-is
+        let creators: &[Creator] = try_cast_slice(read_slice(bytes, 6, creators_size)?)
+            .map_err(|_| MplCoreError::UnexpectedEndOfData)?;
+
+        Ok(Self {
+            basis_points,
+            creators,
+        })
+    }
+
+    /// Royalties of an asset, falling back to the ones of its collection if the asset has no Royalties plugin,
+    /// which is how mpl core resolves them. An asset with a Royalties plugin of 0 basis points still
+    /// overrides the collection. The rule set is not read, see `Royalties::resolve`
+    pub fn resolve(
+        asset: &AssetView<'a>,
+        collection: Option<&CollectionView<'a>>,
+    ) -> Result<Self, ProgramError> {
+        match find_royalties(asset, collection)? {
+            Some(record) => Self::from_bytes(record.data),
+            None => Ok(Self {
+                basis_points: 0,
+                creators: &[],
+            }),
+        }
+    }
+
+    /// Splits the royalties owed on a sale of `sale_price` between the creators.
+    ///
+    /// The total owed is rounded down. Each creator is paid the difference between the rounded down running totals
+    /// of the shares up to and including them, so the payouts always add up to exactly the total owed,
+    /// and no creator is off by more than one from their exact share.
+    ///
+    /// Fails if the basis points are above 10000, or if the creators' percentages don't add up to 100
+    /// (no creators is fine only if there are no basis points either)
+    pub fn compute_payouts(&self, sale_price: u64) -> Result<RoyaltyPayouts<'a>, ProgramError> {
+        if self.basis_points > 10_000 {
+            return Err(MplCoreError::InvalidBasisPoints.into());
+        }
+
+        let total_percentage = self
+            .creators
+            .iter()
+            .try_fold(0u8, |total, creator| total.checked_add(creator.percentage))
+            .ok_or(MplCoreError::InvalidCreatorShares)?;
+
+        if (self.creators.is_empty() && self.basis_points != 0)
+            || (!self.creators.is_empty() && total_percentage != 100)
+        {
+            return Err(MplCoreError::InvalidCreatorShares.into());
+        }
+
+        // basis points are at most 10000, so this always fits in a u64
+        let total = u64::try_from(u128::from(sale_price) * u128::from(self.basis_points) / 10_000)
+            .map_err(|_| MplCoreError::NumericalOverflow)?;
+
+        Ok(RoyaltyPayouts {
+            creators: self.creators.iter(),
+            total,
+            cumulative_percentage: 0,
+            paid: 0,
+        })
+    }
+}
+
+fn find_royalties<'a>(
+    asset: &AssetView<'a>,
+    collection: Option<&CollectionView<'a>>,
+) -> Result<Option<PluginRecord<'a>>, ProgramError> {
+    match asset.find_plugin(PluginType::Royalties)? {
+        Some(record) => Ok(Some(record)),
+        None => match collection {
+            Some(collection) => collection.find_plugin(PluginType::Royalties),
+            None => Ok(None),
+        },
+    }
+}
+
+/// Iterator over `(creator, amount)` returned by `RoyaltiesInfo::compute_payouts`
+pub struct RoyaltyPayouts<'a> {
+    creators: core::slice::Iter<'a, Creator>,
+    total: u64,
+    cumulative_percentage: u64,
+    paid: u64,
+}
+
+impl RoyaltyPayouts<'_> {
+    /// The total royalties owed, which is what all payouts add up to
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl Iterator for RoyaltyPayouts<'_> {
+    type Item = (Pubkey, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let creator = self.creators.next()?;
+        self.cumulative_percentage += u64::from(creator.percentage);
+
+        // percentages were checked to add up to 100, so this is never above total
+        let due = (u128::from(self.total) * u128::from(self.cumulative_percentage) / 100) as u64;
+        let payout = due - self.paid;
+        self.paid = due;
+
+        Some((creator.address, payout))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.creators.size_hint()
     }
 }
 
@@ -485,7 +1541,7 @@ pub fn read_royalties_asset<'a>(bytes: &'a [u8]) -> Result<RoyaltiesInfo<'a>, Pr
     // skip the header
     let offset = match key {
         Key::AssetV1 => BaseAssetV1::skip_bytes(bytes),
-        _ => return Err(ProgramError::InvalidAccountData),
+        _ => return Err(MplCoreError::InvalidKey.into()),
     }?;
 
     // there might not be any plugins. if this happens, there will also not be any registry
@@ -508,7 +1564,7 @@ pub fn read_royalties_collection<'a>(bytes: &'a [u8]) -> Result<RoyaltiesInfo<'a
     // skip the header
     let offset = match key {
         Key::CollectionV1 => BaseCollectionV1::skip_bytes(bytes),
-        _ => return Err(ProgramError::InvalidAccountData),
+        _ => return Err(MplCoreError::InvalidKey.into()),
     }?;
 
     // there might not be any plugins. if this happens, there will also not be any registry
@@ -527,70 +1583,55 @@ pub fn read_royalties<'a>(
     offset: usize,
 ) -> Result<RoyaltiesInfo<'a>, ProgramError> {
     // read the PluginHeaderV1
-    let plugin_header = PluginHeaderV1::deserialize(&bytes[offset..])?;
+    let plugin_header = PluginHeaderV1::deserialize(read_tail(bytes, offset)?)?;
     let mut offset = usize::try_from(plugin_header.plugin_registry_offset)
-        .map_err(|_| ProgramError::ArithmeticOverflow)?;
+        .map_err(|_| MplCoreError::NumericalOverflow)?;
+
+    if offset >= bytes.len() {
+        return Err(MplCoreError::RegistryOffsetOutOfRange.into());
+    }
 
     // read the PluginRegistryV1Safe
 
-    let key = Key::deserialize_from(&bytes[offset..])?;
+    let key = Key::deserialize_from(read_tail(bytes, offset)?)?;
     offset += 1;
 
     if !matches!(key, Key::PluginRegistryV1) {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(MplCoreError::InvalidKey.into());
     }
 
-    let registry_len = u32::deserialize(&bytes[offset..])?;
+    let registry_len = u32::deserialize(read_slice(bytes, offset, 4)?)?;
     offset += size_of::<u32>();
 
     for _ in 0..registry_len {
         // read the RegistryRecordSafe
 
-        let plugin_type = bytes[offset];
+        let plugin_type = read_u8(bytes, offset)?;
         offset += 1;
 
         // skip authority
-        offset += PluginAuthority::skip_bytes(&bytes[offset..])?;
+        offset += PluginAuthority::skip_bytes(read_tail(bytes, offset)?)?;
 
         // read offset
-        let plugin_offset = u64::deserialize(&bytes[offset..])?;
+        let plugin_offset = u64::deserialize(read_slice(bytes, offset, 8)?)?;
         offset += size_of::<u64>();
 
         // check that it is a royalties plugin (type == 0)
         if plugin_type == 0 {
-            offset =
-                usize::try_from(plugin_offset).map_err(|_| ProgramError::ArithmeticOverflow)?;
+            offset = usize::try_from(plugin_offset).map_err(|_| MplCoreError::NumericalOverflow)?;
 
             // deserialize Plugin discriminant and check it again
-            let plugin_disc = bytes[offset];
+            let plugin_disc =
+                read_u8(bytes, offset).map_err(|_| MplCoreError::PluginOffsetOutOfRange)?;
             offset += 1;
 
             if plugin_disc == 0 {
-                // deserialize the Royalties
-                let basis_points = u16::deserialize(&bytes[offset..])?;
-                offset += size_of::<u16>();
-
-                let num_creators = u32::deserialize(&bytes[offset..])?;
-                offset += size_of::<u32>();
-
-                let creators_start = offset;
-                let creators_end = creators_start
-                    + (size_of::<Creator>()
-                        * usize::try_from(num_creators)
-                            .map_err(|_| ProgramError::ArithmeticOverflow)?);
-
                 // creators are a pubkey followed by a u8
                 // this is a miracle
                 // it means there are no aligment issues and I can just return it as-is
-                let creators: &[Creator] = try_cast_slice(&bytes[creators_start..creators_end])
-                    .map_err(|_| ProgramError::InvalidAccountData)?;
-
-                return Ok(RoyaltiesInfo {
-                    basis_points,
-                    creators,
-                });
+                return RoyaltiesInfo::from_bytes(read_tail(bytes, offset)?);
             } else {
-                return Err(ProgramError::InvalidAccountData);
+                return Err(MplCoreError::PluginTypeMismatch.into());
             }
         }
     }
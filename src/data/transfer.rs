@@ -14,6 +14,10 @@ impl<'a> Serialize for TransferV1InstructionData<'a> {
 
         offset += self.compression_proof.serialize_to(&mut buffer[offset..]);
 
-        return offset;
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + self.compression_proof.serialized_size()
     }
 }
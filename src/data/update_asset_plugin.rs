@@ -1,5 +1,11 @@
+use pinocchio::program_error::ProgramError;
+
 use crate::{
-    data::{plugins::Plugin, Serialize},
+    data::{
+        plugins::{Plugin, PluginPatch},
+        Serialize,
+    },
+    error::MplCoreError,
     Instructions,
 };
 
@@ -14,6 +20,41 @@ impl<'a> Serialize for UpdateAssetPluginV1InstructionData<'a> {
 
         offset += self.plugin.serialize_to(&mut buffer[offset..]);
 
-        return offset;
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + self.plugin.serialized_size()
+    }
+}
+
+/// Same as `UpdateAssetPluginV1InstructionData`, but the new plugin is streamed from a patch
+/// of the current one (`AttributesPatch`, `VerifiedCreatorsUpdate`, `AutographUpdate`)
+/// instead of being built beforehand.
+/// The patch borrows the asset data, serialize it before invoking, see `UpdateAssetPluginV1::invoke_serialized`
+pub struct UpdateAssetPluginPatchV1InstructionData<P: PluginPatch> {
+    pub patch: P,
+}
+
+impl<P: PluginPatch> Serialize for UpdateAssetPluginPatchV1InstructionData<P> {
+    fn serialize_to(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = Instructions::UpdateAssetPlugin.to_u8();
+        let mut offset = 1;
+
+        offset += self.patch.serialize_to(&mut buffer[offset..]);
+
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + self.patch.serialized_size()
+    }
+
+    fn try_serialize_to(&self, buffer: &mut [u8]) -> Result<usize, ProgramError> {
+        let [discriminant, patch @ ..] = buffer else {
+            return Err(MplCoreError::BufferTooSmall.into());
+        };
+        *discriminant = Instructions::UpdateAssetPlugin.to_u8();
+        Ok(1 + self.patch.try_serialize_to(patch)?)
     }
 }
@@ -14,6 +14,10 @@ impl<'a> Serialize for UpdateCollectionPluginV1InstructionData<'a> {
 
         offset += self.plugin.serialize_to(&mut buffer[offset..]);
 
-        return offset;
+        offset
+    }
+
+    fn serialized_size(&self) -> usize {
+        1 + self.plugin.serialized_size()
     }
 }
@@ -0,0 +1,502 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    data::{
+        asset::{ExternalCheckResult, Key, PluginHeaderV1},
+        external_plugins::HookableLifecycleEvent,
+        plugins::{
+            AttributesView, AutographView, Edition, MasterEdition, PluginAuthority, PluginType,
+            UpdateAuthority, VerifiedCreatorsView,
+        },
+        read_bytes, read_pubkey, read_slice, read_tail, read_u8, DeserializeSized, Serialize,
+    },
+    error::MplCoreError,
+};
+
+/// Borrowed view over the bytes of an AssetV1 account.
+/// Only the base asset is decoded up front, plugins are read from the registry when asked for,
+/// so nothing is allocated and nothing is copied except the update authority
+pub struct AssetView<'a> {
+    pub owner: &'a Pubkey,
+    pub update_authority: UpdateAuthority,
+    pub name: &'a [u8],
+    pub uri: &'a [u8],
+    pub seq: Option<u64>,
+    /// None if the asset has no plugins at all
+    pub registry: Option<RegistryView<'a>>,
+}
+
+impl<'a> AssetView<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        if !matches!(
+            Key::deserialize_from(read_slice(bytes, 0, 1)?)?,
+            Key::AssetV1
+        ) {
+            return Err(MplCoreError::InvalidKey.into());
+        }
+        let mut offset = 1;
+
+        let owner = read_pubkey(bytes, offset)?;
+        offset += size_of::<Pubkey>();
+
+        let update_authority = UpdateAuthority::deserialize(read_tail(bytes, offset)?)?;
+        offset += update_authority.serialized_size();
+
+        let (name, len) = read_bytes(bytes, offset)?;
+        offset += len;
+
+        let (uri, len) = read_bytes(bytes, offset)?;
+        offset += len;
+
+        let seq = match read_u8(bytes, offset)? {
+            0 => None,
+            1 => Some(u64::from_le_bytes(
+                read_slice(bytes, offset + 1, 8)?
+                    .try_into()
+                    .map_err(|_| MplCoreError::UnexpectedEndOfData)?,
+            )),
+            _ => return Err(MplCoreError::InvalidOption.into()),
+        };
+        offset += seq.serialized_size();
+
+        Ok(Self {
+            owner,
+            update_authority,
+            name,
+            uri,
+            seq,
+            registry: RegistryView::after_base(bytes, offset)?,
+        })
+    }
+
+    /// Iterates over all the plugins of the asset
+    pub fn plugins(&self) -> RegistryIter<'a> {
+        RegistryIter::new(self.registry.as_ref())
+    }
+
+    /// Iterates over all the external plugin adapters of the asset. Their registry comes after
+    /// the plugin records, which are walked over first
+    pub fn external_plugins(&self) -> Result<ExternalRegistryIter<'a>, ProgramError> {
+        ExternalRegistryIter::new(self.registry.as_ref())
+    }
+
+    /// Finds the plugin of the given type, if the asset has it
+    pub fn find_plugin(
+        &self,
+        plugin_type: PluginType,
+    ) -> Result<Option<PluginRecord<'a>>, ProgramError> {
+        find_plugin(self.plugins(), plugin_type)
+    }
+
+    /// The edition number of the asset, if it has the Edition plugin
+    pub fn edition(&self) -> Result<Option<Edition>, ProgramError> {
+        self.find_plugin(PluginType::Edition)?
+            .map(|record| Edition::deserialize(record.data))
+            .transpose()
+    }
+
+    /// The attributes of the asset, if it has the Attributes plugin
+    pub fn attributes(&self) -> Result<Option<AttributesView<'a>>, ProgramError> {
+        self.find_plugin(PluginType::Attributes)?
+            .map(|record| AttributesView::from_bytes(record.data))
+            .transpose()
+    }
+
+    /// The creators of the asset, if it has the VerifiedCreators plugin
+    pub fn verified_creators(&self) -> Result<Option<VerifiedCreatorsView<'a>>, ProgramError> {
+        self.find_plugin(PluginType::VerifiedCreators)?
+            .map(|record| VerifiedCreatorsView::from_bytes(record.data))
+            .transpose()
+    }
+
+    /// The autographs of the asset, if it has the Autograph plugin
+    pub fn autograph(&self) -> Result<Option<AutographView<'a>>, ProgramError> {
+        self.find_plugin(PluginType::Autograph)?
+            .map(|record| AutographView::from_bytes(record.data))
+            .transpose()
+    }
+}
+
+/// Borrowed view over the bytes of a CollectionV1 account. Works the same way as `AssetView`
+pub struct CollectionView<'a> {
+    pub update_authority: &'a Pubkey,
+    pub name: &'a [u8],
+    pub uri: &'a [u8],
+    pub num_minted: u32,
+    pub current_size: u32,
+    /// None if the collection has no plugins at all
+    pub registry: Option<RegistryView<'a>>,
+}
+
+impl<'a> CollectionView<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        if !matches!(
+            Key::deserialize_from(read_slice(bytes, 0, 1)?)?,
+            Key::CollectionV1
+        ) {
+            return Err(MplCoreError::InvalidKey.into());
+        }
+        let mut offset = 1;
+
+        let update_authority = read_pubkey(bytes, offset)?;
+        offset += size_of::<Pubkey>();
+
+        let (name, len) = read_bytes(bytes, offset)?;
+        offset += len;
+
+        let (uri, len) = read_bytes(bytes, offset)?;
+        offset += len;
+
+        let num_minted = u32::deserialize(read_slice(bytes, offset, 4)?)?;
+        offset += size_of::<u32>();
+
+        let current_size = u32::deserialize(read_slice(bytes, offset, 4)?)?;
+        offset += size_of::<u32>();
+
+        Ok(Self {
+            update_authority,
+            name,
+            uri,
+            num_minted,
+            current_size,
+            registry: RegistryView::after_base(bytes, offset)?,
+        })
+    }
+
+    /// Iterates over all the plugins of the collection
+    pub fn plugins(&self) -> RegistryIter<'a> {
+        RegistryIter::new(self.registry.as_ref())
+    }
+
+    /// Iterates over all the external plugin adapters of the collection, see `AssetView::external_plugins`
+    pub fn external_plugins(&self) -> Result<ExternalRegistryIter<'a>, ProgramError> {
+        ExternalRegistryIter::new(self.registry.as_ref())
+    }
+
+    /// Finds the plugin of the given type, if the collection has it
+    pub fn find_plugin(
+        &self,
+        plugin_type: PluginType,
+    ) -> Result<Option<PluginRecord<'a>>, ProgramError> {
+        find_plugin(self.plugins(), plugin_type)
+    }
+
+    /// The MasterEdition of the collection, if it has the plugin
+    pub fn master_edition(&self) -> Result<Option<MasterEdition<'a>>, ProgramError> {
+        self.find_plugin(PluginType::MasterEdition)?
+            .map(|record| MasterEdition::from_bytes(record.data))
+            .transpose()
+    }
+}
+
+/// The PluginRegistryV1 of an asset or collection, still in its serialized form
+#[derive(Clone, Copy)]
+pub struct RegistryView<'a> {
+    /// The whole account, since plugin offsets are relative to its start
+    bytes: &'a [u8],
+    /// Where the first RegistryRecord starts
+    records_offset: usize,
+    len: u32,
+}
+
+impl<'a> RegistryView<'a> {
+    /// Reads the PluginHeaderV1 right after the base asset or collection, if there is one,
+    /// and follows it to the registry
+    fn after_base(bytes: &'a [u8], base_len: usize) -> Result<Option<Self>, ProgramError> {
+        // there might not be any plugins. if this happens, there will also not be any registry
+        if base_len == bytes.len() {
+            return Ok(None);
+        }
+
+        let header = PluginHeaderV1::deserialize(read_slice(bytes, base_len, 9)?)?;
+        let registry_offset = usize::try_from(header.plugin_registry_offset)
+            .map_err(|_| MplCoreError::NumericalOverflow)?;
+
+        let key =
+            read_u8(bytes, registry_offset).map_err(|_| MplCoreError::RegistryOffsetOutOfRange)?;
+        if key != Key::PluginRegistryV1 as u8 {
+            return Err(MplCoreError::InvalidKey.into());
+        }
+
+        let len = u32::deserialize(read_slice(bytes, registry_offset + 1, 4)?)?;
+
+        Ok(Some(Self {
+            bytes,
+            records_offset: registry_offset + 1 + 4,
+            len,
+        }))
+    }
+
+    /// How many plugins are in the registry
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A plugin found through the registry
+#[derive(Clone, Copy)]
+pub struct PluginRecord<'a> {
+    /// Kept as the raw registry value, so that plugins newer than this crate can still be skipped over
+    pub plugin_type: u8,
+    pub authority: PluginAuthority,
+    /// The plugin's data, right after its `Plugin` discriminant, until the end of the account
+    pub data: &'a [u8],
+}
+
+pub struct RegistryIter<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    remaining: u32,
+}
+
+impl<'a> RegistryIter<'a> {
+    fn new(registry: Option<&RegistryView<'a>>) -> Self {
+        match registry {
+            Some(registry) => Self {
+                bytes: registry.bytes,
+                offset: registry.records_offset,
+                remaining: registry.len,
+            },
+            None => Self {
+                bytes: &[],
+                offset: 0,
+                remaining: 0,
+            },
+        }
+    }
+
+    fn read_record(&mut self) -> Result<PluginRecord<'a>, ProgramError> {
+        let plugin_type = read_u8(self.bytes, self.offset)?;
+        self.offset += 1;
+
+        let authority = PluginAuthority::deserialize(read_tail(self.bytes, self.offset)?)?;
+        self.offset += authority.serialized_size();
+
+        let plugin_offset = u64::deserialize(read_slice(self.bytes, self.offset, 8)?)?;
+        self.offset += size_of::<u64>();
+
+        let plugin_offset =
+            usize::try_from(plugin_offset).map_err(|_| MplCoreError::NumericalOverflow)?;
+
+        // check the plugin discriminant matches what the registry says
+        let plugin_disc =
+            read_u8(self.bytes, plugin_offset).map_err(|_| MplCoreError::PluginOffsetOutOfRange)?;
+        if plugin_disc != plugin_type {
+            return Err(MplCoreError::PluginTypeMismatch.into());
+        }
+
+        Ok(PluginRecord {
+            plugin_type,
+            authority,
+            data: &self.bytes[plugin_offset + 1..],
+        })
+    }
+}
+
+impl<'a> Iterator for RegistryIter<'a> {
+    type Item = Result<PluginRecord<'a>, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let record = self.read_record();
+        if record.is_err() {
+            // the rest of the registry can't be trusted
+            self.remaining = 0;
+        }
+        Some(record)
+    }
+}
+
+/// An external plugin adapter found through the external registry
+#[derive(Clone, Copy)]
+pub struct ExternalPluginRecord<'a> {
+    /// Kept as the raw registry value, so that adapters newer than this crate can still be skipped over
+    pub plugin_type: u8,
+    pub authority: PluginAuthority,
+    pub lifecycle_checks: Option<LifecycleChecksView<'a>>,
+    /// The adapter, right after its `ExternalPluginAdapter` discriminant, until the end of the account
+    pub adapter: &'a [u8],
+    /// The data written by the data authority, for the adapters that hold data
+    pub data: Option<&'a [u8]>,
+}
+
+/// The lifecycle checks of an external registry record, still in their serialized form
+#[derive(Clone, Copy)]
+pub struct LifecycleChecksView<'a> {
+    /// The checks without their length, an event and its flags each
+    bytes: &'a [u8],
+}
+
+impl<'a> LifecycleChecksView<'a> {
+    const CHECK_SIZE: usize = 1 + size_of::<u32>();
+
+    /// Borrows the checks from `bytes`, returning them and how many bytes they took, length included
+    fn from_bytes(bytes: &'a [u8]) -> Result<(Self, usize), ProgramError> {
+        let len = u32::deserialize(read_slice(bytes, 0, 4)?)?;
+        let size = usize::try_from(len)
+            .map_err(|_| MplCoreError::NumericalOverflow)?
+            .checked_mul(Self::CHECK_SIZE)
+            .ok_or(MplCoreError::NumericalOverflow)?;
+        Ok((
+            Self {
+                bytes: read_slice(bytes, 4, size)?,
+            },
+            4 + size,
+        ))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len() / Self::CHECK_SIZE
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = Result<(HookableLifecycleEvent, ExternalCheckResult), ProgramError>> + 'a
+    {
+        self.bytes.chunks_exact(Self::CHECK_SIZE).map(|check| {
+            Ok((
+                HookableLifecycleEvent::try_from(check[0])?,
+                ExternalCheckResult {
+                    flags: u32::deserialize(&check[1..])?,
+                },
+            ))
+        })
+    }
+}
+
+pub struct ExternalRegistryIter<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    remaining: u32,
+}
+
+impl<'a> ExternalRegistryIter<'a> {
+    fn new(registry: Option<&RegistryView<'a>>) -> Result<Self, ProgramError> {
+        let Some(registry) = registry else {
+            return Ok(Self {
+                bytes: &[],
+                offset: 0,
+                remaining: 0,
+            });
+        };
+
+        // the plugin records have no fixed size, the external registry is only found by reading them
+        let mut plugins = RegistryIter::new(Some(registry));
+        for record in plugins.by_ref() {
+            record?;
+        }
+
+        Ok(Self {
+            bytes: registry.bytes,
+            offset: plugins.offset + 4,
+            remaining: u32::deserialize(read_slice(registry.bytes, plugins.offset, 4)?)?,
+        })
+    }
+
+    fn read_record(&mut self) -> Result<ExternalPluginRecord<'a>, ProgramError> {
+        let plugin_type = read_u8(self.bytes, self.offset)?;
+        self.offset += 1;
+
+        let authority = PluginAuthority::deserialize(read_tail(self.bytes, self.offset)?)?;
+        self.offset += authority.serialized_size();
+
+        let lifecycle_checks = match read_u8(self.bytes, self.offset)? {
+            0 => None,
+            1 => {
+                let (checks, len) =
+                    LifecycleChecksView::from_bytes(read_tail(self.bytes, self.offset + 1)?)?;
+                self.offset += len;
+                Some(checks)
+            }
+            _ => return Err(MplCoreError::InvalidOption.into()),
+        };
+        self.offset += 1;
+
+        let adapter_offset = self.read_offset()?;
+        let data_offset = self.read_option_offset()?;
+        let data_len = self.read_option_offset()?;
+
+        // check the adapter discriminant matches what the registry says
+        let adapter_disc = read_u8(self.bytes, adapter_offset)
+            .map_err(|_| MplCoreError::PluginOffsetOutOfRange)?;
+        if adapter_disc != plugin_type {
+            return Err(MplCoreError::PluginTypeMismatch.into());
+        }
+
+        let data = match (data_offset, data_len) {
+            (Some(data_offset), Some(data_len)) => Some(
+                read_slice(self.bytes, data_offset, data_len)
+                    .map_err(|_| MplCoreError::PluginOffsetOutOfRange)?,
+            ),
+            (None, None) => None,
+            _ => return Err(MplCoreError::InvalidExternalPluginAdapter.into()),
+        };
+
+        Ok(ExternalPluginRecord {
+            plugin_type,
+            authority,
+            lifecycle_checks,
+            adapter: &self.bytes[adapter_offset + 1..],
+            data,
+        })
+    }
+
+    /// Reads an offset or length of the record as a usize
+    fn read_offset(&mut self) -> Result<usize, ProgramError> {
+        let value = u64::deserialize(read_slice(self.bytes, self.offset, 8)?)?;
+        self.offset += size_of::<u64>();
+        usize::try_from(value).map_err(|_| MplCoreError::NumericalOverflow.into())
+    }
+
+    fn read_option_offset(&mut self) -> Result<Option<usize>, ProgramError> {
+        let is_some = read_u8(self.bytes, self.offset)?;
+        self.offset += 1;
+        match is_some {
+            0 => Ok(None),
+            1 => self.read_offset().map(Some),
+            _ => Err(MplCoreError::InvalidOption.into()),
+        }
+    }
+}
+
+impl<'a> Iterator for ExternalRegistryIter<'a> {
+    type Item = Result<ExternalPluginRecord<'a>, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let record = self.read_record();
+        if record.is_err() {
+            // the rest of the registry can't be trusted
+            self.remaining = 0;
+        }
+        Some(record)
+    }
+}
+
+fn find_plugin<'a>(
+    mut plugins: RegistryIter<'a>,
+    plugin_type: PluginType,
+) -> Result<Option<PluginRecord<'a>>, ProgramError> {
+    plugins
+        .find(|record| match record {
+            Ok(record) => record.plugin_type == plugin_type as u8,
+            Err(_) => true,
+        })
+        .transpose()
+}
@@ -0,0 +1,416 @@
+use pinocchio::program_error::ProgramError;
+use pinocchio_log::logger::Logger;
+
+/// First `ProgramError::Custom` code of `MplCoreError`. mpl core's own codes start at 0, see
+/// `MplCoreProgramError`, so this crate's errors start far above them (and above Anchor's 6000)
+/// to never be mistaken for one of them. `0x4d43` is "MC"
+pub const MPL_CORE_ERROR_BASE: u32 = 0x4d43_0000;
+
+/// Errors returned by this crate, converted into `ProgramError::Custom(MPL_CORE_ERROR_BASE + n)`
+/// where `n` is the variant's position
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MplCoreError {
+    /// The account passed as the Metaplex Core program is not `MPL_CORE_ID`
+    InvalidMplCoreProgram = MPL_CORE_ERROR_BASE,
+    /// The account passed as the system program is not the system program
+    InvalidSystemProgram,
+    /// The account passed as the log wrapper is not the SPL Noop program
+    InvalidLogWrapperProgram,
+    /// An asset or collection account is not owned by the Metaplex Core program
+    InvalidAccountOwner,
+    /// An account that must sign the instruction is not a signer
+    MissingSigner,
+    /// An account that must be writable is not writable
+    AccountNotWritable,
+    /// The account data does not start with the expected `Key`
+    InvalidKey,
+    /// The account data ended before the value being read
+    UnexpectedEndOfData,
+    /// A length or offset read from the account data does not fit in a usize
+    NumericalOverflow,
+    /// The `plugin_registry_offset` in the plugin header points outside of the account data
+    RegistryOffsetOutOfRange,
+    /// A plugin offset in the registry points outside of the account data
+    PluginOffsetOutOfRange,
+    /// The plugin discriminant is not a known `Plugin` variant
+    UnknownPluginDiscriminant,
+    /// The plugin found at a registry record is not of the type the record says it is
+    PluginTypeMismatch,
+    /// The discriminant is not a valid `PluginAuthority` variant
+    InvalidPluginAuthority,
+    /// The discriminant is not a valid `UpdateAuthority` variant
+    InvalidUpdateAuthority,
+    /// The discriminant of an `Option` is neither 0 nor 1
+    InvalidOption,
+    /// The asset's update authority is not a collection
+    UpdateAuthorityNotCollection,
+    /// The buffer given for serialization is smaller than the serialized data
+    BufferTooSmall,
+    /// A bool is neither 0 nor 1
+    InvalidBool,
+    /// Royalties basis points are above 10000
+    InvalidBasisPoints,
+    /// The creators' percentages don't add up to 100
+    InvalidCreatorShares,
+    /// The discriminant is not a valid `RuleSet` variant
+    InvalidRuleSet,
+    /// The attribute value is not a number of the requested type
+    InvalidAttributeValue,
+    /// The collection has no MasterEdition plugin to print editions from
+    MissingMasterEdition,
+    /// Printing another edition would go over the MasterEdition's max supply
+    MaxSupplyReached,
+    /// The instruction needs the asset's collection, but none was given
+    MissingCollection,
+    /// The address already has a signature in the plugin
+    DuplicateSignature,
+    /// The creator is already verified
+    AlreadyVerified,
+    /// The autograph message is longer than `MAX_AUTOGRAPH_MESSAGE_LEN`
+    AutographMessageTooLong,
+    /// The same plugin was added twice
+    DuplicatePlugin,
+    /// An authority was given before any plugin
+    MissingPlugin,
+    /// An update authority was given together with a collection, which already is the update authority
+    ConflictingUpdateAuthority,
+    /// mpl core only supports `DataState::AccountState`
+    LedgerStateNotSupported,
+    /// A name, uri, attribute or message is not valid UTF-8, so it can't be decoded into a `String`
+    InvalidUtf8,
+    /// The authority can't manage the plugin: `None`, or the update authority on a plugin managed by the owner
+    InvalidAuthorityForPlugin,
+    /// A discriminant in an external plugin adapter or its registry record is not a known variant
+    InvalidExternalPluginAdapter,
+}
+
+impl From<MplCoreError> for ProgramError {
+    fn from(error: MplCoreError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+/// Custom errors returned by the Metaplex Core program itself.
+/// When a CPI fails with `ProgramError::Custom(n)`, this tells you what `n` means.
+/// Mirrors mpl-core 0.12
+///
+/// Keep in mind the runtime aborts the whole transaction when a CPI fails, so on-chain you will
+/// only ever see these from a program that returns them itself. Off-chain and in tests, this is what
+/// you match the transaction error against.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MplCoreProgramError {
+    /// 0 - Invalid System Program
+    InvalidSystemProgram = 0,
+    /// 1 - Error deserializing account
+    DeserializationError = 1,
+    /// 2 - Error serializing account
+    SerializationError = 2,
+    /// 3 - Plugins not initialized
+    PluginsNotInitialized = 3,
+    /// 4 - Plugin not found
+    PluginNotFound = 4,
+    /// 5 - Numerical Overflow
+    NumericalOverflow = 5,
+    /// 6 - Incorrect account
+    IncorrectAccount = 6,
+    /// 7 - Incorrect asset hash
+    IncorrectAssetHash = 7,
+    /// 8 - Invalid Plugin
+    InvalidPlugin = 8,
+    /// 9 - Invalid Authority
+    InvalidAuthority = 9,
+    /// 10 - Cannot transfer a frozen asset
+    AssetIsFrozen = 10,
+    /// 11 - Missing compression proof
+    MissingCompressionProof = 11,
+    /// 12 - Cannot migrate a master edition used for prints
+    CannotMigrateMasterWithSupply = 12,
+    /// 13 - Cannot migrate a print edition
+    CannotMigratePrints = 13,
+    /// 14 - Cannot burn a collection NFT
+    CannotBurnCollection = 14,
+    /// 15 - Plugin already exists
+    PluginAlreadyExists = 15,
+    /// 16 - Numerical overflow
+    NumericalOverflowError = 16,
+    /// 17 - Already compressed account
+    AlreadyCompressed = 17,
+    /// 18 - Already decompressed account
+    AlreadyDecompressed = 18,
+    /// 19 - Invalid Collection passed in
+    InvalidCollection = 19,
+    /// 20 - Missing update authority
+    MissingUpdateAuthority = 20,
+    /// 21 - Missing new owner
+    MissingNewOwner = 21,
+    /// 22 - Missing system program
+    MissingSystemProgram = 22,
+    /// 23 - Feature not available
+    NotAvailable = 23,
+    /// 24 - Invalid Asset passed in
+    InvalidAsset = 24,
+    /// 25 - Missing collection
+    MissingCollection = 25,
+    /// 26 - Neither the asset or any plugins have approved this operation
+    NoApprovals = 26,
+    /// 27 - Plugin Manager cannot redelegate a delegated plugin without revoking first
+    CannotRedelegate = 27,
+    /// 28 - Invalid setting for plugin
+    InvalidPluginSetting = 28,
+    /// 29 - Cannot specify both an update authority and collection on an asset
+    ConflictingAuthority = 29,
+    /// 30 - Invalid Log Wrapper Program
+    InvalidLogWrapperProgram = 30,
+    /// 31 - External Plugin Adapter not found
+    ExternalPluginAdapterNotFound = 31,
+    /// 32 - External Plugin Adapter already exists
+    ExternalPluginAdapterAlreadyExists = 32,
+    /// 33 - Missing asset needed for extra account PDA derivation
+    MissingAsset = 33,
+    /// 34 - Missing account needed for external plugin adapter
+    MissingExternalPluginAdapterAccount = 34,
+    /// 35 - Oracle external plugin adapter can only be configured to reject
+    OracleCanRejectOnly = 35,
+    /// 36 - External plugin adapter must have at least one lifecycle check
+    RequiresLifecycleCheck = 36,
+    /// 37 - Duplicate lifecycle checks were provided for external plugin adapter
+    DuplicateLifecycleChecks = 37,
+    /// 38 - Could not read from oracle account
+    InvalidOracleAccountData = 38,
+    /// 39 - Oracle account is uninitialized
+    UninitializedOracleAccount = 39,
+    /// 40 - Missing required signer for operation
+    MissingSigner = 40,
+    /// 41 - Invalid plugin operation
+    InvalidPluginOperation = 41,
+    /// 42 - Collection must be empty to be burned
+    CollectionMustBeEmpty = 42,
+    /// 43 - Two data sources provided, only one is allowed
+    TwoDataSources = 43,
+    /// 44 - External Plugin does not support this operation
+    UnsupportedOperation = 44,
+    /// 45 - No data sources provided, one is required
+    NoDataSources = 45,
+    /// 46 - This plugin adapter cannot be added to an Asset
+    InvalidPluginAdapterTarget = 46,
+    /// 47 - Cannot add a Data Section without a linked external plugin
+    CannotAddDataSection = 47,
+    /// 48 - Cannot move asset to collection with permanent delegates
+    PermanentDelegatesPreventMove = 48,
+    /// 49 - Invalid Signing PDA for Asset or Collection Execute
+    InvalidExecutePda = 49,
+    /// 50 - Bubblegum V2 Plugin limits other plugins
+    BlockedByBubblegumV2 = 50,
+    /// 51 - Agent Identity Program must sign
+    AgentIdentityMustSign = 51,
+    /// 52 - Group must be empty to be closed
+    GroupMustBeEmpty = 52,
+    /// 53 - Duplicate entry provided when adding relationships to a group
+    DuplicateEntry = 53,
+    /// 54 - Group vector is at maximum capacity
+    GroupVectorFull = 54,
+    /// 55 - Group nesting depth exceeded
+    GroupNestingDepthExceeded = 55,
+    /// 56 - Bidirectional group relationship is inconsistent
+    InconsistentGroupRelationship = 56,
+}
+
+impl MplCoreProgramError {
+    /// The message mpl core logs for this error
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::InvalidSystemProgram => "Invalid System Program",
+            Self::DeserializationError => "Error deserializing account",
+            Self::SerializationError => "Error serializing account",
+            Self::PluginsNotInitialized => "Plugins not initialized",
+            Self::PluginNotFound => "Plugin not found",
+            Self::NumericalOverflow => "Numerical Overflow",
+            Self::IncorrectAccount => "Incorrect account",
+            Self::IncorrectAssetHash => "Incorrect asset hash",
+            Self::InvalidPlugin => "Invalid Plugin",
+            Self::InvalidAuthority => "Invalid Authority",
+            Self::AssetIsFrozen => "Cannot transfer a frozen asset",
+            Self::MissingCompressionProof => "Missing compression proof",
+            Self::CannotMigrateMasterWithSupply => {
+                "Cannot migrate a master edition used for prints"
+            }
+            Self::CannotMigratePrints => "Cannot migrate a print edition",
+            Self::CannotBurnCollection => "Cannot burn a collection NFT",
+            Self::PluginAlreadyExists => "Plugin already exists",
+            Self::NumericalOverflowError => "Numerical overflow",
+            Self::AlreadyCompressed => "Already compressed account",
+            Self::AlreadyDecompressed => "Already decompressed account",
+            Self::InvalidCollection => "Invalid Collection passed in",
+            Self::MissingUpdateAuthority => "Missing update authority",
+            Self::MissingNewOwner => "Missing new owner",
+            Self::MissingSystemProgram => "Missing system program",
+            Self::NotAvailable => "Feature not available",
+            Self::InvalidAsset => "Invalid Asset passed in",
+            Self::MissingCollection => "Missing collection",
+            Self::NoApprovals => "Neither the asset or any plugins have approved this operation",
+            Self::CannotRedelegate => {
+                "Plugin Manager cannot redelegate a delegated plugin without revoking first"
+            }
+            Self::InvalidPluginSetting => "Invalid setting for plugin",
+            Self::ConflictingAuthority => {
+                "Cannot specify both an update authority and collection on an asset"
+            }
+            Self::InvalidLogWrapperProgram => "Invalid Log Wrapper Program",
+            Self::ExternalPluginAdapterNotFound => "External Plugin Adapter not found",
+            Self::ExternalPluginAdapterAlreadyExists => "External Plugin Adapter already exists",
+            Self::MissingAsset => "Missing asset needed for extra account PDA derivation",
+            Self::MissingExternalPluginAdapterAccount => {
+                "Missing account needed for external plugin adapter"
+            }
+            Self::OracleCanRejectOnly => {
+                "Oracle external plugin adapter can only be configured to reject"
+            }
+            Self::RequiresLifecycleCheck => {
+                "External plugin adapter must have at least one lifecycle check"
+            }
+            Self::DuplicateLifecycleChecks => {
+                "Duplicate lifecycle checks were provided for external plugin adapter"
+            }
+            Self::InvalidOracleAccountData => "Could not read from oracle account",
+            Self::UninitializedOracleAccount => "Oracle account is uninitialized",
+            Self::MissingSigner => "Missing required signer for operation",
+            Self::InvalidPluginOperation => "Invalid plugin operation",
+            Self::CollectionMustBeEmpty => "Collection must be empty to be burned",
+            Self::TwoDataSources => "Two data sources provided, only one is allowed",
+            Self::UnsupportedOperation => "External Plugin does not support this operation",
+            Self::NoDataSources => "No data sources provided, one is required",
+            Self::InvalidPluginAdapterTarget => "This plugin adapter cannot be added to an Asset",
+            Self::CannotAddDataSection => {
+                "Cannot add a Data Section without a linked external plugin"
+            }
+            Self::PermanentDelegatesPreventMove => {
+                "Cannot move asset to collection with permanent delegates"
+            }
+            Self::InvalidExecutePda => "Invalid Signing PDA for Asset or Collection Execute",
+            Self::BlockedByBubblegumV2 => "Bubblegum V2 Plugin limits other plugins",
+            Self::AgentIdentityMustSign => "Agent Identity Program must sign",
+            Self::GroupMustBeEmpty => "Group must be empty to be closed",
+            Self::DuplicateEntry => "Duplicate entry provided when adding relationships to a group",
+            Self::GroupVectorFull => "Group vector is at maximum capacity",
+            Self::GroupNestingDepthExceeded => "Group nesting depth exceeded",
+            Self::InconsistentGroupRelationship => {
+                "Bidirectional group relationship is inconsistent"
+            }
+        }
+    }
+
+    /// Logs the error code and message, using the same text mpl core does
+    pub fn log(&self) {
+        let mut logger = Logger::<128>::default();
+        logger.append("mpl core error ");
+        logger.append(*self as u32);
+        logger.append(": ");
+        logger.append(self.message());
+        logger.log();
+    }
+}
+
+impl TryFrom<u32> for MplCoreProgramError {
+    type Error = ProgramError;
+
+    /// Fails with `ProgramError::Custom(value)` for codes mpl core doesn't use, which includes every
+    /// `MplCoreError` since those start at `MPL_CORE_ERROR_BASE`
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::InvalidSystemProgram),
+            1 => Ok(Self::DeserializationError),
+            2 => Ok(Self::SerializationError),
+            3 => Ok(Self::PluginsNotInitialized),
+            4 => Ok(Self::PluginNotFound),
+            5 => Ok(Self::NumericalOverflow),
+            6 => Ok(Self::IncorrectAccount),
+            7 => Ok(Self::IncorrectAssetHash),
+            8 => Ok(Self::InvalidPlugin),
+            9 => Ok(Self::InvalidAuthority),
+            10 => Ok(Self::AssetIsFrozen),
+            11 => Ok(Self::MissingCompressionProof),
+            12 => Ok(Self::CannotMigrateMasterWithSupply),
+            13 => Ok(Self::CannotMigratePrints),
+            14 => Ok(Self::CannotBurnCollection),
+            15 => Ok(Self::PluginAlreadyExists),
+            16 => Ok(Self::NumericalOverflowError),
+            17 => Ok(Self::AlreadyCompressed),
+            18 => Ok(Self::AlreadyDecompressed),
+            19 => Ok(Self::InvalidCollection),
+            20 => Ok(Self::MissingUpdateAuthority),
+            21 => Ok(Self::MissingNewOwner),
+            22 => Ok(Self::MissingSystemProgram),
+            23 => Ok(Self::NotAvailable),
+            24 => Ok(Self::InvalidAsset),
+            25 => Ok(Self::MissingCollection),
+            26 => Ok(Self::NoApprovals),
+            27 => Ok(Self::CannotRedelegate),
+            28 => Ok(Self::InvalidPluginSetting),
+            29 => Ok(Self::ConflictingAuthority),
+            30 => Ok(Self::InvalidLogWrapperProgram),
+            31 => Ok(Self::ExternalPluginAdapterNotFound),
+            32 => Ok(Self::ExternalPluginAdapterAlreadyExists),
+            33 => Ok(Self::MissingAsset),
+            34 => Ok(Self::MissingExternalPluginAdapterAccount),
+            35 => Ok(Self::OracleCanRejectOnly),
+            36 => Ok(Self::RequiresLifecycleCheck),
+            37 => Ok(Self::DuplicateLifecycleChecks),
+            38 => Ok(Self::InvalidOracleAccountData),
+            39 => Ok(Self::UninitializedOracleAccount),
+            40 => Ok(Self::MissingSigner),
+            41 => Ok(Self::InvalidPluginOperation),
+            42 => Ok(Self::CollectionMustBeEmpty),
+            43 => Ok(Self::TwoDataSources),
+            44 => Ok(Self::UnsupportedOperation),
+            45 => Ok(Self::NoDataSources),
+            46 => Ok(Self::InvalidPluginAdapterTarget),
+            47 => Ok(Self::CannotAddDataSection),
+            48 => Ok(Self::PermanentDelegatesPreventMove),
+            49 => Ok(Self::InvalidExecutePda),
+            50 => Ok(Self::BlockedByBubblegumV2),
+            51 => Ok(Self::AgentIdentityMustSign),
+            52 => Ok(Self::GroupMustBeEmpty),
+            53 => Ok(Self::DuplicateEntry),
+            54 => Ok(Self::GroupVectorFull),
+            55 => Ok(Self::GroupNestingDepthExceeded),
+            56 => Ok(Self::InconsistentGroupRelationship),
+            _ => Err(ProgramError::Custom(value)),
+        }
+    }
+}
+
+impl TryFrom<ProgramError> for MplCoreProgramError {
+    type Error = ProgramError;
+
+    /// Fails with the original error if it is not a mpl core custom error
+    fn try_from(error: ProgramError) -> Result<Self, Self::Error> {
+        match error {
+            ProgramError::Custom(code) => Self::try_from(code),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<MplCoreProgramError> for ProgramError {
+    fn from(error: MplCoreProgramError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+/// Logs the result of a failed mpl core CPI, decoding it if it is one of mpl core's custom errors.
+/// Errors this crate returned before invoking, like `MplCoreError::BufferTooSmall`, are logged as their
+/// raw code. Returns the error unchanged so it can be used as `.map_err(log_cpi_error)`
+pub fn log_cpi_error(error: ProgramError) -> ProgramError {
+    match MplCoreProgramError::try_from(error) {
+        Ok(mpl_core_error) => mpl_core_error.log(),
+        Err(_) => {
+            let mut logger = Logger::<64>::default();
+            logger.append("mpl core cpi failed: ");
+            logger.append(u64::from(error));
+            logger.log();
+        }
+    }
+    error
+}
@@ -5,7 +5,13 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::data::{burn_asset::BurnAssetV1InstructionData, Serialize};
+use crate::{
+    data::{burn_asset::BurnAssetV1InstructionData, Serialize},
+    instructions::{
+        check_log_wrapper, check_mpl_core_program, check_owned_by_mpl_core, check_signer,
+        check_system_program, check_writable,
+    },
+};
 
 /// Burn an asset
 ///
@@ -43,6 +49,31 @@ pub struct BurnV1<'a> {
 }
 
 impl BurnV1<'_> {
+    /// Checks the program ids, that the asset and the collection, when set, are writable mpl core accounts,
+    /// that the payer signs and is writable, and that the authority signs
+    pub fn validate(&self) -> ProgramResult {
+        check_mpl_core_program(self.mpl_core)?;
+        check_system_program(self.system_program)?;
+        check_log_wrapper(self.log_wrapper)?;
+
+        check_owned_by_mpl_core(self.asset)?;
+        check_writable(self.asset)?;
+
+        if let Some(collection) = self.collection {
+            check_owned_by_mpl_core(collection)?;
+            check_writable(collection)?;
+        }
+
+        check_signer(self.payer)?;
+        check_writable(self.payer)?;
+
+        if let Some(authority) = self.authority {
+            check_signer(authority)?;
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn invoke(
         &self,
@@ -65,7 +96,7 @@ impl BurnV1<'_> {
                 Some(collection) => AccountMeta::writable(collection.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
             },
-            AccountMeta::writable_signer(&self.payer.key()),
+            AccountMeta::writable_signer(self.payer.key()),
             match self.authority {
                 Some(authority) => AccountMeta::readonly_signer(authority.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
@@ -77,12 +108,12 @@ impl BurnV1<'_> {
             },
         ];
 
-        let len = data.serialize_to(serialization_buffer);
+        let len = data.try_serialize_to(serialization_buffer)?;
         let data = &serialization_buffer[..len];
 
         let instruction = Instruction {
             program_id: &crate::MPL_CORE_ID,
-            accounts: &account_metas,
+            accounts: account_metas,
             data,
         };
 
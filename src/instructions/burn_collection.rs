@@ -5,7 +5,13 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::data::{burn_collection::BurnCollectionV1InstructionData, Serialize};
+use crate::{
+    data::{burn_collection::BurnCollectionV1InstructionData, Serialize},
+    instructions::{
+        check_log_wrapper, check_mpl_core_program, check_owned_by_mpl_core, check_signer,
+        check_writable,
+    },
+};
 
 /// Burn a collection
 ///
@@ -37,6 +43,26 @@ pub struct BurnCollectionV1<'a> {
 }
 
 impl BurnCollectionV1<'_> {
+    /// Checks the mpl core and log wrapper program ids, that the collection is a writable mpl core account,
+    /// and that the payer and the authority, when set, sign and are writable like mpl core marks them
+    pub fn validate(&self) -> ProgramResult {
+        check_mpl_core_program(self.mpl_core)?;
+        check_log_wrapper(self.log_wrapper)?;
+
+        check_owned_by_mpl_core(self.collection)?;
+        check_writable(self.collection)?;
+
+        check_signer(self.payer)?;
+        check_writable(self.payer)?;
+
+        if let Some(authority) = self.authority {
+            check_signer(authority)?;
+            check_writable(authority)?;
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn invoke(
         &self,
@@ -55,7 +81,7 @@ impl BurnCollectionV1<'_> {
         // account metadata
         let account_metas: &[AccountMeta] = &[
             AccountMeta::writable(self.collection.key()),
-            AccountMeta::writable_signer(&self.payer.key()),
+            AccountMeta::writable_signer(self.payer.key()),
             match self.authority {
                 Some(authority) => AccountMeta::writable_signer(authority.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
@@ -66,12 +92,12 @@ impl BurnCollectionV1<'_> {
             },
         ];
 
-        let len = data.serialize_to(serialization_buffer);
+        let len = data.try_serialize_to(serialization_buffer)?;
         let data = &serialization_buffer[..len];
 
         let instruction = Instruction {
             program_id: &crate::MPL_CORE_ID,
-            accounts: &account_metas,
+            accounts: account_metas,
             data,
         };
 
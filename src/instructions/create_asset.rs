@@ -2,10 +2,22 @@ use pinocchio::{
     account_info::AccountInfo,
     cpi::invoke_signed,
     instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
     ProgramResult,
 };
 
-use crate::data::{create_asset::CreateAssetV1InstructionData, Serialize};
+use crate::{
+    data::{
+        create_asset::{CreateAssetV1InstructionData, DataState, PrintEditionV1InstructionData},
+        plugins::PluginAuthorityPair,
+        Serialize,
+    },
+    error::MplCoreError,
+    instructions::{
+        check_log_wrapper, check_mpl_core_program, check_owned_by_mpl_core, check_signer,
+        check_system_program, check_writable,
+    },
+};
 
 /// Create an asset
 ///
@@ -49,6 +61,31 @@ pub struct CreateAssetV1<'a> {
 }
 
 impl CreateAssetV1<'_> {
+    /// Checks the mpl core, system and log wrapper program ids, that the new asset and the payer sign and
+    /// are writable, and that the collection, when set, is a writable mpl core account.
+    /// The asset does not exist yet so its owner isn't checked, and neither are the owner and update authority
+    pub fn validate(&self) -> ProgramResult {
+        check_mpl_core_program(self.mpl_core)?;
+        check_system_program(self.system_program)?;
+        check_log_wrapper(self.log_wrapper)?;
+
+        // the asset does not exist yet, so only the collection can be checked for ownership
+        check_signer(self.asset)?;
+        check_writable(self.asset)?;
+
+        if let Some(collection) = self.collection {
+            check_owned_by_mpl_core(collection)?;
+            check_writable(collection)?;
+        }
+
+        if let Some(authority) = self.authority {
+            check_signer(authority)?;
+        }
+
+        check_signer(self.payer)?;
+        check_writable(self.payer)
+    }
+
     #[inline(always)]
     pub fn invoke(
         &self,
@@ -63,6 +100,37 @@ impl CreateAssetV1<'_> {
         data: &CreateAssetV1InstructionData,
         signers: &[Signer],
         serialization_buffer: &mut [u8],
+    ) -> ProgramResult {
+        self.invoke_signed_with(data, signers, serialization_buffer)
+    }
+
+    /// Prints the next edition of `collection`, which must be set
+    #[inline(always)]
+    pub fn invoke_print_edition(
+        &self,
+        data: &PrintEditionV1InstructionData,
+        serialization_buffer: &mut [u8],
+    ) -> ProgramResult {
+        self.invoke_signed_print_edition(data, &[], serialization_buffer)
+    }
+
+    pub fn invoke_signed_print_edition(
+        &self,
+        data: &PrintEditionV1InstructionData,
+        signers: &[Signer],
+        serialization_buffer: &mut [u8],
+    ) -> ProgramResult {
+        if self.collection.is_none() {
+            return Err(MplCoreError::MissingCollection.into());
+        }
+        self.invoke_signed_with(data, signers, serialization_buffer)
+    }
+
+    fn invoke_signed_with(
+        &self,
+        data: &impl Serialize,
+        signers: &[Signer],
+        serialization_buffer: &mut [u8],
     ) -> ProgramResult {
         // account metadata
         let account_metas: &[AccountMeta] = &[
@@ -91,12 +159,12 @@ impl CreateAssetV1<'_> {
             },
         ];
 
-        let len = data.serialize_to(serialization_buffer);
+        let len = data.try_serialize_to(serialization_buffer)?;
         let data = &serialization_buffer[..len];
 
         let instruction = Instruction {
             program_id: &crate::MPL_CORE_ID,
-            accounts: &account_metas,
+            accounts: account_metas,
             data,
         };
 
@@ -116,3 +184,144 @@ impl CreateAssetV1<'_> {
         )
     }
 }
+
+/// Builds a `CreateAssetV1` and its instruction data together:
+///
+/// ```
+/// # use mpl_core_pinocchio::{
+/// #     data::plugins::PluginAuthorityPair, instructions::create_asset::CreateAssetV1,
+/// # };
+/// # use pinocchio::{account_info::AccountInfo, ProgramResult};
+/// fn create(
+///     [asset, collection, authority, payer, owner, system_program, mpl_core]: &[AccountInfo; 7],
+///     plugins: &[PluginAuthorityPair],
+///     serialization_buffer: &mut [u8],
+/// ) -> ProgramResult {
+///     CreateAssetV1::builder(asset, payer, system_program, mpl_core)
+///         .collection(collection)
+///         .authority(authority)
+///         .owner(owner)
+///         .name(b"My asset")
+///         .uri(b"https://example.com/asset.json")
+///         .plugins(plugins)
+///         .invoke(serialization_buffer)
+/// }
+/// ```
+///
+/// `build` and `invoke` reject the combinations mpl core would reject, and `invoke` also runs `validate`
+pub struct CreateAssetV1Builder<'a> {
+    accounts: CreateAssetV1<'a>,
+    data: CreateAssetV1InstructionData<'a>,
+}
+
+impl<'a> CreateAssetV1<'a> {
+    pub fn builder(
+        asset: &'a AccountInfo,
+        payer: &'a AccountInfo,
+        system_program: &'a AccountInfo,
+        mpl_core: &'a AccountInfo,
+    ) -> CreateAssetV1Builder<'a> {
+        CreateAssetV1Builder {
+            accounts: CreateAssetV1 {
+                asset,
+                collection: None,
+                authority: None,
+                payer,
+                owner: None,
+                update_authority: None,
+                system_program,
+                log_wrapper: None,
+                mpl_core,
+            },
+            data: CreateAssetV1InstructionData {
+                data_state: DataState::AccountState,
+                name: &[],
+                uri: &[],
+                plugins: None,
+            },
+        }
+    }
+}
+
+impl<'a> CreateAssetV1Builder<'a> {
+    /// The collection to create the asset into. The asset's update authority becomes the collection,
+    /// so `update_authority` can't be set too
+    pub fn collection(mut self, collection: &'a AccountInfo) -> Self {
+        self.accounts.collection = Some(collection);
+        self
+    }
+
+    /// Signs for the creation, needed to add the asset to a collection
+    pub fn authority(mut self, authority: &'a AccountInfo) -> Self {
+        self.accounts.authority = Some(authority);
+        self
+    }
+
+    /// Defaults to the authority, then to the payer
+    pub fn owner(mut self, owner: &'a AccountInfo) -> Self {
+        self.accounts.owner = Some(owner);
+        self
+    }
+
+    /// Defaults to the authority, then to the payer. Can't be set together with `collection`
+    pub fn update_authority(mut self, update_authority: &'a AccountInfo) -> Self {
+        self.accounts.update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn log_wrapper(mut self, log_wrapper: &'a AccountInfo) -> Self {
+        self.accounts.log_wrapper = Some(log_wrapper);
+        self
+    }
+
+    /// Only `DataState::AccountState` is supported by mpl core
+    pub fn data_state(mut self, data_state: DataState) -> Self {
+        self.data.data_state = data_state;
+        self
+    }
+
+    pub fn name(mut self, name: &'a [u8]) -> Self {
+        self.data.name = name;
+        self
+    }
+
+    pub fn uri(mut self, uri: &'a [u8]) -> Self {
+        self.data.uri = uri;
+        self
+    }
+
+    /// See `Plugins` for building them
+    pub fn plugins(mut self, plugins: &'a [PluginAuthorityPair<'a>]) -> Self {
+        self.data.plugins = Some(plugins);
+        self
+    }
+
+    pub fn build(
+        self,
+    ) -> Result<(CreateAssetV1<'a>, CreateAssetV1InstructionData<'a>), ProgramError> {
+        if self.accounts.collection.is_some() && self.accounts.update_authority.is_some() {
+            return Err(MplCoreError::ConflictingUpdateAuthority.into());
+        }
+
+        if matches!(self.data.data_state, DataState::LedgerState) {
+            return Err(MplCoreError::LedgerStateNotSupported.into());
+        }
+
+        Ok((self.accounts, self.data))
+    }
+
+    #[inline(always)]
+    pub fn invoke(self, serialization_buffer: &mut [u8]) -> ProgramResult {
+        self.invoke_signed(&[], serialization_buffer)
+    }
+
+    pub fn invoke_signed(
+        self,
+        signers: &[Signer],
+        serialization_buffer: &mut [u8],
+    ) -> ProgramResult {
+        let (accounts, data) = self.build()?;
+        accounts.validate()?;
+        accounts.invoke_signed(&data, signers, serialization_buffer)
+    }
+}
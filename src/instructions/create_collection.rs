@@ -5,7 +5,10 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::data::{create_collection::CreateCollectionV1InstructionData, Serialize};
+use crate::{
+    data::{create_collection::CreateCollectionV1InstructionData, Serialize},
+    instructions::{check_mpl_core_program, check_signer, check_system_program, check_writable},
+};
 
 /// Create a collection
 ///
@@ -33,6 +36,19 @@ pub struct CreateCollectionV1<'a> {
 }
 
 impl CreateCollectionV1<'_> {
+    /// Checks the mpl core and system program ids, and that the new collection and the payer sign and are writable.
+    /// Nothing is owned by mpl core yet, so no owner is checked
+    pub fn validate(&self) -> ProgramResult {
+        check_mpl_core_program(self.mpl_core)?;
+        check_system_program(self.system_program)?;
+
+        check_signer(self.collection)?;
+        check_writable(self.collection)?;
+
+        check_signer(self.payer)?;
+        check_writable(self.payer)
+    }
+
     #[inline(always)]
     pub fn invoke(
         &self,
@@ -59,12 +75,12 @@ impl CreateCollectionV1<'_> {
             AccountMeta::readonly(self.system_program.key()),
         ];
 
-        let len = data.serialize_to(serialization_buffer);
+        let len = data.try_serialize_to(serialization_buffer)?;
         let data = &serialization_buffer[..len];
 
         let instruction = Instruction {
             program_id: &crate::MPL_CORE_ID,
-            accounts: &account_metas,
+            accounts: account_metas,
             data,
         };
 
@@ -1,3 +1,7 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{error::MplCoreError, MPL_CORE_ID, SPL_NOOP_ID, SYSTEM_PROGRAM_ID};
+
 pub mod burn_asset;
 pub mod burn_collection;
 pub mod create_asset;
@@ -5,3 +9,55 @@ pub mod create_collection;
 pub mod transfer;
 pub mod update_asset_plugin;
 pub mod update_collection_plugin;
+
+// helpers shared by the validate() of every CPI struct
+
+#[inline(always)]
+pub(crate) fn check_mpl_core_program(mpl_core: &AccountInfo) -> ProgramResult {
+    if mpl_core.key() != &MPL_CORE_ID {
+        return Err(MplCoreError::InvalidMplCoreProgram.into());
+    }
+    Ok(())
+}
+
+#[inline(always)]
+pub(crate) fn check_system_program(system_program: &AccountInfo) -> ProgramResult {
+    if system_program.key() != &SYSTEM_PROGRAM_ID {
+        return Err(MplCoreError::InvalidSystemProgram.into());
+    }
+    Ok(())
+}
+
+#[inline(always)]
+pub(crate) fn check_log_wrapper(log_wrapper: Option<&AccountInfo>) -> ProgramResult {
+    match log_wrapper {
+        Some(log_wrapper) if log_wrapper.key() != &SPL_NOOP_ID => {
+            Err(MplCoreError::InvalidLogWrapperProgram.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[inline(always)]
+pub(crate) fn check_owned_by_mpl_core(account: &AccountInfo) -> ProgramResult {
+    if !account.is_owned_by(&MPL_CORE_ID) {
+        return Err(MplCoreError::InvalidAccountOwner.into());
+    }
+    Ok(())
+}
+
+#[inline(always)]
+pub(crate) fn check_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer() {
+        return Err(MplCoreError::MissingSigner.into());
+    }
+    Ok(())
+}
+
+#[inline(always)]
+pub(crate) fn check_writable(account: &AccountInfo) -> ProgramResult {
+    if !account.is_writable() {
+        return Err(MplCoreError::AccountNotWritable.into());
+    }
+    Ok(())
+}
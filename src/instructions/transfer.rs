@@ -5,7 +5,13 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::data::{transfer::TransferV1InstructionData, Serialize};
+use crate::{
+    data::{transfer::TransferV1InstructionData, Serialize},
+    instructions::{
+        check_log_wrapper, check_mpl_core_program, check_owned_by_mpl_core, check_signer,
+        check_system_program, check_writable,
+    },
+};
 
 /// Transfer an asset
 ///
@@ -46,6 +52,30 @@ pub struct TransferV1<'a> {
 }
 
 impl TransferV1<'_> {
+    /// Checks the program ids, that the asset is a writable mpl core account, that the payer signs and is writable
+    /// and that the authority signs. The collection is only read by mpl core, so it only has to be owned by it
+    pub fn validate(&self) -> ProgramResult {
+        check_mpl_core_program(self.mpl_core)?;
+        check_system_program(self.system_program)?;
+        check_log_wrapper(self.log_wrapper)?;
+
+        check_owned_by_mpl_core(self.asset)?;
+        check_writable(self.asset)?;
+
+        if let Some(collection) = self.collection {
+            check_owned_by_mpl_core(collection)?;
+        }
+
+        check_signer(self.payer)?;
+        check_writable(self.payer)?;
+
+        if let Some(authority) = self.authority {
+            check_signer(authority)?;
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn invoke(
         &self,
@@ -68,7 +98,7 @@ impl TransferV1<'_> {
                 Some(collection) => AccountMeta::readonly(collection.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
             },
-            AccountMeta::writable_signer(&self.payer.key()),
+            AccountMeta::writable_signer(self.payer.key()),
             match self.authority {
                 Some(authority) => AccountMeta::readonly_signer(authority.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
@@ -81,12 +111,12 @@ impl TransferV1<'_> {
             },
         ];
 
-        let len = data.serialize_to(serialization_buffer);
+        let len = data.try_serialize_to(serialization_buffer)?;
         let data = &serialization_buffer[..len];
 
         let instruction = Instruction {
             program_id: &crate::MPL_CORE_ID,
-            accounts: &account_metas,
+            accounts: account_metas,
             data,
         };
 
@@ -5,7 +5,13 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::data::{update_asset_plugin::UpdateAssetPluginV1InstructionData, Serialize};
+use crate::{
+    data::{update_asset_plugin::UpdateAssetPluginV1InstructionData, Serialize},
+    instructions::{
+        check_log_wrapper, check_mpl_core_program, check_owned_by_mpl_core, check_signer,
+        check_system_program, check_writable,
+    },
+};
 
 /// Update an asset
 ///
@@ -43,6 +49,31 @@ pub struct UpdateAssetPluginV1<'a> {
 }
 
 impl UpdateAssetPluginV1<'_> {
+    /// Checks the program ids, that the asset and the collection, when set, are writable mpl core accounts,
+    /// that the payer signs and is writable, and that the authority signs
+    pub fn validate(&self) -> ProgramResult {
+        check_mpl_core_program(self.mpl_core)?;
+        check_system_program(self.system_program)?;
+        check_log_wrapper(self.log_wrapper)?;
+
+        check_owned_by_mpl_core(self.asset)?;
+        check_writable(self.asset)?;
+
+        if let Some(collection) = self.collection {
+            check_owned_by_mpl_core(collection)?;
+            check_writable(collection)?;
+        }
+
+        check_signer(self.payer)?;
+        check_writable(self.payer)?;
+
+        if let Some(authority) = self.authority {
+            check_signer(authority)?;
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn invoke(
         &self,
@@ -57,6 +88,50 @@ impl UpdateAssetPluginV1<'_> {
         instruction_data: &UpdateAssetPluginV1InstructionData,
         signers: &[Signer],
         serialization_buffer: &mut [u8],
+    ) -> ProgramResult {
+        let len = instruction_data.try_serialize_to(serialization_buffer)?;
+        self.invoke_signed_serialized(&serialization_buffer[..len], signers)
+    }
+
+    /// Invokes with instruction data that is already serialized, like a plugin patch.
+    ///
+    /// A patch reads the current plugin from the asset data, so the asset stays borrowed for as
+    /// long as the patch lives, and mpl core fails with `AccountBorrowFailed` if it is still
+    /// borrowed during the CPI. Serialize the patch into a buffer, drop the borrow, then invoke:
+    ///
+    /// ```
+    /// # use mpl_core_pinocchio::{
+    /// #     data::{
+    /// #         plugins::AttributeOp, update_asset_plugin::UpdateAssetPluginPatchV1InstructionData,
+    /// #         view::AssetView, Serialize,
+    /// #     },
+    /// #     error::MplCoreError,
+    /// #     instructions::update_asset_plugin::UpdateAssetPluginV1,
+    /// # };
+    /// # use pinocchio::ProgramResult;
+    /// fn remove_class(update: &UpdateAssetPluginV1, buffer: &mut [u8]) -> ProgramResult {
+    ///     let len = {
+    ///         let data = update.asset.try_borrow_data()?;
+    ///         let attributes = AssetView::new(&data)?
+    ///             .attributes()?
+    ///             .ok_or(MplCoreError::MissingPlugin)?;
+    ///         UpdateAssetPluginPatchV1InstructionData {
+    ///             patch: attributes.patch(&[AttributeOp::Remove { key: b"class" }]),
+    ///         }
+    ///         .try_serialize_to(buffer)?
+    ///     };
+    ///     update.invoke_serialized(&buffer[..len])
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn invoke_serialized(&self, instruction_data: &[u8]) -> ProgramResult {
+        self.invoke_signed_serialized(instruction_data, &[])
+    }
+
+    pub fn invoke_signed_serialized(
+        &self,
+        instruction_data: &[u8],
+        signers: &[Signer],
     ) -> ProgramResult {
         // account metadata
         let account_metas: &[AccountMeta] = &[
@@ -65,7 +140,7 @@ impl UpdateAssetPluginV1<'_> {
                 Some(collection) => AccountMeta::writable(collection.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
             },
-            AccountMeta::writable_signer(&self.payer.key()),
+            AccountMeta::writable_signer(self.payer.key()),
             match self.authority {
                 Some(authority) => AccountMeta::readonly_signer(authority.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
@@ -77,13 +152,10 @@ impl UpdateAssetPluginV1<'_> {
             },
         ];
 
-        let len = instruction_data.serialize_to(serialization_buffer);
-        let data = &serialization_buffer[..len];
-
         let instruction = Instruction {
             program_id: &crate::MPL_CORE_ID,
-            accounts: &account_metas,
-            data,
+            accounts: account_metas,
+            data: instruction_data,
         };
 
         invoke_signed(
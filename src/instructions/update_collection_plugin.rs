@@ -5,17 +5,23 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::data::{update_asset_plugin::UpdateAssetPluginV1InstructionData, Serialize};
+use crate::{
+    data::{update_collection_plugin::UpdateCollectionPluginV1InstructionData, Serialize},
+    instructions::{
+        check_log_wrapper, check_mpl_core_program, check_owned_by_mpl_core, check_signer,
+        check_system_program, check_writable,
+    },
+};
 
-/// Update a collection
+/// Update a plugin of a collection
 ///
 /// ### Accounts:
 ///   0. `[WRITE]` Collection
 ///   1. `[WRITE, SIGNER]` payer
 ///   2. `[SIGNER, OPTIONAL]` Authority
-///   4. `[]` System Program
-///   5. `[OPTIONAL]` SPL Noop
-///   6. `[]` Metaplex Core Program
+///   3. `[]` System Program
+///   4. `[OPTIONAL]` SPL Noop
+///   5. `[]` Metaplex Core Program
 ///
 /// Accounts being optional is very cursed but mimics the behaviour of the official lib.
 /// Accounts set to None get replaced by mpl core program's account.
@@ -40,10 +46,30 @@ pub struct UpdateCollectionPluginV1<'a> {
 }
 
 impl UpdateCollectionPluginV1<'_> {
+    /// Checks the program ids, that the collection is a writable mpl core account,
+    /// that the payer signs and is writable, and that the authority signs
+    pub fn validate(&self) -> ProgramResult {
+        check_mpl_core_program(self.mpl_core)?;
+        check_system_program(self.system_program)?;
+        check_log_wrapper(self.log_wrapper)?;
+
+        check_owned_by_mpl_core(self.collection)?;
+        check_writable(self.collection)?;
+
+        check_signer(self.payer)?;
+        check_writable(self.payer)?;
+
+        if let Some(authority) = self.authority {
+            check_signer(authority)?;
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn invoke(
         &self,
-        instruction_data: &UpdateAssetPluginV1InstructionData,
+        instruction_data: &UpdateCollectionPluginV1InstructionData,
         serialization_buffer: &mut [u8],
     ) -> ProgramResult {
         self.invoke_signed(instruction_data, &[], serialization_buffer)
@@ -51,14 +77,14 @@ impl UpdateCollectionPluginV1<'_> {
 
     pub fn invoke_signed(
         &self,
-        instruction_data: &UpdateAssetPluginV1InstructionData,
+        instruction_data: &UpdateCollectionPluginV1InstructionData,
         signers: &[Signer],
         serialization_buffer: &mut [u8],
     ) -> ProgramResult {
         // account metadata
         let account_metas: &[AccountMeta] = &[
             AccountMeta::writable(self.collection.key()),
-            AccountMeta::writable_signer(&self.payer.key()),
+            AccountMeta::writable_signer(self.payer.key()),
             match self.authority {
                 Some(authority) => AccountMeta::readonly_signer(authority.key()),
                 None => AccountMeta::readonly(self.mpl_core.key()),
@@ -70,12 +96,12 @@ impl UpdateCollectionPluginV1<'_> {
             },
         ];
 
-        let len = instruction_data.serialize_to(serialization_buffer);
+        let len = instruction_data.try_serialize_to(serialization_buffer)?;
         let data = &serialization_buffer[..len];
 
         let instruction = Instruction {
             program_id: &crate::MPL_CORE_ID,
-            accounts: &account_metas,
+            accounts: account_metas,
             data,
         };
 
@@ -1,13 +1,22 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use pinocchio::pubkey::Pubkey;
 use pinocchio_pubkey::pubkey;
 
+pub mod authority;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod data;
+pub mod error;
 pub mod instructions;
+pub mod lifecycle;
 
 /// For internal use, to get the discriminant of the instruction
 #[repr(u8)]
+#[derive(Clone, Copy)]
 pub(crate) enum Instructions {
     CreateAsset = 0,
     CreateCollection = 1,
@@ -48,3 +57,11 @@ impl Instructions {
 }
 
 pub const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+
+/// The SPL Noop program, used by mpl core as the log wrapper
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// The Bubblegum program, the fixed authority of the BubblegumV2 plugin
+pub const BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
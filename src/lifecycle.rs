@@ -0,0 +1,336 @@
+//! Local pre-checks that mirror how mpl core approves or rejects lifecycle events,
+//! so that a CPI that is going to fail can be skipped before paying for it.
+//!
+//! mpl core asks the asset itself, then every plugin of the collection, then every plugin of the asset.
+//! Each of them can approve, reject, force approve or abstain:
+//! - a force approval (only permanent delegates can give one) ends the check immediately, approved,
+//!   even if the asset or the collection rejected before it
+//! - otherwise a rejection anywhere makes the whole check fail, with the first reason found
+//! - otherwise at least one approval is needed
+//!
+//! A Royalties rule set rejects a transfer made from a program it doesn't allow, on the asset or on the collection.
+//! External plugins (oracles, lifecycle hooks) are not evaluated.
+//! FreezeExecute and PermanentFreezeExecute only block the Execute instruction, and AddBlocker only blocks
+//! adding plugins, so they never affect these checks.
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    authority::AuthorityResolver,
+    data::{
+        plugins::{
+            FreezeDelegate, PermanentFreezeDelegate, PluginType, Royalties, UpdateAuthority,
+        },
+        view::{AssetView, CollectionView, PluginRecord},
+        DeserializeSized,
+    },
+    error::MplCoreProgramError,
+};
+
+/// Why mpl core would reject an operation
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// The asset is frozen by its FreezeDelegate
+    FrozenByFreezeDelegate,
+    /// The asset is frozen by its PermanentFreezeDelegate
+    FrozenByPermanentFreezeDelegate,
+    /// The collection is frozen by its PermanentFreezeDelegate
+    CollectionFrozen,
+    /// Neither the asset nor any plugin approved the authority
+    NoApprovals,
+    /// The asset belongs to a collection, but the collection was not given
+    MissingCollection,
+    /// The asset or its collection has the ImmutableMetadata plugin, which rejects every update
+    ImmutableMetadata,
+    /// The rule set of the asset's or the collection's Royalties doesn't allow the program making the transfer
+    ProgramNotAllowed,
+}
+
+impl RejectionReason {
+    /// The error the mpl core CPI would fail with
+    pub fn program_error(&self) -> MplCoreProgramError {
+        match self {
+            Self::FrozenByFreezeDelegate
+            | Self::FrozenByPermanentFreezeDelegate
+            | Self::CollectionFrozen
+            | Self::ImmutableMetadata
+            | Self::ProgramNotAllowed => MplCoreProgramError::InvalidAuthority,
+            Self::NoApprovals => MplCoreProgramError::NoApprovals,
+            Self::MissingCollection => MplCoreProgramError::MissingCollection,
+        }
+    }
+}
+
+/// The outcome of a lifecycle pre-check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LifecycleResult {
+    Approved,
+    Rejected(RejectionReason),
+}
+
+impl LifecycleResult {
+    pub fn is_approved(&self) -> bool {
+        matches!(self, Self::Approved)
+    }
+}
+
+/// What a single asset, collection or plugin says about an operation
+enum ValidationResult {
+    Approved,
+    Rejected(RejectionReason),
+    ForceApproved,
+    Pass,
+}
+
+#[derive(Clone, Copy)]
+enum Lifecycle<'v> {
+    /// With the program making the transfer, for the Royalties rule sets
+    Transfer(&'v Pubkey),
+    Burn,
+    Update,
+}
+
+/// Where a plugin lives, which changes what it rejects with
+#[derive(Clone, Copy, PartialEq)]
+enum PluginSource {
+    Asset,
+    Collection,
+}
+
+/// Folds the result of the asset, the collection plugins or the asset plugins into the running approval
+/// and rejection. Only a force approval decides the outcome early, a rejection is kept until every
+/// group was asked since a permanent delegate further down still overrides it
+fn decide(
+    result: ValidationResult,
+    approved: &mut bool,
+    rejected: &mut Option<RejectionReason>,
+) -> Option<LifecycleResult> {
+    match result {
+        ValidationResult::ForceApproved => return Some(LifecycleResult::Approved),
+        ValidationResult::Rejected(reason) => {
+            rejected.get_or_insert(reason);
+        }
+        ValidationResult::Approved => *approved = true,
+        ValidationResult::Pass => {}
+    }
+    None
+}
+
+struct Evaluator<'v, 'a> {
+    asset: &'v AssetView<'a>,
+    collection: Option<&'v CollectionView<'a>>,
+    authority: &'v Pubkey,
+}
+
+impl Evaluator<'_, '_> {
+    fn resolver(&self) -> AuthorityResolver<'_, '_> {
+        AuthorityResolver::new(self.asset, self.collection, self.authority)
+    }
+
+    fn validate_asset(&self, lifecycle: Lifecycle) -> ValidationResult {
+        let approved = match lifecycle {
+            Lifecycle::Transfer(_) | Lifecycle::Burn => self.resolver().is_owner(),
+            Lifecycle::Update => self.resolver().is_update_authority(),
+        };
+
+        if approved {
+            ValidationResult::Approved
+        } else {
+            ValidationResult::Pass
+        }
+    }
+
+    fn validate_plugin(
+        &self,
+        lifecycle: Lifecycle,
+        source: PluginSource,
+        record: &PluginRecord,
+    ) -> Result<ValidationResult, ProgramError> {
+        // plugins newer than this crate can't be evaluated, mpl core would know what to do with them
+        let Ok(plugin_type) = PluginType::try_from(record.plugin_type) else {
+            return Ok(ValidationResult::Pass);
+        };
+
+        let result = match (lifecycle, plugin_type) {
+            (Lifecycle::Transfer(_) | Lifecycle::Burn, PluginType::FreezeDelegate) => {
+                if FreezeDelegate::deserialize(record.data)?.frozen {
+                    ValidationResult::Rejected(RejectionReason::FrozenByFreezeDelegate)
+                } else {
+                    ValidationResult::Pass
+                }
+            }
+            (Lifecycle::Transfer(_) | Lifecycle::Burn, PluginType::PermanentFreezeDelegate) => {
+                if PermanentFreezeDelegate::deserialize(record.data)?.frozen {
+                    ValidationResult::Rejected(match source {
+                        PluginSource::Asset => RejectionReason::FrozenByPermanentFreezeDelegate,
+                        PluginSource::Collection => RejectionReason::CollectionFrozen,
+                    })
+                } else {
+                    ValidationResult::Pass
+                }
+            }
+            (Lifecycle::Transfer(program_id), PluginType::Royalties) => {
+                if Royalties::from_bytes(record.data)?
+                    .rule_set
+                    .is_program_allowed(program_id)
+                {
+                    ValidationResult::Pass
+                } else {
+                    ValidationResult::Rejected(RejectionReason::ProgramNotAllowed)
+                }
+            }
+            (Lifecycle::Transfer(_), PluginType::TransferDelegate)
+                if source == PluginSource::Asset
+                    && self.resolver().satisfies(&record.authority) =>
+            {
+                ValidationResult::Approved
+            }
+            (Lifecycle::Transfer(_), PluginType::PermanentTransferDelegate)
+                if self.resolver().satisfies(&record.authority) =>
+            {
+                ValidationResult::ForceApproved
+            }
+            (Lifecycle::Burn, PluginType::BurnDelegate)
+                if source == PluginSource::Asset
+                    && self.resolver().satisfies(&record.authority) =>
+            {
+                ValidationResult::Approved
+            }
+            (Lifecycle::Burn, PluginType::PermanentBurnDelegate)
+                if self.resolver().satisfies(&record.authority) =>
+            {
+                ValidationResult::ForceApproved
+            }
+            // the resolver looks at the UpdateDelegate of both the asset and the collection,
+            // so whichever record gets here gives the same answer
+            (Lifecycle::Update, PluginType::UpdateDelegate) => {
+                if self.resolver().is_update_delegate()? {
+                    ValidationResult::Approved
+                } else {
+                    ValidationResult::Pass
+                }
+            }
+            (Lifecycle::Update, PluginType::ImmutableMetadata) => {
+                ValidationResult::Rejected(RejectionReason::ImmutableMetadata)
+            }
+            _ => ValidationResult::Pass,
+        };
+
+        Ok(result)
+    }
+
+    /// Asks every plugin of the asset or of the collection, combining them the way mpl core does
+    fn validate_plugins<'a>(
+        &self,
+        lifecycle: Lifecycle,
+        source: PluginSource,
+        plugins: impl Iterator<Item = Result<PluginRecord<'a>, ProgramError>>,
+    ) -> Result<ValidationResult, ProgramError> {
+        let mut rejected = None;
+        let mut approved = false;
+
+        for record in plugins {
+            match self.validate_plugin(lifecycle, source, &record?)? {
+                ValidationResult::ForceApproved => return Ok(ValidationResult::ForceApproved),
+                ValidationResult::Rejected(reason) => {
+                    rejected.get_or_insert(reason);
+                }
+                ValidationResult::Approved => approved = true,
+                ValidationResult::Pass => {}
+            }
+        }
+
+        Ok(match (rejected, approved) {
+            (Some(reason), _) => ValidationResult::Rejected(reason),
+            (None, true) => ValidationResult::Approved,
+            (None, false) => ValidationResult::Pass,
+        })
+    }
+
+    fn evaluate(&self, lifecycle: Lifecycle) -> Result<LifecycleResult, ProgramError> {
+        if matches!(self.asset.update_authority, UpdateAuthority::Collection(_))
+            && self.collection.is_none()
+        {
+            return Ok(LifecycleResult::Rejected(
+                RejectionReason::MissingCollection,
+            ));
+        }
+
+        let mut approved = false;
+        let mut rejected = None;
+
+        if let Some(result) = decide(self.validate_asset(lifecycle), &mut approved, &mut rejected) {
+            return Ok(result);
+        }
+
+        if let Some(collection) = self.collection {
+            let result =
+                self.validate_plugins(lifecycle, PluginSource::Collection, collection.plugins())?;
+            if let Some(result) = decide(result, &mut approved, &mut rejected) {
+                return Ok(result);
+            }
+        }
+
+        let result = self.validate_plugins(lifecycle, PluginSource::Asset, self.asset.plugins())?;
+        if let Some(result) = decide(result, &mut approved, &mut rejected) {
+            return Ok(result);
+        }
+
+        Ok(match (rejected, approved) {
+            (Some(reason), _) => LifecycleResult::Rejected(reason),
+            (None, true) => LifecycleResult::Approved,
+            (None, false) => LifecycleResult::Rejected(RejectionReason::NoApprovals),
+        })
+    }
+}
+
+impl<'a> AssetView<'a> {
+    /// Whether mpl core would let `authority` transfer this asset from `program_id`, usually the calling program.
+    /// `collection` must be given if the asset belongs to one, since its permanent plugins and royalties apply too.
+    /// It is not checked to actually be the asset's collection
+    pub fn can_transfer(
+        &self,
+        collection: Option<&CollectionView<'a>>,
+        authority: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<LifecycleResult, ProgramError> {
+        Evaluator {
+            asset: self,
+            collection,
+            authority,
+        }
+        .evaluate(Lifecycle::Transfer(program_id))
+    }
+
+    /// Whether mpl core would let `authority` burn this asset.
+    /// `collection` must be given if the asset belongs to one, since its permanent plugins apply too
+    pub fn can_burn(
+        &self,
+        collection: Option<&CollectionView<'a>>,
+        authority: &Pubkey,
+    ) -> Result<LifecycleResult, ProgramError> {
+        Evaluator {
+            asset: self,
+            collection,
+            authority,
+        }
+        .evaluate(Lifecycle::Burn)
+    }
+
+    /// Whether mpl core would let `authority` update this asset (UpdateV1: name, uri or update authority).
+    /// The update authority (through the collection if the asset has one) and UpdateDelegates, including
+    /// their additional delegates, can approve. ImmutableMetadata on the asset or collection rejects
+    pub fn can_update(
+        &self,
+        collection: Option<&CollectionView<'a>>,
+        authority: &Pubkey,
+    ) -> Result<LifecycleResult, ProgramError> {
+        Evaluator {
+            asset: self,
+            collection,
+            authority,
+        }
+        .evaluate(Lifecycle::Update)
+    }
+}
@@ -0,0 +1,54 @@
+//! Checks that mpl core's error codes decode both ways, and that this crate's own errors never
+//! decode as one of them
+
+use mpl_core_pinocchio::error::{MplCoreError, MplCoreProgramError, MPL_CORE_ERROR_BASE};
+use pinocchio::program_error::ProgramError;
+
+/// The last code mpl core 0.12 defines
+const LAST_MPL_CORE_CODE: u32 = 56;
+
+#[test]
+fn mpl_core_codes_round_trip() {
+    for code in 0..=LAST_MPL_CORE_CODE {
+        let error = MplCoreProgramError::try_from(code).unwrap();
+        assert_eq!(error as u32, code);
+        assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+        assert_eq!(
+            MplCoreProgramError::try_from(ProgramError::Custom(code)),
+            Ok(error)
+        );
+        assert!(!error.message().is_empty());
+    }
+
+    assert_eq!(
+        MplCoreProgramError::try_from(LAST_MPL_CORE_CODE + 1),
+        Err(ProgramError::Custom(LAST_MPL_CORE_CODE + 1))
+    );
+    // errors that aren't custom come back unchanged
+    assert_eq!(
+        MplCoreProgramError::try_from(ProgramError::AccountBorrowFailed),
+        Err(ProgramError::AccountBorrowFailed)
+    );
+}
+
+#[test]
+fn crate_errors_do_not_decode_as_mpl_core_errors() {
+    assert_eq!(
+        ProgramError::from(MplCoreError::InvalidMplCoreProgram),
+        ProgramError::Custom(MPL_CORE_ERROR_BASE)
+    );
+
+    for error in [
+        MplCoreError::InvalidMplCoreProgram,
+        MplCoreError::MissingSigner,
+        // was Custom(17) before the base, which is mpl core's AlreadyCompressed
+        MplCoreError::BufferTooSmall,
+        MplCoreError::InvalidUtf8,
+    ] {
+        let program_error = ProgramError::from(error);
+        assert_eq!(
+            MplCoreProgramError::try_from(program_error),
+            Err(program_error)
+        );
+    }
+}
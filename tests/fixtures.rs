@@ -0,0 +1,526 @@
+//! Runs the readers against account images written with the official mpl-core SDK types,
+//! see `tests/fixtures/README.md`
+
+use mpl_core_pinocchio::{
+    authority::AuthorityResolver,
+    data::{
+        asset::BaseAssetV1,
+        external_plugins::{AppData, ExternalPluginAdapterSchema, ExternalPluginAdapterType},
+        plugins::{
+            read_royalties_asset, read_royalties_collection, Edition, PluginAuthority, PluginType,
+            Royalties, RoyaltiesInfo, RuleSet, UpdateAuthority,
+        },
+        view::{AssetView, CollectionView},
+        Skip,
+    },
+    error::MplCoreError,
+    lifecycle::{LifecycleResult, RejectionReason},
+};
+use pinocchio::program_error::ProgramError;
+
+const ALICE: [u8; 32] = [1; 32];
+const BOB: [u8; 32] = [2; 32];
+const PROGRAM: [u8; 32] = [3; 32];
+const COLLECTION: [u8; 32] = [4; 32];
+/// The program the transfers are made from
+const CALLER: [u8; 32] = [5; 32];
+
+const ASSET_NO_PLUGINS: &[u8] = include_bytes!("fixtures/asset_no_plugins.bin");
+const ASSET_ROYALTIES_ATTRIBUTES: &[u8] = include_bytes!("fixtures/asset_royalties_attributes.bin");
+const ASSET_APP_DATA: &[u8] = include_bytes!("fixtures/asset_app_data.bin");
+const ASSET_EDITION_SIGNATURES: &[u8] = include_bytes!("fixtures/asset_edition_signatures.bin");
+const COLLECTION_NO_PLUGINS: &[u8] = include_bytes!("fixtures/collection_no_plugins.bin");
+const COLLECTION_MASTER_EDITION: &[u8] = include_bytes!("fixtures/collection_master_edition.bin");
+const HASHED_ASSET: &[u8] = include_bytes!("fixtures/hashed_asset.bin");
+
+fn error(error: MplCoreError) -> ProgramError {
+    error.into()
+}
+
+#[test]
+fn asset_without_plugins() {
+    let asset = AssetView::new(ASSET_NO_PLUGINS).unwrap();
+    assert_eq!(asset.owner, &ALICE);
+    assert_eq!(asset.update_authority, UpdateAuthority::Address(BOB));
+    assert_eq!(asset.name, b"plain");
+    assert_eq!(asset.uri, b"https://example.com/plain.json");
+    assert_eq!(asset.seq, None);
+    assert!(asset.registry.is_none());
+    assert_eq!(asset.plugins().count(), 0);
+    assert!(asset.attributes().unwrap().is_none());
+
+    let royalties = read_royalties_asset(ASSET_NO_PLUGINS).unwrap();
+    assert!(royalties.is_empty());
+    assert!(royalties.creators.is_empty());
+
+    assert_eq!(
+        BaseAssetV1::get_collection(ASSET_NO_PLUGINS).unwrap_err(),
+        error(MplCoreError::UpdateAuthorityNotCollection)
+    );
+
+    assert!(asset
+        .can_transfer(None, &ALICE, &CALLER)
+        .unwrap()
+        .is_approved());
+    assert!(asset.can_update(None, &BOB).unwrap().is_approved());
+    assert_eq!(
+        asset.can_burn(None, &BOB).unwrap(),
+        LifecycleResult::Rejected(RejectionReason::NoApprovals)
+    );
+}
+
+#[test]
+fn asset_with_royalties_and_attributes() {
+    assert_eq!(
+        BaseAssetV1::get_collection(ASSET_ROYALTIES_ATTRIBUTES).unwrap(),
+        &COLLECTION
+    );
+
+    let royalties = read_royalties_asset(ASSET_ROYALTIES_ATTRIBUTES).unwrap();
+    assert_eq!(royalties.basis_points, 500);
+    let creators: Vec<_> = royalties
+        .creators
+        .iter()
+        .map(|creator| (creator.address, creator.percentage))
+        .collect();
+    assert_eq!(creators, [(ALICE, 70), (BOB, 30)]);
+
+    let asset = AssetView::new(ASSET_ROYALTIES_ATTRIBUTES).unwrap();
+    assert_eq!(
+        asset.update_authority,
+        UpdateAuthority::Collection(COLLECTION)
+    );
+    assert_eq!(asset.registry.unwrap().len(), 2);
+
+    let collection = CollectionView::new(COLLECTION_MASTER_EDITION).unwrap();
+    // the asset's own royalties win over the collection's
+    let royalties = RoyaltiesInfo::resolve(&asset, Some(&collection)).unwrap();
+    assert_eq!(royalties.basis_points, 500);
+
+    let record = asset.find_plugin(PluginType::Royalties).unwrap().unwrap();
+    let royalties = Royalties::from_bytes(record.data).unwrap();
+    assert!(
+        matches!(royalties.rule_set, RuleSet::ProgramDenyList(programs) if programs == [PROGRAM])
+    );
+    assert!(!royalties.rule_set.is_program_allowed(&PROGRAM));
+    assert!(royalties.rule_set.is_program_allowed(&BOB));
+
+    // the deny list is checked against the program making the transfer
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection), &ALICE, &PROGRAM)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::ProgramNotAllowed)
+    );
+    assert!(asset
+        .can_transfer(Some(&collection), &ALICE, &CALLER)
+        .unwrap()
+        .is_approved());
+
+    let payouts: Vec<_> = RoyaltiesInfo::resolve(&asset, Some(&collection))
+        .unwrap()
+        .compute_payouts(1_000)
+        .unwrap()
+        .collect();
+    assert_eq!(payouts, [(ALICE, 35), (BOB, 15)]);
+
+    let attributes = asset.attributes().unwrap().unwrap();
+    assert_eq!(attributes.len(), 2);
+    assert_eq!(attributes.get(b"class"), Some(&b"mage"[..]));
+    assert_eq!(attributes.get_u64(b"level").unwrap(), Some(7));
+    assert_eq!(attributes.get(b"missing"), None);
+}
+
+#[test]
+fn asset_with_external_app_data() {
+    let asset = AssetView::new(ASSET_APP_DATA).unwrap();
+
+    // only the internal plugins are in the registry the view iterates
+    let plugins: Vec<_> = asset
+        .plugins()
+        .map(|record| record.unwrap().plugin_type)
+        .collect();
+    assert_eq!(plugins, [PluginType::FreezeDelegate as u8]);
+
+    // the AppData adapter is in the external registry after them
+    let external: Vec<_> = asset
+        .external_plugins()
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(external.len(), 1);
+    assert_eq!(
+        external[0].plugin_type,
+        ExternalPluginAdapterType::AppData as u8
+    );
+    assert_eq!(external[0].authority, PluginAuthority::UpdateAuthority);
+    assert!(external[0].lifecycle_checks.is_none());
+    assert_eq!(external[0].data, Some(&br#"{"score":10}"#[..]));
+    assert_eq!(
+        AppData::from_bytes(external[0].adapter).unwrap().0,
+        AppData {
+            data_authority: PluginAuthority::Address(PROGRAM),
+            schema: ExternalPluginAdapterSchema::Json,
+        }
+    );
+
+    let royalties = read_royalties_asset(ASSET_APP_DATA).unwrap();
+    assert!(royalties.is_empty());
+
+    assert_eq!(
+        asset.can_transfer(None, &ALICE, &CALLER).unwrap(),
+        LifecycleResult::Rejected(RejectionReason::FrozenByFreezeDelegate)
+    );
+    assert_eq!(
+        asset.can_burn(None, &ALICE).unwrap(),
+        LifecycleResult::Rejected(RejectionReason::FrozenByFreezeDelegate)
+    );
+}
+
+#[test]
+fn asset_with_edition_and_signatures() {
+    let asset = AssetView::new(ASSET_EDITION_SIGNATURES).unwrap();
+    let collection = CollectionView::new(COLLECTION_MASTER_EDITION).unwrap();
+
+    assert_eq!(asset.edition().unwrap().unwrap().number, 3);
+
+    let verified_creators = asset.verified_creators().unwrap().unwrap();
+    let signatures: Vec<_> = verified_creators
+        .iter()
+        .map(|signature| (signature.address, signature.verified))
+        .collect();
+    assert_eq!(signatures, [(ALICE, true), (BOB, false)]);
+    assert_eq!(
+        verified_creators.verify(&ALICE).err(),
+        Some(error(MplCoreError::AlreadyVerified))
+    );
+    assert!(verified_creators.verify(&BOB).is_ok());
+
+    let autograph = asset.autograph().unwrap().unwrap();
+    assert_eq!(autograph.get(&BOB).unwrap().message, b"gm");
+    assert_eq!(
+        autograph.add(&BOB, b"again").err(),
+        Some(error(MplCoreError::DuplicateSignature))
+    );
+
+    // the collection's update authority is the asset's update authority
+    let resolver = AuthorityResolver::new(&asset, Some(&collection), &BOB);
+    assert!(resolver.is_update_authority());
+    let resolver = AuthorityResolver::new(&asset, Some(&collection), &PROGRAM);
+    assert!(!resolver.is_update_authority());
+    assert!(resolver.is_update_delegate().unwrap());
+
+    assert!(asset
+        .can_update(Some(&collection), &PROGRAM)
+        .unwrap()
+        .is_approved());
+    assert_eq!(
+        asset.can_update(None, &BOB).unwrap(),
+        LifecycleResult::Rejected(RejectionReason::MissingCollection)
+    );
+    // the FreezeDelegate is held by PROGRAM but not frozen, and only the owner can transfer
+    assert!(asset
+        .can_transfer(Some(&collection), &ALICE, &CALLER)
+        .unwrap()
+        .is_approved());
+}
+
+#[test]
+fn collection_without_plugins() {
+    let collection = CollectionView::new(COLLECTION_NO_PLUGINS).unwrap();
+    assert_eq!(collection.update_authority, &BOB);
+    assert_eq!(collection.name, b"empty");
+    assert_eq!(collection.num_minted, 0);
+    assert!(collection.registry.is_none());
+    assert!(collection.master_edition().unwrap().is_none());
+
+    assert!(read_royalties_collection(COLLECTION_NO_PLUGINS)
+        .unwrap()
+        .is_empty());
+
+    assert_eq!(
+        Edition::next(&collection).err(),
+        Some(error(MplCoreError::MissingMasterEdition))
+    );
+}
+
+#[test]
+fn collection_with_master_edition() {
+    let collection = CollectionView::new(COLLECTION_MASTER_EDITION).unwrap();
+    assert_eq!(collection.update_authority, &BOB);
+    assert_eq!(collection.num_minted, 3);
+    assert_eq!(collection.current_size, 3);
+
+    let master_edition = collection.master_edition().unwrap().unwrap();
+    assert_eq!(master_edition.max_supply, Some(10));
+    assert_eq!(master_edition.name, Some(&b"Master"[..]));
+    assert_eq!(master_edition.uri, None);
+
+    assert_eq!(Edition::next(&collection).unwrap().number, 4);
+
+    let royalties = read_royalties_collection(COLLECTION_MASTER_EDITION).unwrap();
+    assert_eq!(royalties.basis_points, 250);
+    assert_eq!(royalties.creators.len(), 1);
+    assert_eq!(royalties.creators[0].address, BOB);
+
+    // an asset without royalties falls back to the collection's
+    let asset = AssetView::new(ASSET_EDITION_SIGNATURES).unwrap();
+    let royalties = RoyaltiesInfo::resolve(&asset, Some(&collection)).unwrap();
+    assert_eq!(royalties.basis_points, 250);
+    assert_eq!(royalties.creators.len(), 1);
+
+    let record = collection
+        .find_plugin(PluginType::Royalties)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        Royalties::from_bytes(record.data).unwrap().rule_set,
+        RuleSet::None
+    ));
+}
+
+#[test]
+fn hashed_asset_is_rejected() {
+    assert_eq!(
+        AssetView::new(HASHED_ASSET).err(),
+        Some(error(MplCoreError::InvalidKey))
+    );
+    assert_eq!(
+        read_royalties_asset(HASHED_ASSET).err(),
+        Some(error(MplCoreError::InvalidKey))
+    );
+    assert_eq!(
+        BaseAssetV1::get_collection(HASHED_ASSET).err(),
+        Some(error(MplCoreError::InvalidKey))
+    );
+    assert_eq!(
+        CollectionView::new(HASHED_ASSET).err(),
+        Some(error(MplCoreError::InvalidKey))
+    );
+}
+
+#[test]
+fn truncated_and_corrupted_accounts_are_errors() {
+    let fixtures = [
+        ASSET_NO_PLUGINS,
+        ASSET_ROYALTIES_ATTRIBUTES,
+        ASSET_APP_DATA,
+        ASSET_EDITION_SIGNATURES,
+        COLLECTION_NO_PLUGINS,
+        COLLECTION_MASTER_EDITION,
+        HASHED_ASSET,
+    ];
+
+    // every prefix of a valid account, none of which may panic
+    for fixture in fixtures {
+        for len in 0..fixture.len() {
+            let bytes = &fixture[..len];
+            let _ = read_royalties_asset(bytes);
+            let _ = read_royalties_collection(bytes);
+            let _ = BaseAssetV1::get_collection(bytes);
+            let _ = AssetView::new(bytes).map(|asset| asset.plugins().count());
+            let _ = CollectionView::new(bytes).map(|collection| collection.plugins().count());
+        }
+    }
+    assert!(read_royalties_asset(&[]).is_err());
+    assert!(BaseAssetV1::get_collection(&ASSET_ROYALTIES_ATTRIBUTES[..34]).is_err());
+
+    // a plugin_registry_offset pointing past the end of the account
+    let base_len = BaseAssetV1::skip_bytes(ASSET_ROYALTIES_ATTRIBUTES).unwrap();
+    for registry_offset in [
+        ASSET_ROYALTIES_ATTRIBUTES.len() as u64,
+        u32::MAX as u64,
+        u64::MAX,
+    ] {
+        let mut bytes = ASSET_ROYALTIES_ATTRIBUTES.to_vec();
+        bytes[base_len + 1..base_len + 9].copy_from_slice(&registry_offset.to_le_bytes());
+        assert!(read_royalties_asset(&bytes).is_err());
+        assert!(AssetView::new(&bytes).is_err());
+    }
+
+    // a creator count larger than the account
+    let royalties = read_royalties_collection(COLLECTION_MASTER_EDITION).unwrap();
+    let creators_offset =
+        royalties.creators.as_ptr() as usize - COLLECTION_MASTER_EDITION.as_ptr() as usize;
+    let mut bytes = COLLECTION_MASTER_EDITION.to_vec();
+    bytes[creators_offset - 4..creators_offset].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(
+        read_royalties_collection(&bytes).err(),
+        Some(error(MplCoreError::UnexpectedEndOfData))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_assets_round_trip() {
+    use mpl_core_pinocchio::data::{
+        owned::{
+            OwnedAsset, OwnedAttribute, OwnedExternalPluginAdapter,
+            OwnedExternalPluginAdapterWithData, OwnedPlugin,
+        },
+        Serialize,
+    };
+
+    for fixture in [
+        ASSET_NO_PLUGINS,
+        ASSET_ROYALTIES_ATTRIBUTES,
+        ASSET_EDITION_SIGNATURES,
+    ] {
+        let asset = OwnedAsset::try_from(&AssetView::new(fixture).unwrap()).unwrap();
+        assert_eq!(asset.to_bytes().unwrap(), fixture);
+        asset
+            .with_asset_info(|info| assert_eq!(info.serialized_size(), fixture.len()))
+            .unwrap();
+    }
+
+    let mut asset =
+        OwnedAsset::try_from(&AssetView::new(ASSET_ROYALTIES_ATTRIBUTES).unwrap()).unwrap();
+    assert_eq!(
+        asset.update_authority,
+        UpdateAuthority::Collection(COLLECTION)
+    );
+    let OwnedPlugin::Attributes(attributes) = &mut asset.plugins[1].plugin else {
+        panic!("expected Attributes, got {:?}", asset.plugins[1].plugin);
+    };
+    attributes.attribute_list.push(OwnedAttribute {
+        key: "rarity".into(),
+        value: "epic".into(),
+    });
+    assert_eq!(asset.plugins[1].authority, PluginAuthority::UpdateAuthority);
+
+    // the edited asset reads back the same through the views
+    let bytes = asset.to_bytes().unwrap();
+    let view = AssetView::new(&bytes).unwrap();
+    assert_eq!(
+        view.attributes().unwrap().unwrap().get(b"rarity"),
+        Some(&b"epic"[..])
+    );
+    assert_eq!(OwnedAsset::try_from(&view).unwrap(), asset);
+
+    // external plugin adapters are kept with their data
+    let asset = OwnedAsset::try_from(&AssetView::new(ASSET_APP_DATA).unwrap()).unwrap();
+    assert_eq!(asset.plugins.len(), 1);
+    assert_eq!(
+        asset.external_plugins,
+        [OwnedExternalPluginAdapterWithData {
+            adapter: OwnedExternalPluginAdapter::AppData(AppData {
+                data_authority: PluginAuthority::Address(PROGRAM),
+                schema: ExternalPluginAdapterSchema::Json,
+            }),
+            authority: PluginAuthority::UpdateAuthority,
+            lifecycle_checks: None,
+            data: Some(br#"{"score":10}"#.to_vec()),
+        }]
+    );
+    assert_eq!(asset.to_bytes().unwrap(), ASSET_APP_DATA);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_assets_skip_unknown_plugins() {
+    use mpl_core_pinocchio::data::owned::OwnedAsset;
+
+    // a const can be inlined at each use, the offsets are taken from a single copy
+    let fixture = ASSET_APP_DATA;
+    let asset = AssetView::new(fixture).unwrap();
+    let offset = |data: &[u8]| data.as_ptr() as usize - fixture.as_ptr() as usize - 1;
+    let plugin = offset(asset.plugins().next().unwrap().unwrap().data);
+    let adapter = offset(
+        asset
+            .external_plugins()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .adapter,
+    );
+
+    // plugin type, Owner, offset / adapter type, UpdateAuthority, no lifecycle checks, offset
+    let mut plugin_record = vec![PluginType::FreezeDelegate as u8, 1];
+    plugin_record.extend((plugin as u64).to_le_bytes());
+    let mut adapter_record = vec![ExternalPluginAdapterType::AppData as u8, 2, 0];
+    adapter_record.extend((adapter as u64).to_le_bytes());
+    let find = |record: &[u8]| {
+        fixture
+            .windows(record.len())
+            .position(|window| window == record)
+            .unwrap()
+    };
+
+    // a plugin and an adapter newer than this crate, the views walk over them without matching them
+    let mut bytes = fixture.to_vec();
+    for at in [find(&plugin_record), plugin, find(&adapter_record), adapter] {
+        bytes[at] = 200;
+    }
+    let view = AssetView::new(&bytes).unwrap();
+    assert_eq!(view.plugins().count(), 1);
+    assert!(view
+        .find_plugin(PluginType::FreezeDelegate)
+        .unwrap()
+        .is_none());
+    assert_eq!(view.external_plugins().unwrap().count(), 1);
+
+    let asset = OwnedAsset::try_from(&view).unwrap();
+    assert!(asset.plugins.is_empty());
+    assert!(asset.external_plugins.is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_collections_round_trip() {
+    use mpl_core_pinocchio::data::{
+        owned::{
+            OwnedCollection, OwnedGroups, OwnedMasterEdition, OwnedPlugin, OwnedPluginAuthorityPair,
+        },
+        plugins::PluginAuthority,
+    };
+
+    for fixture in [COLLECTION_NO_PLUGINS, COLLECTION_MASTER_EDITION] {
+        let collection = OwnedCollection::try_from(&CollectionView::new(fixture).unwrap()).unwrap();
+        assert_eq!(collection.to_bytes().unwrap(), fixture);
+    }
+
+    let collection =
+        OwnedCollection::try_from(&CollectionView::new(COLLECTION_MASTER_EDITION).unwrap())
+            .unwrap();
+    assert_eq!(collection.update_authority, BOB);
+    assert_eq!(collection.num_minted, 3);
+    assert_eq!(
+        collection.plugins[0].plugin,
+        OwnedPlugin::MasterEdition(OwnedMasterEdition {
+            max_supply: Some(10),
+            name: Some("Master".into()),
+            uri: None,
+        })
+    );
+
+    // Groups, the last plugin type of mpl core, reads back through the views like the others
+    let mut collection = collection;
+    collection.plugins.push(OwnedPluginAuthorityPair {
+        plugin: OwnedPlugin::Groups(OwnedGroups {
+            groups: vec![ALICE, PROGRAM],
+        }),
+        authority: PluginAuthority::UpdateAuthority,
+    });
+    let bytes = collection.to_bytes().unwrap();
+    let view = CollectionView::new(&bytes).unwrap();
+    assert!(view.find_plugin(PluginType::Groups).unwrap().is_some());
+    assert_eq!(OwnedCollection::try_from(&view).unwrap(), collection);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_names_must_be_utf8() {
+    use mpl_core_pinocchio::data::owned::OwnedAsset;
+
+    // the name of asset_no_plugins starts right after the update authority
+    let mut bytes = ASSET_NO_PLUGINS.to_vec();
+    let name = 1 + 32 + 1 + 32 + 4;
+    assert_eq!(&bytes[name..name + 5], b"plain");
+    bytes[name] = 0xff;
+
+    assert_eq!(
+        OwnedAsset::try_from(&AssetView::new(&bytes).unwrap()).err(),
+        Some(error(MplCoreError::InvalidUtf8))
+    );
+}
@@ -0,0 +1,289 @@
+use std::{fs, path::Path};
+
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1, HashedAssetV1, PluginHeaderV1, PluginRegistryV1},
+    types::{
+        AppData, Attribute, Attributes, Autograph, AutographSignature, Creator, Edition,
+        ExternalPluginAdapter, ExternalPluginAdapterSchema, ExternalPluginAdapterType,
+        ExternalRegistryRecord, FreezeDelegate, Key, MasterEdition, PermanentFreezeDelegate,
+        Plugin, PluginAuthority, PluginType, RegistryRecord, Royalties, RuleSet, UpdateAuthority,
+        UpdateDelegate, VerifiedCreators, VerifiedCreatorsSignature,
+    },
+    Asset, Collection,
+};
+use solana_program::pubkey::Pubkey;
+
+const ALICE: Pubkey = Pubkey::new_from_array([1; 32]);
+const BOB: Pubkey = Pubkey::new_from_array([2; 32]);
+const PROGRAM: Pubkey = Pubkey::new_from_array([3; 32]);
+const COLLECTION: Pubkey = Pubkey::new_from_array([4; 32]);
+
+/// Lays the account out the way mpl core does: base, PluginHeaderV1, plugins,
+/// external adapters each followed by their data, PluginRegistryV1 last
+fn account(
+    base: Vec<u8>,
+    plugins: Vec<(Plugin, PluginAuthority)>,
+    external: Vec<(
+        ExternalPluginAdapter,
+        ExternalPluginAdapterType,
+        PluginAuthority,
+        Vec<u8>,
+    )>,
+) -> Vec<u8> {
+    let mut data = base;
+    if plugins.is_empty() && external.is_empty() {
+        return data;
+    }
+
+    let header_offset = data.len();
+    data.extend([0; PluginHeaderV1::LEN]);
+
+    let mut registry = Vec::new();
+    for (plugin, authority) in plugins {
+        registry.push(RegistryRecord {
+            plugin_type: PluginType::from(&plugin),
+            authority,
+            offset: data.len() as u64,
+        });
+        data.extend(borsh::to_vec(&plugin).unwrap());
+    }
+
+    let mut external_registry = Vec::new();
+    for (adapter, plugin_type, authority, adapter_data) in external {
+        let offset = data.len() as u64;
+        data.extend(borsh::to_vec(&adapter).unwrap());
+        external_registry.push(ExternalRegistryRecord {
+            plugin_type,
+            authority,
+            lifecycle_checks: None,
+            offset,
+            data_offset: Some(data.len() as u64),
+            data_len: Some(adapter_data.len() as u64),
+        });
+        data.extend(adapter_data);
+    }
+
+    let registry_offset = data.len() as u64;
+    data.extend(
+        borsh::to_vec(&PluginRegistryV1 {
+            key: Key::PluginRegistryV1,
+            registry,
+            external_registry,
+        })
+        .unwrap(),
+    );
+
+    let header = PluginHeaderV1 {
+        key: Key::PluginHeaderV1,
+        plugin_registry_offset: registry_offset,
+    };
+    data[header_offset..header_offset + PluginHeaderV1::LEN]
+        .copy_from_slice(&borsh::to_vec(&header).unwrap());
+
+    data
+}
+
+fn asset(
+    update_authority: UpdateAuthority,
+    name: &str,
+    plugins: Vec<(Plugin, PluginAuthority)>,
+    external: Vec<(
+        ExternalPluginAdapter,
+        ExternalPluginAdapterType,
+        PluginAuthority,
+        Vec<u8>,
+    )>,
+) -> Vec<u8> {
+    let base = BaseAssetV1 {
+        key: Key::AssetV1,
+        owner: ALICE,
+        update_authority,
+        name: name.into(),
+        uri: format!("https://example.com/{name}.json"),
+        seq: None,
+    };
+    let data = account(borsh::to_vec(&base).unwrap(), plugins, external);
+
+    Asset::from_bytes(&data).expect("the sdk decodes the asset");
+    data
+}
+
+fn collection(name: &str, num_minted: u32, plugins: Vec<(Plugin, PluginAuthority)>) -> Vec<u8> {
+    let base = BaseCollectionV1 {
+        key: Key::CollectionV1,
+        update_authority: BOB,
+        name: name.into(),
+        uri: format!("https://example.com/{name}.json"),
+        num_minted,
+        current_size: num_minted,
+    };
+    let data = account(borsh::to_vec(&base).unwrap(), plugins, vec![]);
+
+    Collection::from_bytes(&data).expect("the sdk decodes the collection");
+    data
+}
+
+fn main() {
+    let fixtures = [
+        (
+            "asset_no_plugins",
+            asset(UpdateAuthority::Address(BOB), "plain", vec![], vec![]),
+        ),
+        (
+            "asset_royalties_attributes",
+            asset(
+                UpdateAuthority::Collection(COLLECTION),
+                "royalties",
+                vec![
+                    (
+                        Plugin::Royalties(Royalties {
+                            basis_points: 500,
+                            creators: vec![
+                                Creator {
+                                    address: ALICE,
+                                    percentage: 70,
+                                },
+                                Creator {
+                                    address: BOB,
+                                    percentage: 30,
+                                },
+                            ],
+                            rule_set: RuleSet::ProgramDenyList(vec![PROGRAM]),
+                        }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                    (
+                        Plugin::Attributes(Attributes {
+                            attribute_list: vec![
+                                Attribute {
+                                    key: "level".into(),
+                                    value: "7".into(),
+                                },
+                                Attribute {
+                                    key: "class".into(),
+                                    value: "mage".into(),
+                                },
+                            ],
+                        }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                ],
+                vec![],
+            ),
+        ),
+        (
+            "asset_app_data",
+            asset(
+                UpdateAuthority::Address(BOB),
+                "app_data",
+                vec![(
+                    Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+                    PluginAuthority::Owner,
+                )],
+                vec![(
+                    ExternalPluginAdapter::AppData(AppData {
+                        data_authority: PluginAuthority::Address { address: PROGRAM },
+                        schema: ExternalPluginAdapterSchema::Json,
+                    }),
+                    ExternalPluginAdapterType::AppData,
+                    PluginAuthority::UpdateAuthority,
+                    br#"{"score":10}"#.to_vec(),
+                )],
+            ),
+        ),
+        (
+            "asset_edition_signatures",
+            asset(
+                UpdateAuthority::Collection(COLLECTION),
+                "edition",
+                vec![
+                    (
+                        Plugin::Edition(Edition { number: 3 }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                    (
+                        Plugin::VerifiedCreators(VerifiedCreators {
+                            signatures: vec![
+                                VerifiedCreatorsSignature {
+                                    address: ALICE,
+                                    verified: true,
+                                },
+                                VerifiedCreatorsSignature {
+                                    address: BOB,
+                                    verified: false,
+                                },
+                            ],
+                        }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                    (
+                        Plugin::Autograph(Autograph {
+                            signatures: vec![AutographSignature {
+                                address: BOB,
+                                message: "gm".into(),
+                            }],
+                        }),
+                        PluginAuthority::Owner,
+                    ),
+                    (
+                        Plugin::UpdateDelegate(UpdateDelegate {
+                            additional_delegates: vec![PROGRAM],
+                        }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                    (
+                        Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                        PluginAuthority::Address { address: PROGRAM },
+                    ),
+                ],
+                vec![],
+            ),
+        ),
+        ("collection_no_plugins", collection("empty", 0, vec![])),
+        (
+            "collection_master_edition",
+            collection(
+                "editions",
+                3,
+                vec![
+                    (
+                        Plugin::MasterEdition(MasterEdition {
+                            max_supply: Some(10),
+                            name: Some("Master".into()),
+                            uri: None,
+                        }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                    (
+                        Plugin::Royalties(Royalties {
+                            basis_points: 250,
+                            creators: vec![Creator {
+                                address: BOB,
+                                percentage: 100,
+                            }],
+                            rule_set: RuleSet::None,
+                        }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                    (
+                        Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false }),
+                        PluginAuthority::UpdateAuthority,
+                    ),
+                ],
+            ),
+        ),
+        (
+            "hashed_asset",
+            borsh::to_vec(&HashedAssetV1 {
+                key: Key::HashedAssetV1,
+                hash: [7; 32],
+            })
+            .unwrap(),
+        ),
+    ];
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for (name, data) in fixtures {
+        fs::write(dir.join(format!("{name}.bin")), data).unwrap();
+    }
+}
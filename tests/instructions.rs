@@ -0,0 +1,719 @@
+//! Checks what the CPI structs check before invoking, on accounts laid out like the runtime passes them
+
+use core::mem::MaybeUninit;
+
+use mpl_core_pinocchio::{
+    data::{
+        create_asset::DataState,
+        plugins::{Plugin, PluginAuthorityPair},
+    },
+    error::MplCoreError,
+    instructions::{
+        burn_asset::BurnV1, burn_collection::BurnCollectionV1, create_asset::CreateAssetV1,
+        create_collection::CreateCollectionV1, transfer::TransferV1,
+        update_asset_plugin::UpdateAssetPluginV1,
+        update_collection_plugin::UpdateCollectionPluginV1,
+    },
+    MPL_CORE_ID, SPL_NOOP_ID, SYSTEM_PROGRAM_ID,
+};
+use pinocchio::{account_info::AccountInfo, entrypoint::deserialize, pubkey::Pubkey};
+
+const ASSET: Pubkey = [10; 32];
+const COLLECTION: Pubkey = [11; 32];
+const AUTHORITY: Pubkey = [12; 32];
+const PAYER: Pubkey = [13; 32];
+const OWNER: Pubkey = [14; 32];
+const WRONG_PROGRAM: Pubkey = [15; 32];
+
+/// One account without data in the runtime's input format, followed by empty instruction data and
+/// the program id. The buffer is leaked since the `AccountInfo` points into it
+fn account(key: Pubkey, owner: Pubkey, is_signer: bool, is_writable: bool) -> AccountInfo {
+    // accounts count, header, realloc padding, rent epoch, instruction data length, program id
+    let mut input = vec![0u64; (8 + 88 + 10_240 + 8 + 8 + 32) / 8];
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut input);
+    bytes[..8].copy_from_slice(&1u64.to_le_bytes());
+    bytes[8] = u8::MAX; // not a duplicate
+    bytes[9] = is_signer.into();
+    bytes[10] = is_writable.into();
+    bytes[16..48].copy_from_slice(&key);
+    bytes[48..80].copy_from_slice(&owner);
+    bytes[80..88].copy_from_slice(&1_000_000_000u64.to_le_bytes());
+
+    let input = input.leak();
+    let mut accounts = [MaybeUninit::<AccountInfo>::uninit(); 1];
+    // SAFETY: the input holds one account in the runtime's format and lives forever
+    let (_, count, _) = unsafe { deserialize(input.as_mut_ptr().cast(), &mut accounts) };
+    assert_eq!(count, 1);
+    // SAFETY: `deserialize` initialized the one account
+    unsafe { accounts[0].assume_init() }
+}
+
+/// The accounts every instruction is built from, flagged the way mpl core expects them
+struct Accounts {
+    asset: AccountInfo,
+    collection: AccountInfo,
+    authority: AccountInfo,
+    payer: AccountInfo,
+    owner: AccountInfo,
+    system_program: AccountInfo,
+    log_wrapper: AccountInfo,
+    mpl_core: AccountInfo,
+    /// Fails `check_mpl_core_program`, `check_system_program` and `check_log_wrapper`
+    wrong_program: AccountInfo,
+    /// An asset owned by the system program, which fails `check_owned_by_mpl_core`
+    not_mpl_core: AccountInfo,
+    /// An asset that doesn't sign and isn't writable
+    readonly: AccountInfo,
+    /// A payer that doesn't sign
+    unsigned_payer: AccountInfo,
+    /// A payer that isn't writable
+    readonly_payer: AccountInfo,
+}
+
+impl Accounts {
+    fn new() -> Self {
+        Self {
+            asset: account(ASSET, MPL_CORE_ID, true, true),
+            collection: account(COLLECTION, MPL_CORE_ID, true, true),
+            authority: account(AUTHORITY, SYSTEM_PROGRAM_ID, true, true),
+            payer: account(PAYER, SYSTEM_PROGRAM_ID, true, true),
+            owner: account(OWNER, SYSTEM_PROGRAM_ID, false, false),
+            system_program: account(SYSTEM_PROGRAM_ID, [0; 32], false, false),
+            log_wrapper: account(SPL_NOOP_ID, [0; 32], false, false),
+            mpl_core: account(MPL_CORE_ID, [0; 32], false, false),
+            wrong_program: account(WRONG_PROGRAM, [0; 32], false, false),
+            not_mpl_core: account(ASSET, SYSTEM_PROGRAM_ID, true, true),
+            readonly: account(ASSET, MPL_CORE_ID, false, false),
+            unsigned_payer: account(PAYER, SYSTEM_PROGRAM_ID, false, true),
+            readonly_payer: account(PAYER, SYSTEM_PROGRAM_ID, true, false),
+        }
+    }
+
+    fn create_asset(&self) -> CreateAssetV1<'_> {
+        CreateAssetV1 {
+            asset: &self.asset,
+            collection: Some(&self.collection),
+            authority: Some(&self.authority),
+            payer: &self.payer,
+            owner: Some(&self.owner),
+            update_authority: None,
+            system_program: &self.system_program,
+            log_wrapper: Some(&self.log_wrapper),
+            mpl_core: &self.mpl_core,
+        }
+    }
+
+    fn create_collection(&self) -> CreateCollectionV1<'_> {
+        CreateCollectionV1 {
+            collection: &self.collection,
+            update_authority: None,
+            payer: &self.payer,
+            system_program: &self.system_program,
+            mpl_core: &self.mpl_core,
+        }
+    }
+
+    fn transfer(&self) -> TransferV1<'_> {
+        TransferV1 {
+            asset: &self.asset,
+            collection: Some(&self.collection),
+            payer: &self.payer,
+            authority: Some(&self.authority),
+            new_owner: &self.owner,
+            system_program: &self.system_program,
+            log_wrapper: Some(&self.log_wrapper),
+            mpl_core: &self.mpl_core,
+        }
+    }
+
+    fn burn(&self) -> BurnV1<'_> {
+        BurnV1 {
+            asset: &self.asset,
+            collection: Some(&self.collection),
+            payer: &self.payer,
+            authority: Some(&self.authority),
+            system_program: &self.system_program,
+            log_wrapper: Some(&self.log_wrapper),
+            mpl_core: &self.mpl_core,
+        }
+    }
+
+    fn burn_collection(&self) -> BurnCollectionV1<'_> {
+        BurnCollectionV1 {
+            collection: &self.collection,
+            payer: &self.payer,
+            authority: Some(&self.authority),
+            log_wrapper: Some(&self.log_wrapper),
+            mpl_core: &self.mpl_core,
+        }
+    }
+
+    fn update_asset_plugin(&self) -> UpdateAssetPluginV1<'_> {
+        UpdateAssetPluginV1 {
+            asset: &self.asset,
+            collection: Some(&self.collection),
+            payer: &self.payer,
+            authority: Some(&self.authority),
+            system_program: &self.system_program,
+            log_wrapper: Some(&self.log_wrapper),
+            mpl_core: &self.mpl_core,
+        }
+    }
+
+    fn update_collection_plugin(&self) -> UpdateCollectionPluginV1<'_> {
+        UpdateCollectionPluginV1 {
+            collection: &self.collection,
+            payer: &self.payer,
+            authority: Some(&self.authority),
+            system_program: &self.system_program,
+            log_wrapper: Some(&self.log_wrapper),
+            mpl_core: &self.mpl_core,
+        }
+    }
+}
+
+fn assert_fails<T>(result: Result<T, pinocchio::program_error::ProgramError>, error: MplCoreError) {
+    assert_eq!(result.err(), Some(error.into()));
+}
+
+#[test]
+fn create_asset() {
+    let accounts = Accounts::new();
+    assert_eq!(accounts.create_asset().validate(), Ok(()));
+
+    let no_optional_accounts = CreateAssetV1 {
+        collection: None,
+        authority: None,
+        owner: None,
+        log_wrapper: None,
+        ..accounts.create_asset()
+    };
+    assert_eq!(no_optional_accounts.validate(), Ok(()));
+
+    let wrong_mpl_core = CreateAssetV1 {
+        mpl_core: &accounts.wrong_program,
+        ..accounts.create_asset()
+    };
+    assert_fails(
+        wrong_mpl_core.validate(),
+        MplCoreError::InvalidMplCoreProgram,
+    );
+
+    let wrong_system_program = CreateAssetV1 {
+        system_program: &accounts.wrong_program,
+        ..accounts.create_asset()
+    };
+    assert_fails(
+        wrong_system_program.validate(),
+        MplCoreError::InvalidSystemProgram,
+    );
+
+    let wrong_log_wrapper = CreateAssetV1 {
+        log_wrapper: Some(&accounts.wrong_program),
+        ..accounts.create_asset()
+    };
+    assert_fails(
+        wrong_log_wrapper.validate(),
+        MplCoreError::InvalidLogWrapperProgram,
+    );
+
+    // only the collection exists already
+    let collection_not_mpl_core = CreateAssetV1 {
+        collection: Some(&accounts.not_mpl_core),
+        ..accounts.create_asset()
+    };
+    assert_fails(
+        collection_not_mpl_core.validate(),
+        MplCoreError::InvalidAccountOwner,
+    );
+    let new_asset = CreateAssetV1 {
+        asset: &accounts.not_mpl_core,
+        ..accounts.create_asset()
+    };
+    assert_eq!(new_asset.validate(), Ok(()));
+
+    let asset_not_signing = CreateAssetV1 {
+        asset: &accounts.readonly,
+        ..accounts.create_asset()
+    };
+    assert_fails(asset_not_signing.validate(), MplCoreError::MissingSigner);
+
+    let payer_not_signing = CreateAssetV1 {
+        payer: &accounts.unsigned_payer,
+        ..accounts.create_asset()
+    };
+    assert_fails(payer_not_signing.validate(), MplCoreError::MissingSigner);
+
+    let authority_not_signing = CreateAssetV1 {
+        authority: Some(&accounts.owner),
+        ..accounts.create_asset()
+    };
+    assert_fails(
+        authority_not_signing.validate(),
+        MplCoreError::MissingSigner,
+    );
+
+    let collection_not_writable = CreateAssetV1 {
+        collection: Some(&accounts.readonly),
+        ..accounts.create_asset()
+    };
+    assert_fails(
+        collection_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+
+    let payer_not_writable = CreateAssetV1 {
+        payer: &accounts.readonly_payer,
+        ..accounts.create_asset()
+    };
+    assert_fails(
+        payer_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+}
+
+#[test]
+fn create_collection() {
+    let accounts = Accounts::new();
+    assert_eq!(accounts.create_collection().validate(), Ok(()));
+
+    let wrong_mpl_core = CreateCollectionV1 {
+        mpl_core: &accounts.wrong_program,
+        ..accounts.create_collection()
+    };
+    assert_fails(
+        wrong_mpl_core.validate(),
+        MplCoreError::InvalidMplCoreProgram,
+    );
+
+    let wrong_system_program = CreateCollectionV1 {
+        system_program: &accounts.wrong_program,
+        ..accounts.create_collection()
+    };
+    assert_fails(
+        wrong_system_program.validate(),
+        MplCoreError::InvalidSystemProgram,
+    );
+
+    // the collection does not exist yet, so it isn't owned by mpl core
+    let new_collection = CreateCollectionV1 {
+        collection: &accounts.not_mpl_core,
+        ..accounts.create_collection()
+    };
+    assert_eq!(new_collection.validate(), Ok(()));
+
+    let collection_not_signing = CreateCollectionV1 {
+        collection: &accounts.readonly,
+        ..accounts.create_collection()
+    };
+    assert_fails(
+        collection_not_signing.validate(),
+        MplCoreError::MissingSigner,
+    );
+
+    let payer_not_signing = CreateCollectionV1 {
+        payer: &accounts.unsigned_payer,
+        ..accounts.create_collection()
+    };
+    assert_fails(payer_not_signing.validate(), MplCoreError::MissingSigner);
+
+    let payer_not_writable = CreateCollectionV1 {
+        payer: &accounts.readonly_payer,
+        ..accounts.create_collection()
+    };
+    assert_fails(
+        payer_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+}
+
+#[test]
+fn transfer() {
+    let accounts = Accounts::new();
+    assert_eq!(accounts.transfer().validate(), Ok(()));
+
+    let wrong_mpl_core = TransferV1 {
+        mpl_core: &accounts.wrong_program,
+        ..accounts.transfer()
+    };
+    assert_fails(
+        wrong_mpl_core.validate(),
+        MplCoreError::InvalidMplCoreProgram,
+    );
+
+    let wrong_system_program = TransferV1 {
+        system_program: &accounts.wrong_program,
+        ..accounts.transfer()
+    };
+    assert_fails(
+        wrong_system_program.validate(),
+        MplCoreError::InvalidSystemProgram,
+    );
+
+    let asset_not_mpl_core = TransferV1 {
+        asset: &accounts.not_mpl_core,
+        ..accounts.transfer()
+    };
+    assert_fails(
+        asset_not_mpl_core.validate(),
+        MplCoreError::InvalidAccountOwner,
+    );
+
+    let collection_not_mpl_core = TransferV1 {
+        collection: Some(&accounts.not_mpl_core),
+        ..accounts.transfer()
+    };
+    assert_fails(
+        collection_not_mpl_core.validate(),
+        MplCoreError::InvalidAccountOwner,
+    );
+
+    let payer_not_signing = TransferV1 {
+        payer: &accounts.unsigned_payer,
+        ..accounts.transfer()
+    };
+    assert_fails(payer_not_signing.validate(), MplCoreError::MissingSigner);
+
+    let authority_not_signing = TransferV1 {
+        authority: Some(&accounts.owner),
+        ..accounts.transfer()
+    };
+    assert_fails(
+        authority_not_signing.validate(),
+        MplCoreError::MissingSigner,
+    );
+
+    let asset_not_writable = TransferV1 {
+        asset: &accounts.readonly,
+        ..accounts.transfer()
+    };
+    assert_fails(
+        asset_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+
+    // mpl core only reads the collection
+    let readonly_collection = TransferV1 {
+        collection: Some(&accounts.readonly),
+        ..accounts.transfer()
+    };
+    assert_eq!(readonly_collection.validate(), Ok(()));
+}
+
+#[test]
+fn burn() {
+    let accounts = Accounts::new();
+    assert_eq!(accounts.burn().validate(), Ok(()));
+
+    let wrong_mpl_core = BurnV1 {
+        mpl_core: &accounts.wrong_program,
+        ..accounts.burn()
+    };
+    assert_fails(
+        wrong_mpl_core.validate(),
+        MplCoreError::InvalidMplCoreProgram,
+    );
+
+    let wrong_log_wrapper = BurnV1 {
+        log_wrapper: Some(&accounts.wrong_program),
+        ..accounts.burn()
+    };
+    assert_fails(
+        wrong_log_wrapper.validate(),
+        MplCoreError::InvalidLogWrapperProgram,
+    );
+
+    let asset_not_mpl_core = BurnV1 {
+        asset: &accounts.not_mpl_core,
+        ..accounts.burn()
+    };
+    assert_fails(
+        asset_not_mpl_core.validate(),
+        MplCoreError::InvalidAccountOwner,
+    );
+
+    let payer_not_signing = BurnV1 {
+        payer: &accounts.unsigned_payer,
+        ..accounts.burn()
+    };
+    assert_fails(payer_not_signing.validate(), MplCoreError::MissingSigner);
+
+    let collection_not_writable = BurnV1 {
+        collection: Some(&accounts.readonly),
+        ..accounts.burn()
+    };
+    assert_fails(
+        collection_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+}
+
+#[test]
+fn burn_collection() {
+    let accounts = Accounts::new();
+    assert_eq!(accounts.burn_collection().validate(), Ok(()));
+
+    let wrong_mpl_core = BurnCollectionV1 {
+        mpl_core: &accounts.wrong_program,
+        ..accounts.burn_collection()
+    };
+    assert_fails(
+        wrong_mpl_core.validate(),
+        MplCoreError::InvalidMplCoreProgram,
+    );
+
+    let collection_not_mpl_core = BurnCollectionV1 {
+        collection: &accounts.not_mpl_core,
+        ..accounts.burn_collection()
+    };
+    assert_fails(
+        collection_not_mpl_core.validate(),
+        MplCoreError::InvalidAccountOwner,
+    );
+
+    let payer_not_signing = BurnCollectionV1 {
+        payer: &accounts.unsigned_payer,
+        ..accounts.burn_collection()
+    };
+    assert_fails(payer_not_signing.validate(), MplCoreError::MissingSigner);
+
+    // mpl core marks the authority writable for this one
+    let authority_not_writable = BurnCollectionV1 {
+        authority: Some(&accounts.readonly_payer),
+        ..accounts.burn_collection()
+    };
+    assert_fails(
+        authority_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+}
+
+#[test]
+fn update_asset_plugin() {
+    let accounts = Accounts::new();
+    assert_eq!(accounts.update_asset_plugin().validate(), Ok(()));
+
+    let wrong_mpl_core = UpdateAssetPluginV1 {
+        mpl_core: &accounts.wrong_program,
+        ..accounts.update_asset_plugin()
+    };
+    assert_fails(
+        wrong_mpl_core.validate(),
+        MplCoreError::InvalidMplCoreProgram,
+    );
+
+    let wrong_system_program = UpdateAssetPluginV1 {
+        system_program: &accounts.wrong_program,
+        ..accounts.update_asset_plugin()
+    };
+    assert_fails(
+        wrong_system_program.validate(),
+        MplCoreError::InvalidSystemProgram,
+    );
+
+    let collection_not_mpl_core = UpdateAssetPluginV1 {
+        collection: Some(&accounts.not_mpl_core),
+        ..accounts.update_asset_plugin()
+    };
+    assert_fails(
+        collection_not_mpl_core.validate(),
+        MplCoreError::InvalidAccountOwner,
+    );
+
+    let authority_not_signing = UpdateAssetPluginV1 {
+        authority: Some(&accounts.owner),
+        ..accounts.update_asset_plugin()
+    };
+    assert_fails(
+        authority_not_signing.validate(),
+        MplCoreError::MissingSigner,
+    );
+
+    let asset_not_writable = UpdateAssetPluginV1 {
+        asset: &accounts.readonly,
+        ..accounts.update_asset_plugin()
+    };
+    assert_fails(
+        asset_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+}
+
+#[test]
+fn update_collection_plugin() {
+    let accounts = Accounts::new();
+    assert_eq!(accounts.update_collection_plugin().validate(), Ok(()));
+
+    let wrong_log_wrapper = UpdateCollectionPluginV1 {
+        log_wrapper: Some(&accounts.wrong_program),
+        ..accounts.update_collection_plugin()
+    };
+    assert_fails(
+        wrong_log_wrapper.validate(),
+        MplCoreError::InvalidLogWrapperProgram,
+    );
+
+    let collection_not_mpl_core = UpdateCollectionPluginV1 {
+        collection: &accounts.not_mpl_core,
+        ..accounts.update_collection_plugin()
+    };
+    assert_fails(
+        collection_not_mpl_core.validate(),
+        MplCoreError::InvalidAccountOwner,
+    );
+
+    let payer_not_signing = UpdateCollectionPluginV1 {
+        payer: &accounts.unsigned_payer,
+        ..accounts.update_collection_plugin()
+    };
+    assert_fails(payer_not_signing.validate(), MplCoreError::MissingSigner);
+
+    let payer_not_writable = UpdateCollectionPluginV1 {
+        payer: &accounts.readonly_payer,
+        ..accounts.update_collection_plugin()
+    };
+    assert_fails(
+        payer_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+
+    let collection_not_writable = UpdateCollectionPluginV1 {
+        collection: &accounts.readonly,
+        ..accounts.update_collection_plugin()
+    };
+    assert_fails(
+        collection_not_writable.validate(),
+        MplCoreError::AccountNotWritable,
+    );
+}
+
+/// The key of each account slot, `None` where the account is left out
+fn keys(accounts: &CreateAssetV1) -> [Option<Pubkey>; 9] {
+    let key = |account: &AccountInfo| *account.key();
+    [
+        Some(key(accounts.asset)),
+        accounts.collection.map(key),
+        accounts.authority.map(key),
+        Some(key(accounts.payer)),
+        accounts.owner.map(key),
+        accounts.update_authority.map(key),
+        Some(key(accounts.system_program)),
+        accounts.log_wrapper.map(key),
+        Some(key(accounts.mpl_core)),
+    ]
+}
+
+#[test]
+fn create_asset_builder() {
+    let accounts = Accounts::new();
+    let builder = || {
+        CreateAssetV1::builder(
+            &accounts.asset,
+            &accounts.payer,
+            &accounts.system_program,
+            &accounts.mpl_core,
+        )
+    };
+
+    let (minimal, data) = builder().build().unwrap();
+    assert_eq!(
+        keys(&minimal),
+        [
+            Some(ASSET),
+            None,
+            None,
+            Some(PAYER),
+            None,
+            None,
+            Some(SYSTEM_PROGRAM_ID),
+            None,
+            Some(MPL_CORE_ID),
+        ]
+    );
+    assert!(matches!(data.data_state, DataState::AccountState));
+    assert!(data.name.is_empty() && data.uri.is_empty() && data.plugins.is_none());
+
+    let plugins = [PluginAuthorityPair {
+        plugin: Plugin::ImmutableMetadata,
+        authority: None,
+    }];
+    let (in_collection, data) = builder()
+        .collection(&accounts.collection)
+        .authority(&accounts.authority)
+        .owner(&accounts.owner)
+        .log_wrapper(&accounts.log_wrapper)
+        .name(b"My asset")
+        .uri(b"https://example.com/asset.json")
+        .plugins(&plugins)
+        .build()
+        .unwrap();
+    assert_eq!(
+        keys(&in_collection),
+        [
+            Some(ASSET),
+            Some(COLLECTION),
+            Some(AUTHORITY),
+            Some(PAYER),
+            Some(OWNER),
+            None,
+            Some(SYSTEM_PROGRAM_ID),
+            Some(SPL_NOOP_ID),
+            Some(MPL_CORE_ID),
+        ]
+    );
+    assert_eq!(data.name, b"My asset");
+    assert_eq!(data.uri, b"https://example.com/asset.json");
+    assert_eq!(data.plugins.map(<[_]>::len), Some(1));
+
+    let (with_update_authority, _) = builder()
+        .update_authority(&accounts.authority)
+        .build()
+        .unwrap();
+    assert_eq!(keys(&with_update_authority)[5], Some(AUTHORITY));
+}
+
+#[test]
+fn create_asset_builder_rejects_what_mpl_core_rejects() {
+    let accounts = Accounts::new();
+    let builder = || {
+        CreateAssetV1::builder(
+            &accounts.asset,
+            &accounts.payer,
+            &accounts.system_program,
+            &accounts.mpl_core,
+        )
+    };
+
+    // the collection already is the update authority
+    assert_fails(
+        builder()
+            .collection(&accounts.collection)
+            .update_authority(&accounts.authority)
+            .build(),
+        MplCoreError::ConflictingUpdateAuthority,
+    );
+    assert_fails(
+        builder().data_state(DataState::LedgerState).build(),
+        MplCoreError::LedgerStateNotSupported,
+    );
+
+    // invoke builds and validates before invoking anything
+    let mut buffer = [0; 256];
+    assert_fails(
+        builder()
+            .update_authority(&accounts.authority)
+            .collection(&accounts.collection)
+            .invoke(&mut buffer),
+        MplCoreError::ConflictingUpdateAuthority,
+    );
+    assert_fails(
+        CreateAssetV1::builder(
+            &accounts.asset,
+            &accounts.payer,
+            &accounts.system_program,
+            &accounts.wrong_program,
+        )
+        .invoke(&mut buffer),
+        MplCoreError::InvalidMplCoreProgram,
+    );
+}
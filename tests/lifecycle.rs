@@ -0,0 +1,326 @@
+//! Checks how the lifecycle pre-checks combine the asset, the collection plugins and the asset plugins,
+//! on accounts written with `AssetInfo` and `CollectionInfo`
+
+use mpl_core_pinocchio::{
+    data::{
+        asset::{
+            AssetInfo, BaseAssetV1, BaseCollectionV1, CollectionInfo, Key,
+            PluginAuthorityPairWithoutOption,
+        },
+        plugins::{
+            Creator, FreezeDelegate, PermanentFreezeDelegate, Plugin, PluginAuthority, Royalties,
+            RuleSet, UpdateAuthority,
+        },
+        view::{AssetView, CollectionView},
+        Serialize,
+    },
+    lifecycle::{LifecycleResult, RejectionReason},
+};
+
+const ALICE: [u8; 32] = [1; 32];
+const BOB: [u8; 32] = [2; 32];
+const PROGRAM: [u8; 32] = [3; 32];
+const COLLECTION: [u8; 32] = [4; 32];
+/// The program the transfers are made from
+const CALLER: [u8; 32] = [5; 32];
+const ALLOWED: [[u8; 32]; 1] = [PROGRAM];
+static CREATORS: [Creator; 1] = [Creator {
+    address: BOB,
+    percentage: 100,
+}];
+
+fn to_bytes(info: &impl Serialize) -> Vec<u8> {
+    let mut bytes = vec![0; info.serialized_size()];
+    info.serialize_to(&mut bytes);
+    bytes
+}
+
+/// An asset owned by ALICE in COLLECTION, with `plugins`
+fn asset(plugins: &[PluginAuthorityPairWithoutOption]) -> Vec<u8> {
+    to_bytes(&AssetInfo {
+        base: BaseAssetV1 {
+            key: Key::AssetV1,
+            owner: ALICE,
+            update_authority: UpdateAuthority::Collection(COLLECTION),
+            name: b"asset",
+            uri: b"https://example.com/asset.json",
+            seq: None,
+        },
+        plugins,
+        external_plugins: &[],
+    })
+}
+
+/// A collection whose update authority is BOB, with `plugins`
+fn collection(plugins: &[PluginAuthorityPairWithoutOption]) -> Vec<u8> {
+    to_bytes(&CollectionInfo {
+        base: BaseCollectionV1 {
+            key: Key::CollectionV1,
+            update_authority: BOB,
+            name: b"collection",
+            uri: b"https://example.com/collection.json",
+            num_minted: 1,
+            current_size: 1,
+        },
+        plugins,
+        external_plugins: &[],
+    })
+}
+
+/// A collection frozen by a PermanentFreezeDelegate
+fn frozen_collection() -> Vec<u8> {
+    collection(&[PluginAuthorityPairWithoutOption {
+        plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+        authority: PluginAuthority::UpdateAuthority,
+    }])
+}
+
+/// Royalties of one creator, with `rule_set`
+fn royalties(rule_set: RuleSet) -> PluginAuthorityPairWithoutOption {
+    PluginAuthorityPairWithoutOption {
+        plugin: Plugin::Royalties(Royalties {
+            basis_points: 500,
+            creators: &CREATORS,
+            rule_set,
+        }),
+        authority: PluginAuthority::UpdateAuthority,
+    }
+}
+
+#[test]
+fn permanent_delegate_overrides_a_frozen_collection() {
+    let collection = frozen_collection();
+    let collection = CollectionView::new(&collection).unwrap();
+    let asset = asset(&[
+        PluginAuthorityPairWithoutOption {
+            plugin: Plugin::PermanentTransferDelegate,
+            authority: PluginAuthority::Address(PROGRAM),
+        },
+        PluginAuthorityPairWithoutOption {
+            plugin: Plugin::PermanentBurnDelegate,
+            authority: PluginAuthority::Address(PROGRAM),
+        },
+    ]);
+    let asset = AssetView::new(&asset).unwrap();
+
+    // the collection rejects first, the asset's permanent delegates still force approve
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection), &PROGRAM, &CALLER)
+            .unwrap(),
+        LifecycleResult::Approved
+    );
+    assert_eq!(
+        asset.can_burn(Some(&collection), &PROGRAM).unwrap(),
+        LifecycleResult::Approved
+    );
+
+    // the owner has no permanent delegate, so the freeze holds
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection), &ALICE, &CALLER)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::CollectionFrozen)
+    );
+    assert_eq!(
+        asset.can_burn(Some(&collection), &ALICE).unwrap(),
+        LifecycleResult::Rejected(RejectionReason::CollectionFrozen)
+    );
+}
+
+#[test]
+fn first_rejection_is_reported() {
+    let collection = frozen_collection();
+    let collection = CollectionView::new(&collection).unwrap();
+    let asset = asset(&[PluginAuthorityPairWithoutOption {
+        plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+        authority: PluginAuthority::Owner,
+    }]);
+    let asset = AssetView::new(&asset).unwrap();
+
+    // the collection plugins are asked before the asset plugins
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection), &ALICE, &CALLER)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::CollectionFrozen)
+    );
+    // nobody approved BOB, but the rejection wins over the missing approval
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection), &BOB, &CALLER)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::CollectionFrozen)
+    );
+}
+
+#[test]
+fn royalties_rule_set_checks_the_transferring_program() {
+    // the asset's own rule set
+    let asset_deny = asset(&[royalties(RuleSet::ProgramDenyList(&ALLOWED))]);
+    let asset_deny = AssetView::new(&asset_deny).unwrap();
+    let collection_none = collection(&[]);
+    let collection_none = CollectionView::new(&collection_none).unwrap();
+    assert_eq!(
+        asset_deny
+            .can_transfer(Some(&collection_none), &ALICE, &PROGRAM)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::ProgramNotAllowed)
+    );
+    assert!(asset_deny
+        .can_transfer(Some(&collection_none), &ALICE, &CALLER)
+        .unwrap()
+        .is_approved());
+
+    // the collection's rule set applies to its assets too
+    let asset_plain = asset(&[]);
+    let asset_plain = AssetView::new(&asset_plain).unwrap();
+    let collection_allow = collection(&[royalties(RuleSet::ProgramAllowList(&ALLOWED))]);
+    let collection_allow = CollectionView::new(&collection_allow).unwrap();
+    assert_eq!(
+        asset_plain
+            .can_transfer(Some(&collection_allow), &ALICE, &CALLER)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::ProgramNotAllowed)
+    );
+    assert!(asset_plain
+        .can_transfer(Some(&collection_allow), &ALICE, &PROGRAM)
+        .unwrap()
+        .is_approved());
+
+    // no rule set allows every program, and burns and updates never look at it
+    let asset_none = asset(&[royalties(RuleSet::None)]);
+    let asset_none = AssetView::new(&asset_none).unwrap();
+    assert!(asset_none
+        .can_transfer(Some(&collection_allow), &ALICE, &PROGRAM)
+        .unwrap()
+        .is_approved());
+    assert!(asset_deny
+        .can_burn(Some(&collection_none), &ALICE)
+        .unwrap()
+        .is_approved());
+}
+
+#[test]
+fn immutable_metadata_rejects_updates() {
+    let collection_plain = collection(&[]);
+    let collection_plain = CollectionView::new(&collection_plain).unwrap();
+    let asset_plain = asset(&[]);
+    let asset_plain = AssetView::new(&asset_plain).unwrap();
+    // BOB is the update authority through the collection
+    assert!(asset_plain
+        .can_update(Some(&collection_plain), &BOB)
+        .unwrap()
+        .is_approved());
+
+    let immutable = [PluginAuthorityPairWithoutOption {
+        plugin: Plugin::ImmutableMetadata,
+        authority: PluginAuthority::UpdateAuthority,
+    }];
+    let asset_immutable = asset(&immutable);
+    let asset_immutable = AssetView::new(&asset_immutable).unwrap();
+    assert_eq!(
+        asset_immutable
+            .can_update(Some(&collection_plain), &BOB)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::ImmutableMetadata)
+    );
+
+    let collection_immutable = collection(&immutable);
+    let collection_immutable = CollectionView::new(&collection_immutable).unwrap();
+    assert_eq!(
+        asset_plain
+            .can_update(Some(&collection_immutable), &BOB)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::ImmutableMetadata)
+    );
+}
+
+#[test]
+fn delegates_approve_their_own_lifecycle_event() {
+    let collection_plain = collection(&[]);
+    let collection_plain = CollectionView::new(&collection_plain).unwrap();
+    let asset = asset(&[
+        PluginAuthorityPairWithoutOption {
+            plugin: Plugin::TransferDelegate,
+            authority: PluginAuthority::Address(PROGRAM),
+        },
+        PluginAuthorityPairWithoutOption {
+            plugin: Plugin::BurnDelegate,
+            authority: PluginAuthority::Address(BOB),
+        },
+    ]);
+    let asset = AssetView::new(&asset).unwrap();
+
+    assert!(asset
+        .can_transfer(Some(&collection_plain), &PROGRAM, &CALLER)
+        .unwrap()
+        .is_approved());
+    assert_eq!(
+        asset.can_burn(Some(&collection_plain), &PROGRAM).unwrap(),
+        LifecycleResult::Rejected(RejectionReason::NoApprovals)
+    );
+
+    assert!(asset
+        .can_burn(Some(&collection_plain), &BOB)
+        .unwrap()
+        .is_approved());
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection_plain), &BOB, &CALLER)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::NoApprovals)
+    );
+}
+
+#[test]
+fn permanent_burn_delegate_of_the_collection_approves() {
+    let collection = collection(&[PluginAuthorityPairWithoutOption {
+        plugin: Plugin::PermanentBurnDelegate,
+        authority: PluginAuthority::Address(PROGRAM),
+    }]);
+    let collection = CollectionView::new(&collection).unwrap();
+    let asset = asset(&[]);
+    let asset = AssetView::new(&asset).unwrap();
+
+    assert!(asset
+        .can_burn(Some(&collection), &PROGRAM)
+        .unwrap()
+        .is_approved());
+    // a permanent burn delegate can't transfer
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection), &PROGRAM, &CALLER)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::NoApprovals)
+    );
+}
+
+#[test]
+fn force_approval_overrides_a_rejection_of_the_asset() {
+    let collection_plain = collection(&[]);
+    let collection_plain = CollectionView::new(&collection_plain).unwrap();
+    // the FreezeDelegate rejects before the PermanentTransferDelegate is asked
+    let asset = asset(&[
+        PluginAuthorityPairWithoutOption {
+            plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+            authority: PluginAuthority::Owner,
+        },
+        PluginAuthorityPairWithoutOption {
+            plugin: Plugin::PermanentTransferDelegate,
+            authority: PluginAuthority::Address(PROGRAM),
+        },
+    ]);
+    let asset = AssetView::new(&asset).unwrap();
+
+    assert_eq!(
+        asset
+            .can_transfer(Some(&collection_plain), &ALICE, &CALLER)
+            .unwrap(),
+        LifecycleResult::Rejected(RejectionReason::FrozenByFreezeDelegate)
+    );
+    assert!(asset
+        .can_transfer(Some(&collection_plain), &PROGRAM, &CALLER)
+        .unwrap()
+        .is_approved());
+}
@@ -0,0 +1,207 @@
+//! Checks the plugin list built by `Plugins`, its authorities and the errors kept until `build`
+
+use core::mem::MaybeUninit;
+
+use mpl_core_pinocchio::{
+    data::{
+        plugin_builder::Plugins,
+        plugins::{
+            Creator, FreezeDelegate, Plugin, PluginAuthority, PluginAuthorityPair, PluginType,
+            RuleSet,
+        },
+    },
+    error::MplCoreError,
+};
+use pinocchio::program_error::ProgramError;
+
+const DELEGATE: [u8; 32] = [1; 32];
+static CREATORS: [Creator; 1] = [Creator {
+    address: [2; 32],
+    percentage: 100,
+}];
+
+/// The plugin types and authorities of the built list, in order
+fn summary(plugins: &[PluginAuthorityPair]) -> Vec<(u8, Option<PluginAuthority>)> {
+    plugins
+        .iter()
+        .map(|pair| (pair.plugin.get_plugin_number(), pair.authority))
+        .collect()
+}
+
+fn build_error(plugins: Plugins) -> ProgramError {
+    plugins.build().err().expect("build should fail")
+}
+
+#[test]
+fn plugins_keep_their_order_and_default_authority() {
+    let mut buffer = [const { MaybeUninit::uninit() }; 6];
+    let plugins = Plugins::new(&mut buffer)
+        .royalties(500, &CREATORS, RuleSet::None)
+        .freeze_delegate(true)
+        .burn_delegate()
+        .transfer_delegate()
+        .immutable_metadata()
+        .plugin(Plugin::PermanentBurnDelegate)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        summary(plugins),
+        [
+            (
+                PluginType::Royalties as u8,
+                Some(PluginAuthority::UpdateAuthority)
+            ),
+            (
+                PluginType::FreezeDelegate as u8,
+                Some(PluginAuthority::Owner)
+            ),
+            (PluginType::BurnDelegate as u8, Some(PluginAuthority::Owner)),
+            (
+                PluginType::TransferDelegate as u8,
+                Some(PluginAuthority::Owner)
+            ),
+            (
+                PluginType::ImmutableMetadata as u8,
+                Some(PluginAuthority::UpdateAuthority)
+            ),
+            (
+                PluginType::PermanentBurnDelegate as u8,
+                Some(PluginAuthority::UpdateAuthority)
+            ),
+        ]
+    );
+    // the data written through the MaybeUninit buffer is read back as is
+    assert!(matches!(
+        plugins[1].plugin,
+        Plugin::FreezeDelegate(FreezeDelegate { frozen: true })
+    ));
+    match &plugins[0].plugin {
+        Plugin::Royalties(royalties) => {
+            assert_eq!(royalties.basis_points, 500);
+            assert_eq!(royalties.creators, &CREATORS);
+        }
+        _ => panic!("expected royalties"),
+    }
+}
+
+#[test]
+fn no_plugins_is_an_empty_list() {
+    let mut buffer: [MaybeUninit<PluginAuthorityPair>; 0] = [];
+    assert!(Plugins::new(&mut buffer).build().unwrap().is_empty());
+}
+
+#[test]
+fn authority_overrides_the_plugin_just_before() {
+    let mut buffer = [const { MaybeUninit::uninit() }; 3];
+    let plugins = Plugins::new(&mut buffer)
+        .freeze_delegate(false)
+        .authority(PluginAuthority::Address(DELEGATE))
+        .transfer_delegate()
+        .authority(PluginAuthority::Owner)
+        .immutable_metadata()
+        .authority(PluginAuthority::Address(DELEGATE))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        summary(plugins),
+        [
+            (
+                PluginType::FreezeDelegate as u8,
+                Some(PluginAuthority::Address(DELEGATE))
+            ),
+            (
+                PluginType::TransferDelegate as u8,
+                Some(PluginAuthority::Owner)
+            ),
+            (
+                PluginType::ImmutableMetadata as u8,
+                Some(PluginAuthority::Address(DELEGATE))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn authority_that_cant_manage_the_plugin_is_rejected() {
+    let mut buffer = [const { MaybeUninit::uninit() }; 1];
+    assert_eq!(
+        build_error(
+            Plugins::new(&mut buffer)
+                .immutable_metadata()
+                .authority(PluginAuthority::None)
+        ),
+        MplCoreError::InvalidAuthorityForPlugin.into()
+    );
+
+    let owner_managed: [for<'b, 'a> fn(Plugins<'b, 'a>) -> Plugins<'b, 'a>; 4] = [
+        |plugins| plugins.freeze_delegate(false),
+        |plugins| plugins.transfer_delegate(),
+        |plugins| plugins.burn_delegate(),
+        |plugins| plugins.freeze_execute(false),
+    ];
+    for add in owner_managed {
+        let mut buffer = [const { MaybeUninit::uninit() }; 1];
+        assert_eq!(
+            build_error(add(Plugins::new(&mut buffer)).authority(PluginAuthority::UpdateAuthority)),
+            MplCoreError::InvalidAuthorityForPlugin.into()
+        );
+    }
+}
+
+#[test]
+fn duplicate_plugin_is_rejected() {
+    let mut buffer = [const { MaybeUninit::uninit() }; 3];
+    assert_eq!(
+        build_error(
+            Plugins::new(&mut buffer)
+                .freeze_delegate(false)
+                .immutable_metadata()
+                .freeze_delegate(true)
+        ),
+        MplCoreError::DuplicatePlugin.into()
+    );
+}
+
+#[test]
+fn authority_before_any_plugin_is_rejected() {
+    let mut buffer = [const { MaybeUninit::uninit() }; 1];
+    assert_eq!(
+        build_error(
+            Plugins::new(&mut buffer)
+                .authority(PluginAuthority::Owner)
+                .freeze_delegate(false)
+        ),
+        MplCoreError::MissingPlugin.into()
+    );
+}
+
+#[test]
+fn too_many_plugins_for_the_buffer_is_rejected() {
+    let mut buffer = [const { MaybeUninit::uninit() }; 2];
+    assert_eq!(
+        build_error(
+            Plugins::new(&mut buffer)
+                .freeze_delegate(false)
+                .burn_delegate()
+                .transfer_delegate()
+        ),
+        MplCoreError::BufferTooSmall.into()
+    );
+}
+
+#[test]
+fn first_error_is_kept() {
+    let mut buffer = [const { MaybeUninit::uninit() }; 1];
+    assert_eq!(
+        build_error(
+            Plugins::new(&mut buffer)
+                .freeze_delegate(false)
+                .freeze_delegate(false)
+                .burn_delegate()
+                .authority(PluginAuthority::None)
+        ),
+        MplCoreError::DuplicatePlugin.into()
+    );
+}
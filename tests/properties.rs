@@ -0,0 +1,515 @@
+//! Property tests for the `Skip` impls and the registry parsing every reader is built on.
+//! Accounts are generated as owned models, serialized through `AssetInfo` or `CollectionInfo`
+//! and read back
+
+use mpl_core_pinocchio::data::{
+    asset::{
+        AssetInfo, BaseAssetV1, BaseCollectionV1, CollectionInfo, Key,
+        PluginAuthorityPairWithoutOption,
+    },
+    external_plugins::{
+        AppData, ExternalPluginAdapter, ExternalPluginAdapterSchema, ExternalPluginAdapterWithData,
+    },
+    plugins::{
+        read_royalties_asset, read_royalties_collection, Attribute, Attributes, AttributesView,
+        Creator, Edition, FreezeDelegate, Plugin, PluginAuthority, PluginType, Royalties,
+        RoyaltiesInfo, RuleSet, UpdateAuthority, UpdateDelegate,
+    },
+    view::{AssetView, CollectionView, PluginRecord},
+    DeserializeSized, Serialize, Skip,
+};
+use proptest::{collection::vec, option, prelude::*};
+
+type Pubkey = [u8; 32];
+
+#[derive(Clone, Debug)]
+enum RuleSetModel {
+    None,
+    ProgramAllowList(Vec<Pubkey>),
+    ProgramDenyList(Vec<Pubkey>),
+}
+
+/// An owned plugin the borrowed `Plugin` is built from
+#[derive(Clone, Debug)]
+enum PluginModel {
+    Royalties {
+        basis_points: u16,
+        creators: Vec<(Pubkey, u8)>,
+        rule_set: RuleSetModel,
+    },
+    FreezeDelegate(bool),
+    TransferDelegate,
+    UpdateDelegate(Vec<Pubkey>),
+    Attributes(Vec<(Vec<u8>, Vec<u8>)>),
+    Edition(u32),
+    ImmutableMetadata,
+}
+
+impl PluginModel {
+    fn plugin_type(&self) -> PluginType {
+        match self {
+            Self::Royalties { .. } => PluginType::Royalties,
+            Self::FreezeDelegate(_) => PluginType::FreezeDelegate,
+            Self::TransferDelegate => PluginType::TransferDelegate,
+            Self::UpdateDelegate(_) => PluginType::UpdateDelegate,
+            Self::Attributes(_) => PluginType::Attributes,
+            Self::Edition(_) => PluginType::Edition,
+            Self::ImmutableMetadata => PluginType::ImmutableMetadata,
+        }
+    }
+
+    fn creators(&self) -> Vec<Creator> {
+        match self {
+            Self::Royalties { creators, .. } => creators
+                .iter()
+                .map(|&(address, percentage)| Creator {
+                    address,
+                    percentage,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn attributes(&self) -> Vec<Attribute<'_>> {
+        match self {
+            Self::Attributes(attributes) => attributes
+                .iter()
+                .map(|(key, value)| Attribute { key, value })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// `creators` and `attributes` are what `Self::creators` and `Self::attributes` returned
+    fn plugin<'a>(
+        &'a self,
+        creators: &'a [Creator],
+        attributes: &'a [Attribute<'a>],
+    ) -> Plugin<'a> {
+        match self {
+            Self::Royalties {
+                basis_points,
+                rule_set,
+                ..
+            } => Plugin::Royalties(Royalties {
+                basis_points: *basis_points,
+                creators,
+                rule_set: match rule_set {
+                    RuleSetModel::None => RuleSet::None,
+                    RuleSetModel::ProgramAllowList(programs) => RuleSet::ProgramAllowList(programs),
+                    RuleSetModel::ProgramDenyList(programs) => RuleSet::ProgramDenyList(programs),
+                },
+            }),
+            Self::FreezeDelegate(frozen) => {
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: *frozen })
+            }
+            Self::TransferDelegate => Plugin::TransferDelegate,
+            Self::UpdateDelegate(additional_delegates) => Plugin::UpdateDelegate(UpdateDelegate {
+                additional_delegates,
+            }),
+            Self::Attributes(_) => Plugin::Attributes(Attributes {
+                attribute_list: attributes,
+            }),
+            Self::Edition(number) => Plugin::Edition(Edition { number: *number }),
+            Self::ImmutableMetadata => Plugin::ImmutableMetadata,
+        }
+    }
+
+    /// Checks a reader recovers this plugin from the data after its discriminant
+    fn assert_read_back(&self, data: &[u8]) {
+        match self {
+            Self::Royalties {
+                basis_points,
+                creators,
+                rule_set,
+            } => {
+                let royalties = Royalties::from_bytes(data).unwrap();
+                assert_eq!(royalties.basis_points, *basis_points);
+                let read: Vec<_> = royalties
+                    .creators
+                    .iter()
+                    .map(|creator| (creator.address, creator.percentage))
+                    .collect();
+                assert_eq!(&read, creators);
+                match (royalties.rule_set, rule_set) {
+                    (RuleSet::None, RuleSetModel::None) => {}
+                    (RuleSet::ProgramAllowList(read), RuleSetModel::ProgramAllowList(programs))
+                    | (RuleSet::ProgramDenyList(read), RuleSetModel::ProgramDenyList(programs)) => {
+                        assert_eq!(read, &programs[..])
+                    }
+                    _ => panic!("rule set read back as a different variant"),
+                }
+            }
+            Self::FreezeDelegate(frozen) => {
+                assert_eq!(FreezeDelegate::deserialize(data).unwrap().frozen, *frozen)
+            }
+            Self::UpdateDelegate(additional_delegates) => assert_eq!(
+                UpdateDelegate::from_bytes(data)
+                    .unwrap()
+                    .additional_delegates,
+                &additional_delegates[..]
+            ),
+            Self::Attributes(attributes) => {
+                let view = AttributesView::from_bytes(data).unwrap();
+                let read: Vec<_> = view
+                    .iter()
+                    .map(|attribute| (attribute.key.to_vec(), attribute.value.to_vec()))
+                    .collect();
+                assert_eq!(&read, attributes);
+            }
+            Self::Edition(number) => {
+                assert_eq!(Edition::deserialize(data).unwrap().number, *number)
+            }
+            Self::TransferDelegate | Self::ImmutableMetadata => {}
+        }
+    }
+
+    fn royalties(&self) -> Option<(u16, Vec<(Pubkey, u8)>)> {
+        match self {
+            Self::Royalties {
+                basis_points,
+                creators,
+                ..
+            } => Some((*basis_points, creators.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct AppDataModel {
+    authority: PluginAuthority,
+    data_authority: PluginAuthority,
+    data: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug)]
+struct AssetModel {
+    owner: Pubkey,
+    update_authority: UpdateAuthority,
+    name: Vec<u8>,
+    uri: Vec<u8>,
+    seq: Option<u64>,
+    plugins: Vec<(PluginModel, PluginAuthority)>,
+    app_data: Vec<AppDataModel>,
+}
+
+#[derive(Clone, Debug)]
+struct CollectionModel {
+    update_authority: Pubkey,
+    name: String,
+    uri: String,
+    num_minted: u32,
+    current_size: u32,
+    plugins: Vec<(PluginModel, PluginAuthority)>,
+}
+
+fn pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<Pubkey>()
+}
+
+fn plugin_authority() -> impl Strategy<Value = PluginAuthority> {
+    prop_oneof![
+        Just(PluginAuthority::None),
+        Just(PluginAuthority::Owner),
+        Just(PluginAuthority::UpdateAuthority),
+        pubkey().prop_map(PluginAuthority::Address),
+    ]
+}
+
+fn update_authority() -> impl Strategy<Value = UpdateAuthority> {
+    prop_oneof![
+        Just(UpdateAuthority::None),
+        pubkey().prop_map(UpdateAuthority::Address),
+        pubkey().prop_map(UpdateAuthority::Collection),
+    ]
+}
+
+fn bytes(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 0..max_len)
+}
+
+fn rule_set() -> impl Strategy<Value = RuleSetModel> {
+    prop_oneof![
+        Just(RuleSetModel::None),
+        vec(pubkey(), 0..4).prop_map(RuleSetModel::ProgramAllowList),
+        vec(pubkey(), 0..4).prop_map(RuleSetModel::ProgramDenyList),
+    ]
+}
+
+fn plugin() -> impl Strategy<Value = PluginModel> {
+    prop_oneof![
+        (any::<u16>(), vec((pubkey(), any::<u8>()), 0..5), rule_set()).prop_map(
+            |(basis_points, creators, rule_set)| PluginModel::Royalties {
+                basis_points,
+                creators,
+                rule_set,
+            }
+        ),
+        any::<bool>().prop_map(PluginModel::FreezeDelegate),
+        Just(PluginModel::TransferDelegate),
+        vec(pubkey(), 0..4).prop_map(PluginModel::UpdateDelegate),
+        vec((bytes(16), bytes(16)), 0..5).prop_map(PluginModel::Attributes),
+        any::<u32>().prop_map(PluginModel::Edition),
+        Just(PluginModel::ImmutableMetadata),
+    ]
+}
+
+/// mpl core allows each plugin type once per account
+fn plugins() -> impl Strategy<Value = Vec<(PluginModel, PluginAuthority)>> {
+    vec((plugin(), plugin_authority()), 0..6).prop_map(|mut plugins| {
+        let mut seen = Vec::new();
+        plugins.retain(|(plugin, _)| {
+            let plugin_type = plugin.plugin_type();
+            let first = !seen.contains(&plugin_type);
+            seen.push(plugin_type);
+            first
+        });
+        plugins
+    })
+}
+
+fn app_data() -> impl Strategy<Value = AppDataModel> {
+    (
+        plugin_authority(),
+        plugin_authority(),
+        option::of(bytes(32)),
+    )
+        .prop_map(|(authority, data_authority, data)| AppDataModel {
+            authority,
+            data_authority,
+            data,
+        })
+}
+
+fn asset() -> impl Strategy<Value = AssetModel> {
+    (
+        pubkey(),
+        update_authority(),
+        bytes(40),
+        bytes(80),
+        option::of(any::<u64>()),
+        plugins(),
+        vec(app_data(), 0..3),
+    )
+        .prop_map(
+            |(owner, update_authority, name, uri, seq, plugins, app_data)| AssetModel {
+                owner,
+                update_authority,
+                name,
+                uri,
+                seq,
+                plugins,
+                app_data,
+            },
+        )
+}
+
+fn collection() -> impl Strategy<Value = CollectionModel> {
+    (
+        pubkey(),
+        ".{0,32}",
+        ".{0,64}",
+        any::<u32>(),
+        any::<u32>(),
+        plugins(),
+    )
+        .prop_map(
+            |(update_authority, name, uri, num_minted, current_size, plugins)| CollectionModel {
+                update_authority,
+                name,
+                uri,
+                num_minted,
+                current_size,
+                plugins,
+            },
+        )
+}
+
+fn serialize(value: &impl Serialize) -> Vec<u8> {
+    let mut bytes = vec![0; value.serialized_size()];
+    assert_eq!(value.serialize_to(&mut bytes), bytes.len());
+    bytes
+}
+
+/// Serializes the asset the same way mpl core lays it out
+fn asset_account(asset: &AssetModel) -> (Vec<u8>, usize) {
+    let creators: Vec<_> = asset.plugins.iter().map(|(p, _)| p.creators()).collect();
+    let attributes: Vec<_> = asset.plugins.iter().map(|(p, _)| p.attributes()).collect();
+    let plugins: Vec<_> = asset
+        .plugins
+        .iter()
+        .zip(creators.iter().zip(&attributes))
+        .map(
+            |((plugin, authority), (creators, attributes))| PluginAuthorityPairWithoutOption {
+                plugin: plugin.plugin(creators, attributes),
+                authority: *authority,
+            },
+        )
+        .collect();
+    let external_plugins: Vec<_> = asset
+        .app_data
+        .iter()
+        .map(|app_data| ExternalPluginAdapterWithData {
+            adapter: ExternalPluginAdapter::AppData(AppData {
+                data_authority: app_data.data_authority,
+                schema: ExternalPluginAdapterSchema::Binary,
+            }),
+            authority: app_data.authority,
+            lifecycle_checks: None,
+            data: app_data.data.as_deref(),
+        })
+        .collect();
+
+    let base = BaseAssetV1 {
+        key: Key::AssetV1,
+        owner: asset.owner,
+        update_authority: asset.update_authority,
+        name: &asset.name,
+        uri: &asset.uri,
+        seq: asset.seq,
+    };
+    let base_len = base.serialized_size();
+
+    let info = AssetInfo {
+        base,
+        plugins: &plugins,
+        external_plugins: &external_plugins,
+    };
+    (serialize(&info), base_len)
+}
+
+/// Serializes the collection the same way mpl core lays it out
+fn collection_account(collection: &CollectionModel) -> (Vec<u8>, usize) {
+    let creators: Vec<_> = collection
+        .plugins
+        .iter()
+        .map(|(p, _)| p.creators())
+        .collect();
+    let attributes: Vec<_> = collection
+        .plugins
+        .iter()
+        .map(|(p, _)| p.attributes())
+        .collect();
+    let plugins: Vec<_> = collection
+        .plugins
+        .iter()
+        .zip(creators.iter().zip(&attributes))
+        .map(
+            |((plugin, authority), (creators, attributes))| PluginAuthorityPairWithoutOption {
+                plugin: plugin.plugin(creators, attributes),
+                authority: *authority,
+            },
+        )
+        .collect();
+
+    let base = BaseCollectionV1 {
+        key: Key::CollectionV1,
+        update_authority: collection.update_authority,
+        name: collection.name.as_bytes(),
+        uri: collection.uri.as_bytes(),
+        num_minted: collection.num_minted,
+        current_size: collection.current_size,
+    };
+    let base_len = base.serialized_size();
+
+    let info = CollectionInfo {
+        base,
+        plugins: &plugins,
+        external_plugins: &[],
+    };
+    (serialize(&info), base_len)
+}
+
+fn assert_records<'a>(
+    records: impl Iterator<Item = Result<PluginRecord<'a>, pinocchio::program_error::ProgramError>>,
+    plugins: &[(PluginModel, PluginAuthority)],
+) {
+    let records: Vec<_> = records.map(Result::unwrap).collect();
+    assert_eq!(records.len(), plugins.len());
+    for (record, (plugin, authority)) in records.iter().zip(plugins) {
+        assert_eq!(record.plugin_type, plugin.plugin_type() as u8);
+        assert_eq!(record.authority, *authority);
+        plugin.assert_read_back(record.data);
+    }
+}
+
+fn assert_royalties(royalties: RoyaltiesInfo, plugins: &[(PluginModel, PluginAuthority)]) {
+    let (basis_points, creators) = plugins
+        .iter()
+        .find_map(|(plugin, _)| plugin.royalties())
+        .unwrap_or_default();
+    assert_eq!(royalties.basis_points, basis_points);
+    let read: Vec<_> = royalties
+        .creators
+        .iter()
+        .map(|creator| (creator.address, creator.percentage))
+        .collect();
+    assert_eq!(read, creators);
+}
+
+proptest! {
+    #[test]
+    fn authorities_skip_their_serialized_size(
+        plugin_authority in plugin_authority(),
+        update_authority in update_authority(),
+        trailing in bytes(16),
+    ) {
+        let mut bytes = serialize(&plugin_authority);
+        let len = bytes.len();
+        bytes.extend_from_slice(&trailing);
+        prop_assert_eq!(PluginAuthority::skip_bytes(&bytes).unwrap(), len);
+        prop_assert_eq!(PluginAuthority::deserialize(&bytes).unwrap(), plugin_authority);
+
+        let mut bytes = serialize(&update_authority);
+        let len = bytes.len();
+        bytes.extend_from_slice(&trailing);
+        prop_assert_eq!(UpdateAuthority::skip_bytes(&bytes).unwrap(), len);
+        prop_assert_eq!(UpdateAuthority::deserialize(&bytes).unwrap(), update_authority);
+    }
+
+    #[test]
+    fn assets_read_back(asset in asset()) {
+        let (bytes, base_len) = asset_account(&asset);
+        prop_assert_eq!(BaseAssetV1::skip_bytes(&bytes).unwrap(), base_len);
+
+        let view = AssetView::new(&bytes).unwrap();
+        prop_assert_eq!(view.owner, &asset.owner);
+        prop_assert_eq!(view.update_authority, asset.update_authority);
+        prop_assert_eq!(view.name, &asset.name[..]);
+        prop_assert_eq!(view.uri, &asset.uri[..]);
+        prop_assert_eq!(view.seq, asset.seq);
+
+        let has_plugins = !asset.plugins.is_empty() || !asset.app_data.is_empty();
+        prop_assert_eq!(view.registry.is_some(), has_plugins);
+        prop_assert_eq!(bytes.len() == base_len, !has_plugins);
+        assert_records(view.plugins(), &asset.plugins);
+
+        assert_royalties(read_royalties_asset(&bytes).unwrap(), &asset.plugins);
+
+        match asset.update_authority {
+            UpdateAuthority::Collection(collection) => {
+                prop_assert_eq!(BaseAssetV1::get_collection(&bytes).unwrap(), &collection)
+            }
+            _ => prop_assert!(BaseAssetV1::get_collection(&bytes).is_err()),
+        }
+    }
+
+    #[test]
+    fn collections_read_back(collection in collection()) {
+        let (bytes, base_len) = collection_account(&collection);
+        prop_assert_eq!(BaseCollectionV1::skip_bytes(&bytes).unwrap(), base_len);
+
+        let view = CollectionView::new(&bytes).unwrap();
+        prop_assert_eq!(view.update_authority, &collection.update_authority);
+        prop_assert_eq!(view.name, collection.name.as_bytes());
+        prop_assert_eq!(view.uri, collection.uri.as_bytes());
+        prop_assert_eq!(view.num_minted, collection.num_minted);
+        prop_assert_eq!(view.current_size, collection.current_size);
+        prop_assert_eq!(view.registry.is_some(), !collection.plugins.is_empty());
+        assert_records(view.plugins(), &collection.plugins);
+
+        assert_royalties(read_royalties_collection(&bytes).unwrap(), &collection.plugins);
+    }
+}
@@ -0,0 +1,374 @@
+//! Owned Borsh models of the mpl core account and instruction layouts, written after mpl-core 0.12.
+//! They are the source of truth the hand written `Serialize` impls are checked against
+
+#![allow(dead_code)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+pub type Pubkey = [u8; 32];
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub percentage: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum RuleSet {
+    None,
+    ProgramAllowList(Vec<Pubkey>),
+    ProgramDenyList(Vec<Pubkey>),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Royalties {
+    pub basis_points: u16,
+    pub creators: Vec<Creator>,
+    pub rule_set: RuleSet,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct FreezeDelegate {
+    pub frozen: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct UpdateDelegate {
+    pub additional_delegates: Vec<Pubkey>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Groups {
+    pub groups: Vec<Pubkey>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Attribute {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Attributes {
+    pub attribute_list: Vec<Attribute>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Edition {
+    pub number: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MasterEdition {
+    pub max_supply: Option<u32>,
+    pub name: Option<String>,
+    pub uri: Option<String>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct VerifiedCreatorsSignature {
+    pub address: Pubkey,
+    pub verified: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct VerifiedCreators {
+    pub signatures: Vec<VerifiedCreatorsSignature>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct AutographSignature {
+    pub address: Pubkey,
+    pub message: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Autograph {
+    pub signatures: Vec<AutographSignature>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum Plugin {
+    Royalties(Royalties),
+    FreezeDelegate(FreezeDelegate),
+    BurnDelegate,
+    TransferDelegate,
+    UpdateDelegate(UpdateDelegate),
+    PermanentFreezeDelegate(FreezeDelegate),
+    Attributes(Attributes),
+    PermanentTransferDelegate,
+    PermanentBurnDelegate,
+    Edition(Edition),
+    MasterEdition(MasterEdition),
+    AddBlocker,
+    ImmutableMetadata,
+    VerifiedCreators(VerifiedCreators),
+    Autograph(Autograph),
+    BubblegumV2,
+    FreezeExecute(FreezeDelegate),
+    PermanentFreezeExecute(FreezeDelegate),
+    Groups(Groups),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PluginAuthority {
+    None,
+    Owner,
+    UpdateAuthority,
+    Address { address: Pubkey },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum UpdateAuthority {
+    None,
+    Address(Pubkey),
+    Collection(Pubkey),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PluginAuthorityPair {
+    pub plugin: Plugin,
+    pub authority: Option<PluginAuthority>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct HashablePluginSchema {
+    pub index: u64,
+    pub authority: PluginAuthority,
+    pub plugin: Plugin,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct CompressionProof {
+    pub owner: Pubkey,
+    pub update_authority: UpdateAuthority,
+    pub name: String,
+    pub uri: String,
+    pub seq: u64,
+    pub plugins: Vec<HashablePluginSchema>,
+}
+
+// instructions, each one is the instruction discriminant followed by its args
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum DataState {
+    AccountState,
+    LedgerState,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct CreateV1Args {
+    pub data_state: DataState,
+    pub name: String,
+    pub uri: String,
+    pub plugins: Option<Vec<PluginAuthorityPair>>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct CreateCollectionV1Args {
+    pub name: String,
+    pub uri: String,
+    pub plugins: Option<Vec<PluginAuthorityPair>>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct UpdatePluginV1Args {
+    pub plugin: Plugin,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct CompressionProofArgs {
+    pub compression_proof: Option<CompressionProof>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Instruction<T> {
+    pub discriminant: u8,
+    pub args: T,
+}
+
+// accounts
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Key {
+    Uninitialized,
+    AssetV1,
+    HashedAssetV1,
+    PluginHeaderV1,
+    PluginRegistryV1,
+    CollectionV1,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct BaseAssetV1 {
+    pub key: Key,
+    pub owner: Pubkey,
+    pub update_authority: UpdateAuthority,
+    pub name: String,
+    pub uri: String,
+    pub seq: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct BaseCollectionV1 {
+    pub key: Key,
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub uri: String,
+    pub num_minted: u32,
+    pub current_size: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PluginHeaderV1 {
+    pub key: Key,
+    pub plugin_registry_offset: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RegistryRecord {
+    pub plugin_type: u8,
+    pub authority: PluginAuthority,
+    pub offset: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum HookableLifecycleEvent {
+    Create,
+    Transfer,
+    Burn,
+    Update,
+    Execute,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ExternalCheckResult {
+    pub flags: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ExternalRegistryRecord {
+    pub plugin_type: u8,
+    pub authority: PluginAuthority,
+    pub lifecycle_checks: Option<Vec<(HookableLifecycleEvent, ExternalCheckResult)>>,
+    pub offset: u64,
+    pub data_offset: Option<u64>,
+    pub data_len: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PluginRegistryV1 {
+    pub key: Key,
+    pub registry: Vec<RegistryRecord>,
+    pub external_registry: Vec<ExternalRegistryRecord>,
+}
+
+// external plugin adapters
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ExternalPluginAdapterSchema {
+    Binary,
+    Json,
+    MsgPack,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum Seed {
+    Collection,
+    Owner,
+    Recipient,
+    Asset,
+    Address(Pubkey),
+    Bytes(Vec<u8>),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum ExtraAccount {
+    PreconfiguredProgram {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredCollection {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredOwner {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredRecipient {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    PreconfiguredAsset {
+        is_signer: bool,
+        is_writable: bool,
+    },
+    CustomPda {
+        seeds: Vec<Seed>,
+        custom_program_id: Option<Pubkey>,
+        is_signer: bool,
+        is_writable: bool,
+    },
+    Address {
+        address: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum ValidationResultsOffset {
+    NoOffset,
+    Anchor,
+    Custom(u64),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum LinkedDataKey {
+    LinkedLifecycleHook(Pubkey),
+    LinkedAppData(PluginAuthority),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct LifecycleHook {
+    pub hooked_program: Pubkey,
+    pub extra_accounts: Option<Vec<ExtraAccount>>,
+    pub data_authority: Option<PluginAuthority>,
+    pub schema: ExternalPluginAdapterSchema,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Oracle {
+    pub base_address: Pubkey,
+    pub base_address_config: Option<ExtraAccount>,
+    pub results_offset: ValidationResultsOffset,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct AppData {
+    pub data_authority: PluginAuthority,
+    pub schema: ExternalPluginAdapterSchema,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct DataSection {
+    pub parent_key: LinkedDataKey,
+    pub schema: ExternalPluginAdapterSchema,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct AgentIdentity {
+    pub uri: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum ExternalPluginAdapter {
+    LifecycleHook(LifecycleHook),
+    Oracle(Oracle),
+    AppData(AppData),
+    LinkedLifecycleHook(LifecycleHook),
+    LinkedAppData(AppData),
+    DataSection(DataSection),
+    AgentIdentity(AgentIdentity),
+}
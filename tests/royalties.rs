@@ -0,0 +1,298 @@
+//! Checks which royalties an asset resolves to, and how `RoyaltiesInfo::compute_payouts` splits a sale
+//! between the creators
+
+use mpl_core_pinocchio::{
+    data::{
+        asset::{
+            AssetInfo, BaseAssetV1, BaseCollectionV1, CollectionInfo, Key,
+            PluginAuthorityPairWithoutOption,
+        },
+        plugins::{
+            Creator, Plugin, PluginAuthority, Royalties, RoyaltiesInfo, RuleSet, UpdateAuthority,
+        },
+        view::{AssetView, CollectionView},
+        Serialize,
+    },
+    error::MplCoreError,
+};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+const ALICE: [u8; 32] = [1; 32];
+const BOB: [u8; 32] = [2; 32];
+const CAROL: [u8; 32] = [3; 32];
+const COLLECTION: [u8; 32] = [4; 32];
+const PROGRAMS: [Pubkey; 2] = [[5; 32], [6; 32]];
+
+fn to_bytes(info: &impl Serialize) -> Vec<u8> {
+    let mut bytes = vec![0; info.serialized_size()];
+    info.serialize_to(&mut bytes);
+    bytes
+}
+
+fn royalties<'a>(
+    basis_points: u16,
+    creators: &'a [Creator],
+    rule_set: RuleSet<'a>,
+) -> [PluginAuthorityPairWithoutOption<'a>; 1] {
+    [PluginAuthorityPairWithoutOption {
+        plugin: Plugin::Royalties(Royalties {
+            basis_points,
+            creators,
+            rule_set,
+        }),
+        authority: PluginAuthority::UpdateAuthority,
+    }]
+}
+
+/// An asset in COLLECTION, with `plugins`
+fn asset(plugins: &[PluginAuthorityPairWithoutOption]) -> Vec<u8> {
+    to_bytes(&AssetInfo {
+        base: BaseAssetV1 {
+            key: Key::AssetV1,
+            owner: ALICE,
+            update_authority: UpdateAuthority::Collection(COLLECTION),
+            name: b"asset",
+            uri: b"https://example.com/asset.json",
+            seq: None,
+        },
+        plugins,
+        external_plugins: &[],
+    })
+}
+
+fn collection(plugins: &[PluginAuthorityPairWithoutOption]) -> Vec<u8> {
+    to_bytes(&CollectionInfo {
+        base: BaseCollectionV1 {
+            key: Key::CollectionV1,
+            update_authority: BOB,
+            name: b"collection",
+            uri: b"https://example.com/collection.json",
+            num_minted: 1,
+            current_size: 1,
+        },
+        plugins,
+        external_plugins: &[],
+    })
+}
+
+/// The rule sets, each with the discriminant it must resolve to and its programs
+fn rule_sets() -> [(RuleSet<'static>, u8, &'static [Pubkey]); 3] {
+    [
+        (RuleSet::None, 0, &[]),
+        (RuleSet::ProgramAllowList(&PROGRAMS), 1, &PROGRAMS),
+        (RuleSet::ProgramDenyList(&PROGRAMS), 2, &PROGRAMS),
+    ]
+}
+
+/// The discriminant and programs of a rule set, which has no `PartialEq`
+fn rule_set_parts<'a>(rule_set: &RuleSet<'a>) -> (u8, &'a [Pubkey]) {
+    match *rule_set {
+        RuleSet::None => (0, &[]),
+        RuleSet::ProgramAllowList(programs) => (1, programs),
+        RuleSet::ProgramDenyList(programs) => (2, programs),
+    }
+}
+
+fn creators<const N: usize>(percentages: [u8; N]) -> Vec<Creator> {
+    [ALICE, BOB, CAROL]
+        .into_iter()
+        .zip(percentages)
+        .map(|(address, percentage)| Creator {
+            address,
+            percentage,
+        })
+        .collect()
+}
+
+fn payouts(basis_points: u16, creators: &[Creator], sale_price: u64) -> Vec<(Pubkey, u64)> {
+    RoyaltiesInfo {
+        basis_points,
+        creators,
+    }
+    .compute_payouts(sale_price)
+    .unwrap()
+    .collect()
+}
+
+fn payouts_error(basis_points: u16, creators: &[Creator]) -> ProgramError {
+    RoyaltiesInfo {
+        basis_points,
+        creators,
+    }
+    .compute_payouts(1_000)
+    .err()
+    .expect("compute_payouts should fail")
+}
+
+#[test]
+fn asset_royalties_resolve_with_their_rule_set() {
+    let asset_creators = creators([100]);
+    let collection_creators = creators([50, 50]);
+    let collection = collection(&royalties(
+        1_000,
+        &collection_creators,
+        RuleSet::ProgramDenyList(&PROGRAMS[..1]),
+    ));
+    let collection = CollectionView::new(&collection).unwrap();
+
+    for (rule_set, discriminant, programs) in rule_sets() {
+        let asset = asset(&royalties(500, &asset_creators, rule_set));
+        let asset = AssetView::new(&asset).unwrap();
+
+        // the asset's own royalties win, with or without the collection
+        for collection in [None, Some(&collection)] {
+            let royalties = Royalties::resolve(&asset, collection).unwrap().unwrap();
+            assert_eq!(royalties.basis_points, 500);
+            assert_eq!(royalties.creators, &asset_creators[..]);
+            assert_eq!(
+                rule_set_parts(&royalties.rule_set),
+                (discriminant, programs)
+            );
+
+            let info = RoyaltiesInfo::resolve(&asset, collection).unwrap();
+            assert_eq!(info.basis_points, 500);
+            assert_eq!(info.creators, &asset_creators[..]);
+        }
+    }
+}
+
+#[test]
+fn collection_royalties_resolve_with_their_rule_set() {
+    let collection_creators = creators([50, 50]);
+    let asset = asset(&[]);
+    let asset = AssetView::new(&asset).unwrap();
+
+    for (rule_set, discriminant, programs) in rule_sets() {
+        let collection = collection(&royalties(1_000, &collection_creators, rule_set));
+        let collection = CollectionView::new(&collection).unwrap();
+
+        let royalties = Royalties::resolve(&asset, Some(&collection))
+            .unwrap()
+            .unwrap();
+        assert_eq!(royalties.basis_points, 1_000);
+        assert_eq!(royalties.creators, &collection_creators[..]);
+        assert_eq!(
+            rule_set_parts(&royalties.rule_set),
+            (discriminant, programs)
+        );
+
+        let info = RoyaltiesInfo::resolve(&asset, Some(&collection)).unwrap();
+        assert_eq!(info.basis_points, 1_000);
+        assert_eq!(info.creators, &collection_creators[..]);
+    }
+
+    // no royalties anywhere
+    let collection = collection(&[]);
+    let collection = CollectionView::new(&collection).unwrap();
+    assert!(Royalties::resolve(&asset, Some(&collection))
+        .unwrap()
+        .is_none());
+    assert!(Royalties::resolve(&asset, None).unwrap().is_none());
+    assert!(RoyaltiesInfo::resolve(&asset, None).unwrap().is_empty());
+}
+
+#[test]
+fn rounding_remainder_goes_to_the_creator_crossing_it() {
+    // 5% of 1000 is 50, split 33/33/34 the exact shares are 16.5, 16.5 and 17
+    let thirds = creators([33, 33, 34]);
+    let royalties = RoyaltiesInfo {
+        basis_points: 500,
+        creators: &thirds,
+    };
+    assert_eq!(royalties.compute_payouts(1_000).unwrap().total(), 50);
+    // each creator is paid up to the rounded down cumulative share, so the half unit lands on BOB
+    assert_eq!(
+        payouts(500, &thirds, 1_000),
+        [(ALICE, 16), (BOB, 17), (CAROL, 17)]
+    );
+
+    // 10 split 34/33/33: the cumulative shares round down to 3, 6 and 10, the last creator gets the remainder
+    assert_eq!(
+        payouts(10_000, &creators([34, 33, 33]), 10),
+        [(ALICE, 3), (BOB, 3), (CAROL, 4)]
+    );
+
+    // the total itself rounds down, 1.5% of 99 is 1.485
+    assert_eq!(payouts(150, &creators([100]), 99), [(ALICE, 1)]);
+}
+
+#[test]
+fn shares_not_adding_up_to_100_are_rejected() {
+    let invalid = MplCoreError::InvalidCreatorShares.into();
+
+    assert_eq!(payouts_error(500, &creators([50, 40])), invalid);
+    assert_eq!(payouts_error(500, &creators([50, 40, 20])), invalid);
+    // 200 + 100 would overflow the u8 sum
+    assert_eq!(payouts_error(500, &creators([200, 100])), invalid);
+    // basis points with nobody to pay them to
+    assert_eq!(payouts_error(500, &[]), invalid);
+    // even without basis points, the creators given must be valid
+    assert_eq!(payouts_error(0, &creators([50])), invalid);
+
+    // no basis points and no creators is just no royalties
+    assert_eq!(payouts(0, &[], 1_000), []);
+}
+
+#[test]
+fn basis_points_above_10000_are_rejected() {
+    assert_eq!(
+        payouts_error(10_001, &creators([100])),
+        MplCoreError::InvalidBasisPoints.into()
+    );
+    assert_eq!(
+        payouts_error(u16::MAX, &creators([100])),
+        MplCoreError::InvalidBasisPoints.into()
+    );
+    // 100% is still allowed
+    assert_eq!(payouts(10_000, &creators([100]), 1_000), [(ALICE, 1_000)]);
+}
+
+#[test]
+fn zero_price_pays_every_creator_nothing() {
+    assert_eq!(
+        payouts(500, &creators([33, 33, 34]), 0),
+        [(ALICE, 0), (BOB, 0), (CAROL, 0)]
+    );
+}
+
+#[test]
+fn prices_near_u64_max_do_not_overflow() {
+    let split = creators([50, 25, 25]);
+    for (basis_points, sale_price) in [
+        (10_000, u64::MAX),
+        (9_999, u64::MAX),
+        (10_000, u64::MAX - 1),
+        (1, u64::MAX),
+    ] {
+        let royalties = RoyaltiesInfo {
+            basis_points,
+            creators: &split,
+        };
+        let total = royalties.compute_payouts(sale_price).unwrap().total();
+        assert_eq!(
+            u128::from(total),
+            u128::from(sale_price) * u128::from(basis_points) / 10_000
+        );
+
+        let paid: Vec<_> = royalties.compute_payouts(sale_price).unwrap().collect();
+        assert_eq!(paid.len(), 3);
+        assert_eq!(
+            paid.iter()
+                .map(|(_, amount)| u128::from(*amount))
+                .sum::<u128>(),
+            u128::from(total)
+        );
+    }
+
+    // the cumulative shares are computed in u128 too
+    let half = u64::MAX / 2;
+    let three_quarters = (u128::from(u64::MAX) * 75 / 100) as u64;
+    assert_eq!(
+        payouts(10_000, &split, u64::MAX),
+        [
+            (ALICE, half),
+            (BOB, three_quarters - half),
+            (CAROL, u64::MAX - three_quarters),
+        ]
+    );
+}
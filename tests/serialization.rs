@@ -0,0 +1,1741 @@
+//! Checks every `Serialize` impl byte for byte against the Borsh reference models in `reference`,
+//! and that the readers decode what Borsh wrote
+
+mod reference;
+
+use borsh::BorshSerialize;
+use mpl_core_pinocchio::data::{
+    asset::{
+        AssetInfo, BaseAssetV1, BaseCollectionV1, CollectionInfo, ExternalCheckResult,
+        ExternalRegistryRecordSafe, Key, PluginAuthorityPairWithoutOption, PluginHeaderV1,
+        PluginRegistryV1Safe, RegistryRecordSafe,
+    },
+    burn_asset::BurnAssetV1InstructionData,
+    burn_collection::BurnCollectionV1InstructionData,
+    create_asset::{CreateAssetV1InstructionData, DataState, PrintEditionV1InstructionData},
+    create_collection::CreateCollectionV1InstructionData,
+    external_plugins::{
+        AgentIdentity, AppData, DataSection, ExternalPluginAdapter, ExternalPluginAdapterSchema,
+        ExternalPluginAdapterWithData, ExtraAccount, HookableLifecycleEvent, LifecycleHook,
+        LinkedDataKey, Oracle, Seed, ValidationResultsOffset,
+    },
+    plugins::{
+        Attribute, AttributeOp, Attributes, AttributesView, Autograph, AutographSignature,
+        AutographView, CompressionProof, Creator, Edition, FreezeDelegate, FreezeExecute, Groups,
+        HashablePluginSchema, MasterEdition, PermanentFreezeDelegate, PermanentFreezeExecute,
+        Plugin, PluginAuthority, PluginAuthorityPair, Royalties, RuleSet, UpdateAuthority,
+        UpdateDelegate, VerifiedCreators, VerifiedCreatorsSignature, VerifiedCreatorsView,
+    },
+    transfer::TransferV1InstructionData,
+    update_asset_plugin::{
+        UpdateAssetPluginPatchV1InstructionData, UpdateAssetPluginV1InstructionData,
+    },
+    update_collection_plugin::UpdateCollectionPluginV1InstructionData,
+    view::{AssetView, CollectionView, ExternalRegistryIter},
+    DeserializeSized, Serialize,
+};
+use mpl_core_pinocchio::error::MplCoreError;
+
+use reference as r;
+
+const ALICE: [u8; 32] = [1; 32];
+const BOB: [u8; 32] = [2; 32];
+const PROGRAM: [u8; 32] = [3; 32];
+
+static CREATORS: [Creator; 2] = [
+    Creator {
+        address: ALICE,
+        percentage: 70,
+    },
+    Creator {
+        address: BOB,
+        percentage: 30,
+    },
+];
+static PROGRAMS: [[u8; 32]; 2] = [PROGRAM, BOB];
+static ATTRIBUTES: [Attribute; 2] = [
+    Attribute {
+        key: b"level",
+        value: b"7",
+    },
+    Attribute {
+        key: b"class",
+        value: b"mage",
+    },
+];
+static VERIFIED_CREATORS: [VerifiedCreatorsSignature; 2] = [
+    VerifiedCreatorsSignature {
+        address: ALICE,
+        verified: true,
+    },
+    VerifiedCreatorsSignature {
+        address: BOB,
+        verified: false,
+    },
+];
+static AUTOGRAPHS: [AutographSignature; 1] = [AutographSignature {
+    address: BOB,
+    message: b"gm",
+}];
+
+/// Serializes `ours` and compares it with what Borsh writes for `reference`.
+/// Also checks `serialized_size`, and that a buffer one byte too short is an error instead of a panic
+fn assert_encodes<T: Serialize + ?Sized>(ours: &T, reference: &impl BorshSerialize) {
+    let expected = borsh::to_vec(reference).unwrap();
+    assert_eq!(ours.serialized_size(), expected.len(), "serialized_size");
+
+    let mut buffer = vec![0xAA; expected.len()];
+    let len = ours.try_serialize_to(&mut buffer).unwrap();
+    assert_eq!(len, expected.len(), "written length");
+    assert_eq!(buffer, expected);
+
+    if len > 0 {
+        assert!(ours.try_serialize_to(&mut buffer[..len - 1]).is_err());
+    }
+}
+
+fn ref_creators() -> Vec<r::Creator> {
+    CREATORS
+        .iter()
+        .map(|creator| r::Creator {
+            address: creator.address,
+            percentage: creator.percentage,
+        })
+        .collect()
+}
+
+/// Every plugin variant, and every rule set, next to its reference
+fn plugins() -> Vec<(Plugin<'static>, r::Plugin)> {
+    let rule_sets = [
+        (RuleSet::None, r::RuleSet::None),
+        (
+            RuleSet::ProgramAllowList(&PROGRAMS),
+            r::RuleSet::ProgramAllowList(PROGRAMS.to_vec()),
+        ),
+        (
+            RuleSet::ProgramDenyList(&PROGRAMS),
+            r::RuleSet::ProgramDenyList(PROGRAMS.to_vec()),
+        ),
+    ];
+
+    let mut plugins: Vec<_> = rule_sets
+        .into_iter()
+        .map(|(rule_set, ref_rule_set)| {
+            (
+                Plugin::Royalties(Royalties {
+                    basis_points: 500,
+                    creators: &CREATORS,
+                    rule_set,
+                }),
+                r::Plugin::Royalties(r::Royalties {
+                    basis_points: 500,
+                    creators: ref_creators(),
+                    rule_set: ref_rule_set,
+                }),
+            )
+        })
+        .collect();
+
+    plugins.extend([
+        (
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+            r::Plugin::FreezeDelegate(r::FreezeDelegate { frozen: true }),
+        ),
+        (Plugin::BurnDelegate, r::Plugin::BurnDelegate),
+        (Plugin::TransferDelegate, r::Plugin::TransferDelegate),
+        (
+            Plugin::UpdateDelegate(UpdateDelegate {
+                additional_delegates: &PROGRAMS,
+            }),
+            r::Plugin::UpdateDelegate(r::UpdateDelegate {
+                additional_delegates: PROGRAMS.to_vec(),
+            }),
+        ),
+        (
+            Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false }),
+            r::Plugin::PermanentFreezeDelegate(r::FreezeDelegate { frozen: false }),
+        ),
+        (
+            Plugin::Attributes(Attributes {
+                attribute_list: &ATTRIBUTES,
+            }),
+            r::Plugin::Attributes(r::Attributes {
+                attribute_list: vec![
+                    r::Attribute {
+                        key: "level".into(),
+                        value: "7".into(),
+                    },
+                    r::Attribute {
+                        key: "class".into(),
+                        value: "mage".into(),
+                    },
+                ],
+            }),
+        ),
+        (
+            Plugin::PermanentTransferDelegate,
+            r::Plugin::PermanentTransferDelegate,
+        ),
+        (
+            Plugin::PermanentBurnDelegate,
+            r::Plugin::PermanentBurnDelegate,
+        ),
+        (
+            Plugin::Edition(Edition { number: 42 }),
+            r::Plugin::Edition(r::Edition { number: 42 }),
+        ),
+        (
+            Plugin::MasterEdition(MasterEdition {
+                max_supply: Some(100),
+                name: Some(b"master"),
+                uri: None,
+            }),
+            r::Plugin::MasterEdition(r::MasterEdition {
+                max_supply: Some(100),
+                name: Some("master".into()),
+                uri: None,
+            }),
+        ),
+        (Plugin::AddBlocker, r::Plugin::AddBlocker),
+        (Plugin::ImmutableMetadata, r::Plugin::ImmutableMetadata),
+        (
+            Plugin::VerifiedCreators(VerifiedCreators {
+                signatures: &VERIFIED_CREATORS,
+            }),
+            r::Plugin::VerifiedCreators(r::VerifiedCreators {
+                signatures: vec![
+                    r::VerifiedCreatorsSignature {
+                        address: ALICE,
+                        verified: true,
+                    },
+                    r::VerifiedCreatorsSignature {
+                        address: BOB,
+                        verified: false,
+                    },
+                ],
+            }),
+        ),
+        (
+            Plugin::Autograph(Autograph {
+                signatures: &AUTOGRAPHS,
+            }),
+            r::Plugin::Autograph(r::Autograph {
+                signatures: vec![r::AutographSignature {
+                    address: BOB,
+                    message: "gm".into(),
+                }],
+            }),
+        ),
+        (Plugin::BubblegumV2, r::Plugin::BubblegumV2),
+        (
+            Plugin::FreezeExecute(FreezeExecute { frozen: true }),
+            r::Plugin::FreezeExecute(r::FreezeDelegate { frozen: true }),
+        ),
+        (
+            Plugin::PermanentFreezeExecute(PermanentFreezeExecute { frozen: true }),
+            r::Plugin::PermanentFreezeExecute(r::FreezeDelegate { frozen: true }),
+        ),
+        (
+            Plugin::Groups(Groups { groups: &PROGRAMS }),
+            r::Plugin::Groups(r::Groups {
+                groups: PROGRAMS.to_vec(),
+            }),
+        ),
+    ]);
+
+    plugins
+}
+
+fn authorities() -> [(PluginAuthority, r::PluginAuthority); 4] {
+    [
+        (PluginAuthority::None, r::PluginAuthority::None),
+        (PluginAuthority::Owner, r::PluginAuthority::Owner),
+        (
+            PluginAuthority::UpdateAuthority,
+            r::PluginAuthority::UpdateAuthority,
+        ),
+        (
+            PluginAuthority::Address(ALICE),
+            r::PluginAuthority::Address { address: ALICE },
+        ),
+    ]
+}
+
+fn update_authorities() -> [(UpdateAuthority, r::UpdateAuthority); 3] {
+    [
+        (UpdateAuthority::None, r::UpdateAuthority::None),
+        (
+            UpdateAuthority::Address(ALICE),
+            r::UpdateAuthority::Address(ALICE),
+        ),
+        (
+            UpdateAuthority::Collection(BOB),
+            r::UpdateAuthority::Collection(BOB),
+        ),
+    ]
+}
+
+fn instruction<T>(discriminant: u8, args: T) -> r::Instruction<T> {
+    r::Instruction { discriminant, args }
+}
+
+#[test]
+fn every_plugin_variant() {
+    let plugins = plugins();
+    // every variant is covered, royalties three times for the rule sets
+    assert_eq!(plugins.len(), 19 + 2);
+
+    for (plugin, reference) in &plugins {
+        assert_encodes(plugin, reference);
+    }
+}
+
+#[test]
+fn plugin_and_update_authorities_round_trip() {
+    for (authority, reference) in authorities() {
+        assert_encodes(&authority, &reference);
+        let bytes = borsh::to_vec(&reference).unwrap();
+        assert_eq!(PluginAuthority::deserialize(&bytes).unwrap(), authority);
+    }
+
+    for (update_authority, reference) in update_authorities() {
+        assert_encodes(&update_authority, &reference);
+        let bytes = borsh::to_vec(&reference).unwrap();
+        assert_eq!(
+            UpdateAuthority::deserialize(&bytes).unwrap(),
+            update_authority
+        );
+    }
+}
+
+#[test]
+fn plugin_authority_pairs() {
+    let authorities = authorities()
+        .map(|(authority, reference)| (Some(authority), Some(reference)))
+        .into_iter()
+        .chain([(None, None)]);
+
+    for (authority, ref_authority) in authorities {
+        for (plugin, reference) in plugins() {
+            assert_encodes(
+                &PluginAuthorityPair { plugin, authority },
+                &r::PluginAuthorityPair {
+                    plugin: reference,
+                    authority: ref_authority,
+                },
+            );
+        }
+    }
+}
+
+#[test]
+fn create_asset_instruction() {
+    let (plugins, ref_plugins): (Vec<_>, Vec<_>) = plugins()
+        .into_iter()
+        .map(|(plugin, reference)| {
+            (
+                PluginAuthorityPair {
+                    plugin,
+                    authority: Some(PluginAuthority::UpdateAuthority),
+                },
+                r::PluginAuthorityPair {
+                    plugin: reference,
+                    authority: Some(r::PluginAuthority::UpdateAuthority),
+                },
+            )
+        })
+        .unzip();
+
+    for (data_state, ref_data_state) in [
+        (DataState::AccountState, r::DataState::AccountState),
+        (DataState::LedgerState, r::DataState::LedgerState),
+    ] {
+        for with_plugins in [false, true] {
+            assert_encodes(
+                &CreateAssetV1InstructionData {
+                    data_state,
+                    name: b"asset",
+                    uri: b"https://example.com",
+                    plugins: with_plugins.then_some(&plugins[..]),
+                },
+                &instruction(
+                    0,
+                    r::CreateV1Args {
+                        data_state: ref_data_state,
+                        name: "asset".into(),
+                        uri: "https://example.com".into(),
+                        plugins: with_plugins.then(|| ref_plugins.clone()),
+                    },
+                ),
+            );
+        }
+    }
+
+    for with_plugins in [false, true] {
+        assert_encodes(
+            &CreateCollectionV1InstructionData {
+                name: b"collection",
+                uri: b"",
+                plugins: with_plugins.then_some(&plugins[..]),
+            },
+            &instruction(
+                1,
+                r::CreateCollectionV1Args {
+                    name: "collection".into(),
+                    uri: "".into(),
+                    plugins: with_plugins.then(|| ref_plugins.clone()),
+                },
+            ),
+        );
+    }
+}
+
+#[test]
+fn update_plugin_instructions() {
+    for (plugin, reference) in plugins() {
+        assert_encodes(
+            &UpdateAssetPluginV1InstructionData { plugin },
+            &instruction(
+                6,
+                r::UpdatePluginV1Args {
+                    plugin: reference.clone(),
+                },
+            ),
+        );
+    }
+
+    for (plugin, reference) in plugins() {
+        assert_encodes(
+            &UpdateCollectionPluginV1InstructionData { plugin },
+            &instruction(7, r::UpdatePluginV1Args { plugin: reference }),
+        );
+    }
+}
+
+#[test]
+fn compression_proof_instructions() {
+    let (schemas, ref_schemas): (Vec<_>, Vec<_>) = plugins()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (plugin, reference))| {
+            (
+                HashablePluginSchema {
+                    index: index as u64,
+                    authority: PluginAuthority::Owner,
+                    plugin,
+                },
+                r::HashablePluginSchema {
+                    index: index as u64,
+                    authority: r::PluginAuthority::Owner,
+                    plugin: reference,
+                },
+            )
+        })
+        .unzip();
+
+    let proof = || CompressionProof {
+        owner: ALICE,
+        update_authority: UpdateAuthority::Collection(BOB),
+        name: b"compressed",
+        uri: b"uri",
+        seq: 9,
+        plugins: &schemas,
+    };
+    let ref_proof = r::CompressionProof {
+        owner: ALICE,
+        update_authority: r::UpdateAuthority::Collection(BOB),
+        name: "compressed".into(),
+        uri: "uri".into(),
+        seq: 9,
+        plugins: ref_schemas,
+    };
+
+    for with_proof in [false, true] {
+        let args = r::CompressionProofArgs {
+            compression_proof: with_proof.then(|| ref_proof.clone()),
+        };
+
+        assert_encodes(
+            &TransferV1InstructionData {
+                compression_proof: with_proof.then(proof),
+            },
+            &instruction(14, args.clone()),
+        );
+        assert_encodes(
+            &BurnAssetV1InstructionData {
+                compression_proof: with_proof.then(proof),
+            },
+            &instruction(12, args.clone()),
+        );
+        assert_encodes(
+            &BurnCollectionV1InstructionData {
+                compression_proof: with_proof.then(proof),
+            },
+            &instruction(13, args),
+        );
+    }
+}
+
+#[test]
+fn registry_types() {
+    for (key, reference) in [
+        (Key::Uninitialized, r::Key::Uninitialized),
+        (Key::AssetV1, r::Key::AssetV1),
+        (Key::HashedAssetV1, r::Key::HashedAssetV1),
+        (Key::PluginHeaderV1, r::Key::PluginHeaderV1),
+        (Key::PluginRegistryV1, r::Key::PluginRegistryV1),
+        (Key::CollectionV1, r::Key::CollectionV1),
+    ] {
+        assert_encodes(&key, &reference);
+    }
+
+    assert_encodes(
+        &PluginHeaderV1 {
+            key: Key::PluginHeaderV1,
+            plugin_registry_offset: 1234,
+        },
+        &r::PluginHeaderV1 {
+            key: r::Key::PluginHeaderV1,
+            plugin_registry_offset: 1234,
+        },
+    );
+
+    let records: Vec<_> = authorities()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (authority, _))| RegistryRecordSafe {
+            plugin_type: index as u8,
+            authority,
+            offset: 100 * index as u64,
+        })
+        .collect();
+    let ref_records: Vec<_> = authorities()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, authority))| r::RegistryRecord {
+            plugin_type: index as u8,
+            authority,
+            offset: 100 * index as u64,
+        })
+        .collect();
+
+    let checks = [
+        (
+            HookableLifecycleEvent::Create,
+            ExternalCheckResult { flags: 1 },
+        ),
+        (
+            HookableLifecycleEvent::Transfer,
+            ExternalCheckResult { flags: 6 },
+        ),
+        (
+            HookableLifecycleEvent::Burn,
+            ExternalCheckResult { flags: 2 },
+        ),
+        (
+            HookableLifecycleEvent::Update,
+            ExternalCheckResult { flags: 4 },
+        ),
+        (
+            HookableLifecycleEvent::Execute,
+            ExternalCheckResult { flags: 7 },
+        ),
+    ];
+    let ref_checks = vec![
+        (
+            r::HookableLifecycleEvent::Create,
+            r::ExternalCheckResult { flags: 1 },
+        ),
+        (
+            r::HookableLifecycleEvent::Transfer,
+            r::ExternalCheckResult { flags: 6 },
+        ),
+        (
+            r::HookableLifecycleEvent::Burn,
+            r::ExternalCheckResult { flags: 2 },
+        ),
+        (
+            r::HookableLifecycleEvent::Update,
+            r::ExternalCheckResult { flags: 4 },
+        ),
+        (
+            r::HookableLifecycleEvent::Execute,
+            r::ExternalCheckResult { flags: 7 },
+        ),
+    ];
+    let external_records = [
+        ExternalRegistryRecordSafe {
+            plugin_type: 0,
+            authority: PluginAuthority::UpdateAuthority,
+            lifecycle_checks: Some(&checks),
+            offset: 300,
+            data_offset: None,
+            data_len: None,
+        },
+        ExternalRegistryRecordSafe {
+            plugin_type: 2,
+            authority: PluginAuthority::Address(BOB),
+            lifecycle_checks: None,
+            offset: 400,
+            data_offset: Some(450),
+            data_len: Some(12),
+        },
+    ];
+    let ref_external_records = vec![
+        r::ExternalRegistryRecord {
+            plugin_type: 0,
+            authority: r::PluginAuthority::UpdateAuthority,
+            lifecycle_checks: Some(ref_checks),
+            offset: 300,
+            data_offset: None,
+            data_len: None,
+        },
+        r::ExternalRegistryRecord {
+            plugin_type: 2,
+            authority: r::PluginAuthority::Address { address: BOB },
+            lifecycle_checks: None,
+            offset: 400,
+            data_offset: Some(450),
+            data_len: Some(12),
+        },
+    ];
+
+    assert_encodes(
+        &PluginRegistryV1Safe {
+            key: Key::PluginRegistryV1,
+            registry: &records,
+            external_registry: &external_records,
+        },
+        &r::PluginRegistryV1 {
+            key: r::Key::PluginRegistryV1,
+            registry: ref_records,
+            external_registry: ref_external_records,
+        },
+    );
+}
+
+fn external_adapters() -> Vec<(ExternalPluginAdapter<'static>, r::ExternalPluginAdapter)> {
+    static SEEDS: [Seed; 6] = [
+        Seed::Collection,
+        Seed::Owner,
+        Seed::Recipient,
+        Seed::Asset,
+        Seed::Address(ALICE),
+        Seed::Bytes(b"seed"),
+    ];
+    static EXTRA_ACCOUNTS: [ExtraAccount; 7] = [
+        ExtraAccount::PreconfiguredProgram {
+            is_signer: false,
+            is_writable: true,
+        },
+        ExtraAccount::PreconfiguredCollection {
+            is_signer: true,
+            is_writable: false,
+        },
+        ExtraAccount::PreconfiguredOwner {
+            is_signer: false,
+            is_writable: false,
+        },
+        ExtraAccount::PreconfiguredRecipient {
+            is_signer: true,
+            is_writable: true,
+        },
+        ExtraAccount::PreconfiguredAsset {
+            is_signer: false,
+            is_writable: true,
+        },
+        ExtraAccount::CustomPda {
+            seeds: &SEEDS,
+            custom_program_id: Some(PROGRAM),
+            is_signer: false,
+            is_writable: true,
+        },
+        ExtraAccount::Address {
+            address: BOB,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    let ref_extra_accounts = vec![
+        r::ExtraAccount::PreconfiguredProgram {
+            is_signer: false,
+            is_writable: true,
+        },
+        r::ExtraAccount::PreconfiguredCollection {
+            is_signer: true,
+            is_writable: false,
+        },
+        r::ExtraAccount::PreconfiguredOwner {
+            is_signer: false,
+            is_writable: false,
+        },
+        r::ExtraAccount::PreconfiguredRecipient {
+            is_signer: true,
+            is_writable: true,
+        },
+        r::ExtraAccount::PreconfiguredAsset {
+            is_signer: false,
+            is_writable: true,
+        },
+        r::ExtraAccount::CustomPda {
+            seeds: vec![
+                r::Seed::Collection,
+                r::Seed::Owner,
+                r::Seed::Recipient,
+                r::Seed::Asset,
+                r::Seed::Address(ALICE),
+                r::Seed::Bytes(b"seed".to_vec()),
+            ],
+            custom_program_id: Some(PROGRAM),
+            is_signer: false,
+            is_writable: true,
+        },
+        r::ExtraAccount::Address {
+            address: BOB,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    let hook = || LifecycleHook {
+        hooked_program: PROGRAM,
+        extra_accounts: Some(&EXTRA_ACCOUNTS),
+        data_authority: Some(PluginAuthority::Owner),
+        schema: ExternalPluginAdapterSchema::Json,
+    };
+    let ref_hook = r::LifecycleHook {
+        hooked_program: PROGRAM,
+        extra_accounts: Some(ref_extra_accounts),
+        data_authority: Some(r::PluginAuthority::Owner),
+        schema: r::ExternalPluginAdapterSchema::Json,
+    };
+
+    let mut adapters = vec![
+        (
+            ExternalPluginAdapter::LifecycleHook(hook()),
+            r::ExternalPluginAdapter::LifecycleHook(ref_hook.clone()),
+        ),
+        (
+            ExternalPluginAdapter::LifecycleHook(LifecycleHook {
+                hooked_program: PROGRAM,
+                extra_accounts: None,
+                data_authority: None,
+                schema: ExternalPluginAdapterSchema::Binary,
+            }),
+            r::ExternalPluginAdapter::LifecycleHook(r::LifecycleHook {
+                hooked_program: PROGRAM,
+                extra_accounts: None,
+                data_authority: None,
+                schema: r::ExternalPluginAdapterSchema::Binary,
+            }),
+        ),
+        (
+            ExternalPluginAdapter::LinkedLifecycleHook(hook()),
+            r::ExternalPluginAdapter::LinkedLifecycleHook(ref_hook),
+        ),
+        (
+            ExternalPluginAdapter::AppData(AppData {
+                data_authority: PluginAuthority::Address(ALICE),
+                schema: ExternalPluginAdapterSchema::MsgPack,
+            }),
+            r::ExternalPluginAdapter::AppData(r::AppData {
+                data_authority: r::PluginAuthority::Address { address: ALICE },
+                schema: r::ExternalPluginAdapterSchema::MsgPack,
+            }),
+        ),
+        (
+            ExternalPluginAdapter::LinkedAppData(AppData {
+                data_authority: PluginAuthority::UpdateAuthority,
+                schema: ExternalPluginAdapterSchema::Binary,
+            }),
+            r::ExternalPluginAdapter::LinkedAppData(r::AppData {
+                data_authority: r::PluginAuthority::UpdateAuthority,
+                schema: r::ExternalPluginAdapterSchema::Binary,
+            }),
+        ),
+        (
+            ExternalPluginAdapter::DataSection(DataSection {
+                parent_key: LinkedDataKey::LinkedLifecycleHook(PROGRAM),
+                schema: ExternalPluginAdapterSchema::Json,
+            }),
+            r::ExternalPluginAdapter::DataSection(r::DataSection {
+                parent_key: r::LinkedDataKey::LinkedLifecycleHook(PROGRAM),
+                schema: r::ExternalPluginAdapterSchema::Json,
+            }),
+        ),
+        (
+            ExternalPluginAdapter::DataSection(DataSection {
+                parent_key: LinkedDataKey::LinkedAppData(PluginAuthority::Address(BOB)),
+                schema: ExternalPluginAdapterSchema::Binary,
+            }),
+            r::ExternalPluginAdapter::DataSection(r::DataSection {
+                parent_key: r::LinkedDataKey::LinkedAppData(r::PluginAuthority::Address {
+                    address: BOB,
+                }),
+                schema: r::ExternalPluginAdapterSchema::Binary,
+            }),
+        ),
+        (
+            ExternalPluginAdapter::AgentIdentity(AgentIdentity { uri: b"agent" }),
+            r::ExternalPluginAdapter::AgentIdentity(r::AgentIdentity {
+                uri: "agent".into(),
+            }),
+        ),
+    ];
+
+    for (results_offset, ref_results_offset) in [
+        (
+            ValidationResultsOffset::NoOffset,
+            r::ValidationResultsOffset::NoOffset,
+        ),
+        (
+            ValidationResultsOffset::Anchor,
+            r::ValidationResultsOffset::Anchor,
+        ),
+        (
+            ValidationResultsOffset::Custom(48),
+            r::ValidationResultsOffset::Custom(48),
+        ),
+    ] {
+        adapters.push((
+            ExternalPluginAdapter::Oracle(Oracle {
+                base_address: PROGRAM,
+                base_address_config: Some(ExtraAccount::Address {
+                    address: ALICE,
+                    is_signer: false,
+                    is_writable: false,
+                }),
+                results_offset,
+            }),
+            r::ExternalPluginAdapter::Oracle(r::Oracle {
+                base_address: PROGRAM,
+                base_address_config: Some(r::ExtraAccount::Address {
+                    address: ALICE,
+                    is_signer: false,
+                    is_writable: false,
+                }),
+                results_offset: ref_results_offset,
+            }),
+        ));
+    }
+
+    adapters
+}
+
+#[test]
+fn every_external_plugin_adapter() {
+    for (adapter, reference) in &external_adapters() {
+        assert_encodes(adapter, reference);
+    }
+}
+
+/// Builds what an asset or collection account looks like with the reference models, the way mpl core
+/// lays it out: base asset or collection, header, plugins, external adapters each followed by their data, registry
+fn reference_account(
+    base: &impl BorshSerialize,
+    plugins: &[(r::Plugin, r::PluginAuthority)],
+    external: &[(
+        r::ExternalPluginAdapter,
+        r::PluginAuthority,
+        Option<Vec<u8>>,
+    )],
+) -> Vec<u8> {
+    let mut account = borsh::to_vec(base).unwrap();
+    if plugins.is_empty() && external.is_empty() {
+        return account;
+    }
+
+    let header_offset = account.len();
+    account.extend([0; 9]);
+
+    let mut registry = Vec::new();
+    for (plugin, authority) in plugins {
+        let offset = account.len() as u64;
+        let bytes = borsh::to_vec(plugin).unwrap();
+        registry.push(r::RegistryRecord {
+            plugin_type: bytes[0],
+            authority: *authority,
+            offset,
+        });
+        account.extend(bytes);
+    }
+
+    let mut external_registry = Vec::new();
+    for (adapter, authority, data) in external {
+        let offset = account.len() as u64;
+        let bytes = borsh::to_vec(adapter).unwrap();
+        let data_offset = offset + bytes.len() as u64;
+        external_registry.push(r::ExternalRegistryRecord {
+            plugin_type: bytes[0],
+            authority: *authority,
+            lifecycle_checks: None,
+            offset,
+            data_offset: data.as_ref().map(|_| data_offset),
+            data_len: data.as_ref().map(|data| data.len() as u64),
+        });
+        account.extend(bytes);
+        account.extend(data.iter().flatten());
+    }
+
+    let registry_offset = account.len() as u64;
+    account.extend(
+        borsh::to_vec(&r::PluginRegistryV1 {
+            key: r::Key::PluginRegistryV1,
+            registry,
+            external_registry,
+        })
+        .unwrap(),
+    );
+
+    let header = borsh::to_vec(&r::PluginHeaderV1 {
+        key: r::Key::PluginHeaderV1,
+        plugin_registry_offset: registry_offset,
+    })
+    .unwrap();
+    account[header_offset..header_offset + 9].copy_from_slice(&header);
+
+    account
+}
+
+/// `reference_account` already is the serialized form, this just writes it as is
+struct Raw(Vec<u8>);
+
+impl BorshSerialize for Raw {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.0)
+    }
+}
+
+#[test]
+fn asset_info_account_image() {
+    for (update_authority, ref_update_authority) in update_authorities() {
+        for seq in [None, Some(3)] {
+            let base = || BaseAssetV1 {
+                key: Key::AssetV1,
+                owner: ALICE,
+                update_authority,
+                name: b"asset",
+                uri: b"https://example.com",
+                seq,
+            };
+            let ref_base = r::BaseAssetV1 {
+                key: r::Key::AssetV1,
+                owner: ALICE,
+                update_authority: ref_update_authority,
+                name: "asset".into(),
+                uri: "https://example.com".into(),
+                seq,
+            };
+
+            // no plugins at all, so no header or registry either
+            assert_encodes(
+                &AssetInfo {
+                    base: base(),
+                    plugins: &[],
+                    external_plugins: &[],
+                },
+                &Raw(reference_account(&ref_base, &[], &[])),
+            );
+
+            // more than the 16 plugins the old implementation could hold
+            let (plugins, ref_plugins): (Vec<_>, Vec<_>) = plugins()
+                .into_iter()
+                .zip(authorities().into_iter().cycle())
+                .map(|((plugin, reference), (authority, ref_authority))| {
+                    (
+                        PluginAuthorityPairWithoutOption { plugin, authority },
+                        (reference, ref_authority),
+                    )
+                })
+                .unzip();
+            assert!(plugins.len() > 16);
+
+            let (external, ref_external): (Vec<_>, Vec<_>) = external_adapters()
+                .into_iter()
+                .enumerate()
+                .map(|(index, (adapter, reference))| {
+                    let data: Option<&'static [u8]> = (index % 2 == 0).then_some(b"{\"a\":1}");
+                    (
+                        ExternalPluginAdapterWithData {
+                            adapter,
+                            authority: PluginAuthority::UpdateAuthority,
+                            lifecycle_checks: None,
+                            data,
+                        },
+                        (
+                            reference,
+                            r::PluginAuthority::UpdateAuthority,
+                            data.map(<[u8]>::to_vec),
+                        ),
+                    )
+                })
+                .unzip();
+
+            let info = AssetInfo {
+                base: base(),
+                plugins: &plugins,
+                external_plugins: &external,
+            };
+            let expected = reference_account(&ref_base, &ref_plugins, &ref_external);
+            assert_encodes(&info, &Raw(expected.clone()));
+
+            // and the view reads it back
+            let view = AssetView::new(&expected).unwrap();
+            assert_eq!(view.update_authority, update_authority);
+            assert_eq!(view.seq, seq);
+            assert_eq!(view.plugins().count(), plugins.len());
+            for (record, pair) in view.plugins().zip(&plugins) {
+                let record = record.unwrap();
+                assert_eq!(record.plugin_type, pair.plugin.get_plugin_number());
+                assert_eq!(record.authority, pair.authority);
+            }
+            assert_external_records(view.external_plugins().unwrap(), &external);
+
+            // every adapter survives the owned mirror
+            #[cfg(feature = "alloc")]
+            assert_eq!(
+                mpl_core_pinocchio::data::owned::OwnedAsset::try_from(&view)
+                    .unwrap()
+                    .to_bytes()
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+}
+
+/// The external registry the view reads matches the adapters the account was written with
+fn assert_external_records(
+    records: ExternalRegistryIter,
+    external: &[ExternalPluginAdapterWithData],
+) {
+    let records: Vec<_> = records.map(Result::unwrap).collect();
+    assert_eq!(records.len(), external.len());
+    for (record, external) in records.iter().zip(external) {
+        assert_eq!(record.plugin_type, external.adapter.adapter_type() as u8);
+        assert_eq!(record.authority, external.authority);
+        assert_eq!(record.data, external.data);
+        assert_eq!(
+            record.lifecycle_checks.map(|checks| checks.len()),
+            external.lifecycle_checks.map(<[_]>::len)
+        );
+        let mut adapter = vec![0; external.adapter.serialized_size()];
+        external.adapter.serialize_to(&mut adapter);
+        assert_eq!(&record.adapter[..adapter.len() - 1], &adapter[1..]);
+    }
+}
+
+#[test]
+fn collection_info_account_image() {
+    let base = || BaseCollectionV1 {
+        key: Key::CollectionV1,
+        update_authority: BOB,
+        name: b"collection",
+        uri: b"https://example.com/collection.json",
+        num_minted: 7,
+        current_size: 5,
+    };
+    let ref_base = r::BaseCollectionV1 {
+        key: r::Key::CollectionV1,
+        update_authority: BOB,
+        name: "collection".into(),
+        uri: "https://example.com/collection.json".into(),
+        num_minted: 7,
+        current_size: 5,
+    };
+    assert_encodes(&base(), &ref_base);
+
+    // no plugins at all, so no header or registry either
+    assert_encodes(
+        &CollectionInfo {
+            base: base(),
+            plugins: &[],
+            external_plugins: &[],
+        },
+        &ref_base,
+    );
+
+    let (plugins, ref_plugins): (Vec<_>, Vec<_>) = plugins()
+        .into_iter()
+        .zip(authorities().into_iter().cycle())
+        .map(|((plugin, reference), (authority, ref_authority))| {
+            (
+                PluginAuthorityPairWithoutOption { plugin, authority },
+                (reference, ref_authority),
+            )
+        })
+        .unzip();
+    let (external, ref_external): (Vec<_>, Vec<_>) = external_adapters()
+        .into_iter()
+        .map(|(adapter, reference)| {
+            (
+                ExternalPluginAdapterWithData {
+                    adapter,
+                    authority: PluginAuthority::UpdateAuthority,
+                    lifecycle_checks: None,
+                    data: Some(b"data"),
+                },
+                (
+                    reference,
+                    r::PluginAuthority::UpdateAuthority,
+                    Some(b"data".to_vec()),
+                ),
+            )
+        })
+        .unzip();
+
+    let info = CollectionInfo {
+        base: base(),
+        plugins: &plugins,
+        external_plugins: &external,
+    };
+    let expected = reference_account(&ref_base, &ref_plugins, &ref_external);
+    assert_encodes(&info, &Raw(expected.clone()));
+
+    // and the view reads it back
+    let view = CollectionView::new(&expected).unwrap();
+    assert_eq!(view.update_authority, &BOB);
+    assert_eq!(view.num_minted, 7);
+    assert_eq!(view.current_size, 5);
+    assert_eq!(view.plugins().count(), plugins.len());
+    for (record, pair) in view.plugins().zip(&plugins) {
+        let record = record.unwrap();
+        assert_eq!(record.plugin_type, pair.plugin.get_plugin_number());
+        assert_eq!(record.authority, pair.authority);
+    }
+    assert_external_records(view.external_plugins().unwrap(), &external);
+
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        mpl_core_pinocchio::data::owned::OwnedCollection::try_from(&view)
+            .unwrap()
+            .to_bytes()
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn external_registry_lifecycle_checks() {
+    let checks = [
+        (
+            HookableLifecycleEvent::Create,
+            ExternalCheckResult { flags: 1 },
+        ),
+        (
+            HookableLifecycleEvent::Transfer,
+            ExternalCheckResult { flags: 2 },
+        ),
+        (
+            HookableLifecycleEvent::Burn,
+            ExternalCheckResult { flags: 4 },
+        ),
+        (
+            HookableLifecycleEvent::Update,
+            ExternalCheckResult { flags: 5 },
+        ),
+        (
+            HookableLifecycleEvent::Execute,
+            ExternalCheckResult { flags: 0 },
+        ),
+    ];
+    let external: Vec<_> = external_adapters()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (adapter, _))| ExternalPluginAdapterWithData {
+            adapter,
+            authority: PluginAuthority::Address(PROGRAM),
+            lifecycle_checks: match index % 3 {
+                0 => None,
+                1 => Some(&[]),
+                _ => Some(&checks[..index % checks.len() + 1]),
+            },
+            data: (index % 2 == 1).then_some(b""),
+        })
+        .collect();
+    let info = AssetInfo {
+        base: BaseAssetV1 {
+            key: Key::AssetV1,
+            owner: ALICE,
+            update_authority: UpdateAuthority::None,
+            name: b"hooked",
+            uri: b"",
+            seq: None,
+        },
+        plugins: &[],
+        external_plugins: &external,
+    };
+    let mut bytes = vec![0; info.serialized_size()];
+    info.serialize_to(&mut bytes);
+
+    let view = AssetView::new(&bytes).unwrap();
+    assert_eq!(view.plugins().count(), 0);
+    assert_external_records(view.external_plugins().unwrap(), &external);
+    for (record, external) in view.external_plugins().unwrap().zip(&external) {
+        let read: Option<Vec<_>> = record
+            .unwrap()
+            .lifecycle_checks
+            .map(|checks| checks.iter().map(Result::unwrap).collect());
+        assert_eq!(read.as_deref(), external.lifecycle_checks);
+    }
+
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        mpl_core_pinocchio::data::owned::OwnedAsset::try_from(&view)
+            .unwrap()
+            .to_bytes()
+            .unwrap(),
+        bytes
+    );
+
+    // an event mpl core doesn't have, in place of Burn
+    let burn = bytes
+        .windows(10)
+        .position(|window| window == [1, 2, 0, 0, 0, 2, 4, 0, 0, 0])
+        .unwrap()
+        + 5;
+    bytes[burn] = 5;
+    let view = AssetView::new(&bytes).unwrap();
+    assert!(view
+        .external_plugins()
+        .unwrap()
+        .flat_map(|record| record.unwrap().lifecycle_checks)
+        .flat_map(|checks| checks.iter())
+        .any(|check| check == Err(MplCoreError::InvalidExternalPluginAdapter.into())));
+}
+
+#[test]
+fn readers_decode_reference_bytes() {
+    for (_, reference) in plugins() {
+        let bytes = borsh::to_vec(&reference).unwrap();
+        let data = &bytes[1..];
+
+        match reference {
+            r::Plugin::Royalties(expected) => {
+                let royalties = Royalties::from_bytes(data).unwrap();
+                assert_eq!(royalties.basis_points, expected.basis_points);
+                assert_eq!(royalties.creators.len(), expected.creators.len());
+                match (royalties.rule_set, expected.rule_set) {
+                    (RuleSet::None, r::RuleSet::None) => {}
+                    (RuleSet::ProgramAllowList(ours), r::RuleSet::ProgramAllowList(theirs))
+                    | (RuleSet::ProgramDenyList(ours), r::RuleSet::ProgramDenyList(theirs)) => {
+                        assert_eq!(ours, &theirs[..])
+                    }
+                    _ => panic!("wrong rule set"),
+                }
+            }
+            r::Plugin::UpdateDelegate(expected) => {
+                let update_delegate = UpdateDelegate::from_bytes(data).unwrap();
+                assert_eq!(
+                    update_delegate.additional_delegates,
+                    &expected.additional_delegates[..]
+                );
+            }
+            r::Plugin::Attributes(expected) => {
+                let view = AttributesView::from_bytes(data).unwrap();
+                assert_eq!(view.len() as usize, expected.attribute_list.len());
+                for attribute in &expected.attribute_list {
+                    assert_eq!(
+                        view.get(attribute.key.as_bytes()),
+                        Some(attribute.value.as_bytes())
+                    );
+                }
+                assert_eq!(view.get_u64(b"level").unwrap(), Some(7));
+                assert!(view.get_u64(b"class").is_err());
+            }
+            r::Plugin::Edition(expected) => {
+                assert_eq!(Edition::deserialize(data).unwrap().number, expected.number);
+            }
+            r::Plugin::MasterEdition(expected) => {
+                let master_edition = MasterEdition::from_bytes(data).unwrap();
+                assert_eq!(master_edition.max_supply, expected.max_supply);
+                assert_eq!(
+                    master_edition.name,
+                    expected.name.as_ref().map(String::as_bytes)
+                );
+                assert_eq!(
+                    master_edition.uri,
+                    expected.uri.as_ref().map(String::as_bytes)
+                );
+            }
+            r::Plugin::VerifiedCreators(expected) => {
+                let view = VerifiedCreatorsView::from_bytes(data).unwrap();
+                let signatures: Vec<_> = view
+                    .iter()
+                    .map(|signature| (signature.address, signature.verified))
+                    .collect();
+                let expected: Vec<_> = expected
+                    .signatures
+                    .iter()
+                    .map(|signature| (signature.address, signature.verified))
+                    .collect();
+                assert_eq!(signatures, expected);
+            }
+            r::Plugin::Autograph(expected) => {
+                let view = AutographView::from_bytes(data).unwrap();
+                let signatures: Vec<_> = view
+                    .iter()
+                    .map(|signature| (signature.address, signature.message.to_vec()))
+                    .collect();
+                let expected: Vec<_> = expected
+                    .signatures
+                    .iter()
+                    .map(|signature| (signature.address, signature.message.as_bytes().to_vec()))
+                    .collect();
+                assert_eq!(signatures, expected);
+            }
+            r::Plugin::FreezeDelegate(expected) => {
+                assert_eq!(
+                    FreezeDelegate::deserialize(data).unwrap().frozen,
+                    expected.frozen
+                );
+            }
+            r::Plugin::PermanentFreezeDelegate(expected) => {
+                assert_eq!(
+                    PermanentFreezeDelegate::deserialize(data).unwrap().frozen,
+                    expected.frozen
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn unknown_rule_set_is_rejected_before_its_length() {
+    // no length follows, an unknown discriminant must still say so instead of running out of data
+    assert_eq!(
+        RuleSet::from_bytes(&[3]).err(),
+        Some(MplCoreError::InvalidRuleSet.into())
+    );
+    assert_eq!(
+        RuleSet::from_bytes(&[1, 1, 0]).err(),
+        Some(MplCoreError::UnexpectedEndOfData.into())
+    );
+}
+
+#[test]
+fn patches_match_the_rebuilt_plugin() {
+    let attributes = borsh::to_vec(&r::Attributes {
+        attribute_list: vec![
+            r::Attribute {
+                key: "level".into(),
+                value: "7".into(),
+            },
+            r::Attribute {
+                key: "class".into(),
+                value: "mage".into(),
+            },
+        ],
+    })
+    .unwrap();
+    let view = AttributesView::from_bytes(&attributes).unwrap();
+    let ops = [
+        AttributeOp::Set {
+            key: b"level",
+            value: b"8",
+        },
+        AttributeOp::Remove { key: b"class" },
+        AttributeOp::Set {
+            key: b"hp",
+            value: b"100",
+        },
+    ];
+    assert_eq!(view.patch(&ops).len(), Ok(2));
+    assert!(!view.patch(&ops).is_empty());
+    assert!(view
+        .patch(&[
+            AttributeOp::Remove { key: b"level" },
+            AttributeOp::Remove { key: b"class" }
+        ])
+        .is_empty());
+    // the last op on a key wins, whether the key is already there or not
+    assert_encodes(
+        &view.patch(&[
+            AttributeOp::Remove { key: b"level" },
+            AttributeOp::Set {
+                key: b"hp",
+                value: b"1",
+            },
+            AttributeOp::Set {
+                key: b"level",
+                value: b"9",
+            },
+            AttributeOp::Remove { key: b"hp" },
+            AttributeOp::Set {
+                key: b"mp",
+                value: b"5",
+            },
+        ]),
+        &r::Plugin::Attributes(r::Attributes {
+            attribute_list: vec![
+                r::Attribute {
+                    key: "level".into(),
+                    value: "9".into(),
+                },
+                r::Attribute {
+                    key: "class".into(),
+                    value: "mage".into(),
+                },
+                r::Attribute {
+                    key: "mp".into(),
+                    value: "5".into(),
+                },
+            ],
+        }),
+    );
+    assert_encodes(
+        &UpdateAssetPluginPatchV1InstructionData {
+            patch: view.patch(&ops),
+        },
+        &instruction(
+            6,
+            r::UpdatePluginV1Args {
+                plugin: r::Plugin::Attributes(r::Attributes {
+                    attribute_list: vec![
+                        r::Attribute {
+                            key: "level".into(),
+                            value: "8".into(),
+                        },
+                        r::Attribute {
+                            key: "hp".into(),
+                            value: "100".into(),
+                        },
+                    ],
+                }),
+            },
+        ),
+    );
+
+    let verified_creators = borsh::to_vec(&r::VerifiedCreators {
+        signatures: vec![r::VerifiedCreatorsSignature {
+            address: ALICE,
+            verified: false,
+        }],
+    })
+    .unwrap();
+    let view = VerifiedCreatorsView::from_bytes(&verified_creators).unwrap();
+    assert_encodes(
+        &view.verify(&ALICE).unwrap(),
+        &r::Plugin::VerifiedCreators(r::VerifiedCreators {
+            signatures: vec![r::VerifiedCreatorsSignature {
+                address: ALICE,
+                verified: true,
+            }],
+        }),
+    );
+    assert_encodes(
+        &view.add(&BOB, false).unwrap(),
+        &r::Plugin::VerifiedCreators(r::VerifiedCreators {
+            signatures: vec![
+                r::VerifiedCreatorsSignature {
+                    address: ALICE,
+                    verified: false,
+                },
+                r::VerifiedCreatorsSignature {
+                    address: BOB,
+                    verified: false,
+                },
+            ],
+        }),
+    );
+
+    let autograph = borsh::to_vec(&r::Autograph {
+        signatures: vec![r::AutographSignature {
+            address: ALICE,
+            message: "first".into(),
+        }],
+    })
+    .unwrap();
+    let view = AutographView::from_bytes(&autograph).unwrap();
+    assert_encodes(
+        &view.add(&BOB, b"second").unwrap(),
+        &r::Plugin::Autograph(r::Autograph {
+            signatures: vec![
+                r::AutographSignature {
+                    address: ALICE,
+                    message: "first".into(),
+                },
+                r::AutographSignature {
+                    address: BOB,
+                    message: "second".into(),
+                },
+            ],
+        }),
+    );
+
+    let print = PrintEditionV1InstructionData {
+        data_state: DataState::AccountState,
+        name: b"print",
+        uri: b"uri",
+        edition: Edition { number: 5 },
+        edition_authority: Some(PluginAuthority::UpdateAuthority),
+    };
+    assert_encodes(
+        &print,
+        &instruction(
+            0,
+            r::CreateV1Args {
+                data_state: r::DataState::AccountState,
+                name: "print".into(),
+                uri: "uri".into(),
+                plugins: Some(vec![r::PluginAuthorityPair {
+                    plugin: r::Plugin::Edition(r::Edition { number: 5 }),
+                    authority: Some(r::PluginAuthority::UpdateAuthority),
+                }]),
+            },
+        ),
+    );
+}
+
+/// With the `borsh` feature borsh writes exactly what `Serialize` does, and reads back the types that own their data
+#[cfg(feature = "borsh")]
+#[test]
+fn borsh_impls_match_serialize() {
+    fn assert_borsh(ours: &impl borsh::BorshSerialize, reference: &impl BorshSerialize) {
+        assert_eq!(
+            borsh::to_vec(ours).unwrap(),
+            borsh::to_vec(reference).unwrap()
+        );
+    }
+
+    for (plugin, reference) in plugins() {
+        assert_borsh(&plugin, &reference);
+        assert_borsh(
+            &UpdateAssetPluginV1InstructionData { plugin },
+            &instruction(6, r::UpdatePluginV1Args { plugin: reference }),
+        );
+    }
+
+    for (authority, reference) in authorities() {
+        assert_borsh(&authority, &reference);
+        let bytes = borsh::to_vec(&reference).unwrap();
+        assert_eq!(
+            borsh::from_slice::<PluginAuthority>(&bytes).unwrap(),
+            authority
+        );
+    }
+    for (update_authority, reference) in update_authorities() {
+        assert_borsh(&update_authority, &reference);
+        let bytes = borsh::to_vec(&reference).unwrap();
+        assert_eq!(
+            borsh::from_slice::<UpdateAuthority>(&bytes).unwrap(),
+            update_authority
+        );
+    }
+    for key in [
+        r::Key::Uninitialized,
+        r::Key::AssetV1,
+        r::Key::HashedAssetV1,
+        r::Key::PluginHeaderV1,
+        r::Key::PluginRegistryV1,
+        r::Key::CollectionV1,
+    ] {
+        let bytes = borsh::to_vec(&key).unwrap();
+        let ours = borsh::from_slice::<Key>(&bytes).unwrap();
+        assert_borsh(&ours, &key);
+    }
+
+    let header = r::PluginHeaderV1 {
+        key: r::Key::PluginHeaderV1,
+        plugin_registry_offset: 1234,
+    };
+    let ours = borsh::from_slice::<PluginHeaderV1>(&borsh::to_vec(&header).unwrap()).unwrap();
+    assert_eq!(ours.plugin_registry_offset, 1234);
+    assert_borsh(&ours, &header);
+
+    assert_borsh(
+        &CreateAssetV1InstructionData {
+            data_state: DataState::AccountState,
+            name: b"asset",
+            uri: b"https://example.com",
+            plugins: Some(&[PluginAuthorityPair {
+                plugin: Plugin::Edition(Edition { number: 1 }),
+                authority: None,
+            }]),
+        },
+        &instruction(
+            0,
+            r::CreateV1Args {
+                data_state: r::DataState::AccountState,
+                name: "asset".into(),
+                uri: "https://example.com".into(),
+                plugins: Some(vec![r::PluginAuthorityPair {
+                    plugin: r::Plugin::Edition(r::Edition { number: 1 }),
+                    authority: None,
+                }]),
+            },
+        ),
+    );
+    assert_borsh(
+        &TransferV1InstructionData {
+            compression_proof: Some(CompressionProof {
+                owner: ALICE,
+                update_authority: UpdateAuthority::Address(BOB),
+                name: b"compressed",
+                uri: b"uri",
+                seq: 9,
+                plugins: &[],
+            }),
+        },
+        &instruction(
+            14,
+            r::CompressionProofArgs {
+                compression_proof: Some(r::CompressionProof {
+                    owner: ALICE,
+                    update_authority: r::UpdateAuthority::Address(BOB),
+                    name: "compressed".into(),
+                    uri: "uri".into(),
+                    seq: 9,
+                    plugins: vec![],
+                }),
+            },
+        ),
+    );
+
+    // unknown discriminants and missing pubkeys
+    assert!(borsh::from_slice::<Key>(&[6]).is_err());
+    assert!(borsh::from_slice::<PluginAuthority>(&[4]).is_err());
+    assert!(borsh::from_slice::<PluginAuthority>(&[3, 1, 2]).is_err());
+    assert!(borsh::from_slice::<UpdateAuthority>(&[3]).is_err());
+    assert!(borsh::from_slice::<UpdateAuthority>(&[2]).is_err());
+    assert!(borsh::from_slice::<PluginHeaderV1>(&[1, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+}
+
+#[cfg(feature = "borsh")]
+#[test]
+fn owned_mirrors_round_trip_through_borsh() {
+    use mpl_core_pinocchio::data::owned::{
+        OwnedBurnAssetV1InstructionData, OwnedBurnCollectionV1InstructionData,
+        OwnedCompressionProof, OwnedCreateAssetV1InstructionData,
+        OwnedCreateCollectionV1InstructionData, OwnedPlugin, OwnedTransferV1InstructionData,
+        OwnedUpdateAssetPluginV1InstructionData, OwnedUpdateCollectionPluginV1InstructionData,
+    };
+
+    /// Reads what the reference wrote and checks it writes back the same bytes
+    fn round_trip<T: borsh::BorshDeserialize + borsh::BorshSerialize>(
+        reference: &impl BorshSerialize,
+    ) -> T {
+        let bytes = borsh::to_vec(reference).unwrap();
+        let ours = borsh::from_slice::<T>(&bytes).unwrap();
+        assert_eq!(borsh::to_vec(&ours).unwrap(), bytes);
+        ours
+    }
+
+    let ref_plugins: Vec<_> = plugins()
+        .into_iter()
+        .map(|(_, reference)| reference)
+        .collect();
+
+    for plugin in &ref_plugins {
+        round_trip::<OwnedPlugin>(plugin);
+        round_trip::<OwnedUpdateAssetPluginV1InstructionData>(&instruction(
+            6,
+            r::UpdatePluginV1Args {
+                plugin: plugin.clone(),
+            },
+        ));
+        round_trip::<OwnedUpdateCollectionPluginV1InstructionData>(&instruction(
+            7,
+            r::UpdatePluginV1Args {
+                plugin: plugin.clone(),
+            },
+        ));
+    }
+    assert_eq!(
+        round_trip::<OwnedPlugin>(&r::Plugin::Edition(r::Edition { number: 7 })),
+        OwnedPlugin::Edition(Edition { number: 7 })
+    );
+
+    // every plugin, with and without an authority
+    let ref_pairs: Vec<_> = ref_plugins
+        .iter()
+        .enumerate()
+        .map(|(index, plugin)| r::PluginAuthorityPair {
+            plugin: plugin.clone(),
+            authority: (index % 2 == 0).then_some(r::PluginAuthority::Address { address: BOB }),
+        })
+        .collect();
+    for with_plugins in [false, true] {
+        let create = round_trip::<OwnedCreateAssetV1InstructionData>(&instruction(
+            0,
+            r::CreateV1Args {
+                data_state: r::DataState::LedgerState,
+                name: "asset".into(),
+                uri: "https://example.com".into(),
+                plugins: with_plugins.then(|| ref_pairs.clone()),
+            },
+        ));
+        assert_eq!(create.data_state, DataState::LedgerState);
+        assert_eq!(create.name, "asset");
+        assert_eq!(
+            create.plugins.map(|plugins| plugins.len()),
+            with_plugins.then_some(ref_pairs.len())
+        );
+
+        round_trip::<OwnedCreateCollectionV1InstructionData>(&instruction(
+            1,
+            r::CreateCollectionV1Args {
+                name: "collection".into(),
+                uri: "".into(),
+                plugins: with_plugins.then(|| ref_pairs.clone()),
+            },
+        ));
+    }
+
+    let ref_proof = r::CompressionProof {
+        owner: ALICE,
+        update_authority: r::UpdateAuthority::Collection(BOB),
+        name: "compressed".into(),
+        uri: "uri".into(),
+        seq: 9,
+        plugins: ref_plugins
+            .iter()
+            .enumerate()
+            .map(|(index, plugin)| r::HashablePluginSchema {
+                index: index as u64,
+                authority: r::PluginAuthority::Owner,
+                plugin: plugin.clone(),
+            })
+            .collect(),
+    };
+    let proof = round_trip::<OwnedCompressionProof>(&ref_proof);
+    assert_eq!(proof.update_authority, UpdateAuthority::Collection(BOB));
+    assert_eq!(proof.plugins.len(), ref_plugins.len());
+
+    for with_proof in [false, true] {
+        let args = r::CompressionProofArgs {
+            compression_proof: with_proof.then(|| ref_proof.clone()),
+        };
+        let transfer = round_trip::<OwnedTransferV1InstructionData>(&instruction(14, args.clone()));
+        assert_eq!(transfer.compression_proof.is_some(), with_proof);
+        round_trip::<OwnedBurnAssetV1InstructionData>(&instruction(12, args.clone()));
+        round_trip::<OwnedBurnCollectionV1InstructionData>(&instruction(13, args));
+    }
+
+    // another instruction's discriminant, unknown plugin types, data states and rule sets
+    let transfer = borsh::to_vec(&instruction(
+        14,
+        r::CompressionProofArgs {
+            compression_proof: None,
+        },
+    ))
+    .unwrap();
+    assert!(borsh::from_slice::<OwnedTransferV1InstructionData>(&transfer).is_ok());
+    assert!(borsh::from_slice::<OwnedBurnAssetV1InstructionData>(&transfer).is_err());
+    assert!(borsh::from_slice::<OwnedPlugin>(&[19]).is_err());
+    assert!(borsh::from_slice::<OwnedPlugin>(&[0, 0, 0, 0, 0, 0, 0, 3]).is_err());
+    let mut create = borsh::to_vec(&instruction(
+        0,
+        r::CreateV1Args {
+            data_state: r::DataState::AccountState,
+            name: "".into(),
+            uri: "".into(),
+            plugins: None,
+        },
+    ))
+    .unwrap();
+    create[1] = 2;
+    assert!(borsh::from_slice::<OwnedCreateAssetV1InstructionData>(&create).is_err());
+}